@@ -0,0 +1,103 @@
+use crate::render::appearance;
+use crate::sgr::apply_sgr;
+use crate::width::escape_len;
+use crate::StyleSpec;
+
+/// An iterator over the runs of text in a string containing escape
+/// sequences, each with the style the terminal displays it in, as
+/// returned by `parse_ansi`.
+#[derive(Clone, Debug)]
+pub struct AnsiSegments<'a> {
+    rest: &'a str,
+    spec: StyleSpec,
+}
+
+impl<'a> Iterator for AnsiSegments<'a> {
+    type Item = (StyleSpec, &'a str);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.rest.is_empty() {
+            if self.rest.starts_with('\x1b') {
+                let (escape, rest) = self.rest.split_at(escape_len(self.rest));
+                self.rest = rest;
+                if let Some(params) = escape.strip_prefix("\x1b[").and_then(|e| e.strip_suffix('m')) {
+                    let mut spec = self.spec;
+                    if apply_sgr(&mut spec, params).is_ok() {
+                        self.spec = spec;
+                    }
+                }
+                continue;
+            }
+            let end = self.rest.find('\x1b').unwrap_or(self.rest.len());
+            let (text, rest) = self.rest.split_at(end);
+            self.rest = rest;
+            return Some((appearance(&self.spec), text));
+        }
+        None
+    }
+}
+
+/// Split `s`, such as captured output of a command run with colors
+/// forced on, into runs of text, each with the style that the SGR escape
+/// sequences before it leave the terminal in, for re-rendering the output
+/// elsewhere or testing the styles a program emits.
+///
+/// Each style is the complete appearance of its text, as `parse_sgr`
+/// reads the parameters of every escape sequence so far, without reset
+/// or cleared attributes, so plain text has a plain spec. Other escape
+/// sequences, such as cursor movement and hyperlinks, are skipped, as are
+/// SGR sequences that `parse_sgr` rejects. The escape sequences themselves
+/// never appear in the text; to drop them without the styles, use
+/// `strip_ansi`.
+///
+/// # Examples
+///
+///     use colorparse::{parse_ansi, parse_spec, StyleSpec};
+///
+///     let segments: Vec<_> = parse_ansi("\x1b[1;31merror\x1b[22m:\x1b[0m ok").collect();
+///     assert_eq!(segments, [
+///         (parse_spec("bold red").unwrap(), "error"),
+///         (parse_spec("red").unwrap(), ":"),
+///         (StyleSpec::new(), " ok"),
+///     ]);
+pub fn parse_ansi(s: &str) -> AnsiSegments<'_> {
+    AnsiSegments { rest: s, spec: StyleSpec::new() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::global::lock_override;
+    use crate::{paint, parse_spec, render, set_override};
+
+    fn segments(s: &str) -> Vec<(StyleSpec, &str)> {
+        parse_ansi(s).collect()
+    }
+
+    #[test]
+    fn test_parse_ansi() {
+        let spec = |s| parse_spec(s).unwrap();
+        assert_eq!(segments(""), []);
+        assert_eq!(segments("plain"), [(StyleSpec::new(), "plain")]);
+        assert_eq!(segments("\x1b[4m\x1b[4:3;38;5;208ma\x1b[24;59mb\x1b[m"),
+                   [(spec("curly-ul 208"), "a"), (spec("208"), "b")]);
+        assert_eq!(segments("\x1b[58;5;1;53mx\x1b[55;39;49my"), [(spec("overline ul=red"), "x"), (spec("ul=red"), "y")]);
+        assert_eq!(segments("\x1b[1;32;41m\x1b[0;2mx"), [(spec("dim"), "x")]);
+        assert_eq!(segments("\x1b[31m\x1b[?25l\x1b]8;;https://example.com\x1b\\link\x1b[1;300m!\x1b[32"),
+                   [(spec("red"), "link"), (spec("red"), "!")]);
+    }
+
+    #[test]
+    fn test_parse_ansi_round_trip() {
+        let _lock = lock_override();
+        set_override(Some(true));
+        let styles = ["bold red", "ul #102030 blue", "", "reverse 208"];
+        let rendered = render(styles.iter().map(|s| (crate::parse(s).unwrap(), "x")));
+        let read: Vec<StyleSpec> = parse_ansi(&rendered).map(|(spec, _)| spec).collect();
+        let expected: Vec<StyleSpec> = styles.iter().map(|s| parse_spec(s).unwrap()).collect();
+        assert_eq!(read, expected);
+        let painted = paint(crate::parse("dim italic").unwrap(), "y").to_string();
+        assert_eq!(parse_ansi(&painted).collect::<Vec<_>>(), [(parse_spec("dim italic").unwrap(), "y")]);
+        set_override(None);
+    }
+}
@@ -0,0 +1,136 @@
+//! Parsing Git-syntax specs into `anstyle` styles.
+//!
+//! `anstyle` is the style type shared by `clap`, `anstream`, and the rest
+//! of their ecosystem. This module converts a `StyleSpec` into an
+//! `anstyle::Style`, which can hold the underline color and shape, so this
+//! crate can parse the styles of any program built on that stack;
+//! `colorparse::parse_anstyle` is this module's `parse`.
+//!
+//! # Examples
+//!
+//!     # extern crate anstyle;
+//!     # extern crate colorparse;
+//!     use anstyle::{AnsiColor, Style};
+//!
+//!     # fn main() {
+//!     let style = colorparse::parse_anstyle("bold red blue").unwrap();
+//!     assert_eq!(style, Style::new().fg_color(Some(AnsiColor::Red.into())).bg_color(Some(AnsiColor::Blue.into())).bold());
+//!     println!("{style}Bold red on blue{style:#}");
+//!     # }
+
+use ansi_term::Color;
+use anstyle::{AnsiColor, Ansi256Color, Color as AnstyleColor, Effects, RgbColor, Style as AnstyleStyle};
+
+use crate::style_builder::build;
+use crate::{parse_spec, Attribute, ColorValue, Error, StyleBuilder, StyleSpec, UnderlineStyle};
+
+/// The `anstyle::AnsiColor` for each of colors 0 through 15.
+const ANSI: [AnsiColor; 16] = [
+    AnsiColor::Black, AnsiColor::Red, AnsiColor::Green, AnsiColor::Yellow,
+    AnsiColor::Blue, AnsiColor::Magenta, AnsiColor::Cyan, AnsiColor::White,
+    AnsiColor::BrightBlack, AnsiColor::BrightRed, AnsiColor::BrightGreen, AnsiColor::BrightYellow,
+    AnsiColor::BrightBlue, AnsiColor::BrightMagenta, AnsiColor::BrightCyan, AnsiColor::BrightWhite,
+];
+
+/// Convert `color` to the `anstyle::Color` that gives the escape sequence
+/// Git writes for it, with colors 0–15 as `AnsiColor`.
+pub fn to_anstyle_color(color: Color) -> AnstyleColor {
+    match color {
+        Color::Black => AnsiColor::Black.into(),
+        Color::Red => AnsiColor::Red.into(),
+        Color::Green => AnsiColor::Green.into(),
+        Color::Yellow => AnsiColor::Yellow.into(),
+        Color::Blue => AnsiColor::Blue.into(),
+        Color::Purple => AnsiColor::Magenta.into(),
+        Color::Cyan => AnsiColor::Cyan.into(),
+        Color::White => AnsiColor::White.into(),
+        Color::Fixed(n) if n < 16 => ANSI[n as usize].into(),
+        Color::Fixed(n) => Ansi256Color(n).into(),
+        Color::RGB(r, g, b) => RgbColor(r, g, b).into(),
+    }
+}
+
+/// Parse a string in Git's color configuration syntax, as `parse_spec`
+/// does, into an `anstyle::Style`.
+pub fn parse(s: &str) -> Result<AnstyleStyle, Error> {
+    parse_spec(s).map(AnstyleStyle::from)
+}
+
+/// Builds an `anstyle::Style`, with the underline shapes as
+/// `Effects::CURLY_UNDERLINE` and the like.
+impl StyleBuilder for AnstyleStyle {
+    fn set_fg(&mut self, color: ColorValue) {
+        *self = self.fg_color(Some(to_anstyle_color(color.into())));
+    }
+
+    fn set_bg(&mut self, color: ColorValue) {
+        *self = self.bg_color(Some(to_anstyle_color(color.into())));
+    }
+
+    fn set_attr(&mut self, attribute: Attribute) {
+        let effect = match attribute {
+            Attribute::Bold => Effects::BOLD,
+            Attribute::Dimmed => Effects::DIMMED,
+            Attribute::Italic => Effects::ITALIC,
+            Attribute::Underline => Effects::UNDERLINE,
+            Attribute::Blink => Effects::BLINK,
+            Attribute::Reverse => Effects::INVERT,
+            Attribute::Hidden => Effects::HIDDEN,
+            Attribute::Strikethrough => Effects::STRIKETHROUGH,
+            Attribute::Overline | Attribute::RapidBlink => return,
+        };
+        *self = self.effects(self.get_effects() | effect);
+    }
+
+    fn set_underline_style(&mut self, style: UnderlineStyle) {
+        let shape = match style {
+            UnderlineStyle::Single => return,
+            UnderlineStyle::Double => Effects::DOUBLE_UNDERLINE,
+            UnderlineStyle::Curly => Effects::CURLY_UNDERLINE,
+            UnderlineStyle::Dotted => Effects::DOTTED_UNDERLINE,
+            UnderlineStyle::Dashed => Effects::DASHED_UNDERLINE,
+        };
+        *self = self.effects(self.get_effects().remove(Effects::UNDERLINE) | shape);
+    }
+
+    fn set_underline_color(&mut self, color: ColorValue) {
+        *self = self.underline_color(Some(to_anstyle_color(color.into())));
+    }
+}
+
+/// Converts to an `anstyle::Style`, as `StyleBuilder` builds it, dropping
+/// overline, rapid blink, cleared attributes, reset, and default colors.
+impl From<StyleSpec> for AnstyleStyle {
+    fn from(spec: StyleSpec) -> Self {
+        build(&spec)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let style = AnstyleStyle::new;
+        assert_eq!(parse("ul dim 208 #102030"),
+                   Ok(style().fg_color(Some(Ansi256Color(208).into())).bg_color(Some(RgbColor(16, 32, 48).into())).underline().dimmed()));
+        assert_eq!(parse("conceal strike blink reverse italic magenta 15"),
+                   Ok(style().fg_color(Some(AnsiColor::Magenta.into())).bg_color(Some(AnsiColor::BrightWhite.into()))
+                      .italic().blink().invert().hidden().strikethrough()));
+        assert_eq!(parse("curly-ul ul=1"),
+                   Ok(style().effects(Effects::CURLY_UNDERLINE).underline_color(Some(AnsiColor::Red.into()))));
+        assert_eq!(parse("reset default brightred nobold overline rapidblink"), Ok(style().bg_color(Some(AnsiColor::BrightRed.into()))));
+        assert_eq!(parse(""), Ok(style()));
+        assert!(parse("bold sparkly").is_err());
+    }
+
+    #[test]
+    fn test_escape_sequences() {
+        for s in ["bold red blue", "dim ul 9 208", "italic #ff8000 normal", "reverse strike white black", "double-ul ul=#010203"].iter() {
+            let rendered = parse(s).unwrap().render().to_string();
+            let params: Vec<&str> = rendered.split("\x1b[").filter(|p| !p.is_empty()).map(|p| p.trim_end_matches('m')).collect();
+            assert_eq!(crate::parse_sgr(&params.join(";")), crate::parse_spec(s), "{}", s);
+        }
+    }
+}
@@ -0,0 +1,152 @@
+use ansi_term::Style;
+
+use crate::{downgrade_style, Attribute, ColorLevel};
+
+/// What a terminal can display: a color level, and the set of attributes
+/// it renders.
+///
+/// # Examples
+///
+///     use colorparse::{Attribute, Capabilities, ColorLevel};
+///
+///     let caps = Capabilities::new(ColorLevel::Ansi256).without(Attribute::Italic);
+///     assert_eq!(caps.color_level(), ColorLevel::Ansi256);
+///     assert!(caps.supports(Attribute::Bold));
+///     assert!(!caps.supports(Attribute::Italic));
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Capabilities {
+    color_level: ColorLevel,
+    /// One bit per attribute, indexed by its position in `Attribute::ALL`.
+    attributes: u16,
+}
+
+impl Capabilities {
+    /// Create a report for a terminal that displays colors up to `level`
+    /// and every attribute.
+    pub fn new(level: ColorLevel) -> Self {
+        Capabilities { color_level: level, attributes: !0 }
+    }
+
+    /// Mark `attribute` as one the terminal does not render.
+    pub fn without(mut self, attribute: Attribute) -> Self {
+        self.attributes &= !(1 << attribute as u16);
+        self
+    }
+
+    /// Return the highest color level the terminal displays.
+    pub fn color_level(&self) -> ColorLevel {
+        self.color_level
+    }
+
+    /// Return true if the terminal renders `attribute`.
+    pub fn supports(&self, attribute: Attribute) -> bool {
+        self.attributes & (1 << attribute as u16) != 0
+    }
+}
+
+impl Default for Capabilities {
+    fn default() -> Self {
+        Self::new(ColorLevel::TrueColor)
+    }
+}
+
+/// The attribute suggested in place of one the terminal does not render.
+fn substitute(attribute: Attribute) -> Option<Attribute> {
+    match attribute {
+        Attribute::Italic => Some(Attribute::Underline),
+        Attribute::Blink => Some(Attribute::Bold),
+        Attribute::Strikethrough => Some(Attribute::Dimmed),
+        _ => None,
+    }
+}
+
+/// A slot that a terminal cannot display as written, as reported by
+/// `Theme::check`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Finding {
+    /// The name of the slot.
+    pub name: String,
+    /// The style of the slot, as written in the theme.
+    pub style: Style,
+    /// The style the terminal will actually display: the colors
+    /// downgraded to its color level, without the attributes it does not
+    /// render.
+    pub displayed: Style,
+    /// The attributes of the style that the terminal will not render.
+    pub dropped: Vec<Attribute>,
+    /// A portable replacement for the style: `displayed`, plus a supported
+    /// attribute standing in for each dropped one where there is a
+    /// reasonable stand-in (`ul` for `italic`, `bold` for `blink`, and
+    /// `dim` for `strike`).
+    pub suggestion: Style,
+}
+
+impl Finding {
+    /// Return true if the terminal will show different colors than the
+    /// style asks for.
+    pub fn is_downgraded(&self) -> bool {
+        self.style.foreground != self.displayed.foreground || self.style.background != self.displayed.background
+    }
+}
+
+pub(crate) fn check(name: &str, style: Style, caps: &Capabilities) -> Option<Finding> {
+    let dropped: Vec<Attribute> = Attribute::ALL.iter().cloned()
+        .filter(|&a| a.is_set(&style) && !caps.supports(a))
+        .collect();
+    let mut displayed = downgrade_style(style, caps.color_level);
+    for &attribute in &dropped {
+        displayed = without(displayed, attribute);
+    }
+    if displayed == style {
+        return None;
+    }
+    let suggestion = dropped.iter()
+        .filter_map(|&a| substitute(a))
+        .filter(|&a| caps.supports(a))
+        .fold(displayed, |style, a| a.apply(style));
+    Some(Finding { name: name.to_string(), style, displayed, dropped, suggestion })
+}
+
+fn without(style: Style, attribute: Attribute) -> Style {
+    let mut style = style;
+    let flag = match attribute {
+        Attribute::Bold => &mut style.is_bold,
+        Attribute::Dimmed => &mut style.is_dimmed,
+        Attribute::Italic => &mut style.is_italic,
+        Attribute::Underline => &mut style.is_underline,
+        Attribute::Blink => &mut style.is_blink,
+        Attribute::Reverse => &mut style.is_reverse,
+        Attribute::Hidden => &mut style.is_hidden,
+        Attribute::Strikethrough => &mut style.is_strikethrough,
+        Attribute::Overline | Attribute::RapidBlink => return style,
+    };
+    *flag = false;
+    style
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ansi_term::Color::*;
+
+    #[test]
+    fn test_check() {
+        let caps = Capabilities::new(ColorLevel::Ansi16).without(Attribute::Italic).without(Attribute::Blink)
+            .without(Attribute::Bold);
+        assert_eq!(check("plain", Red.bold().on(Fixed(9)), &Capabilities::default()), None);
+        assert_eq!(check("plain", Red.underline(), &caps), None);
+
+        let finding = check("error", RGB(0xff, 0, 0).italic(), &caps).unwrap();
+        assert_eq!(finding.name, "error");
+        assert_eq!(finding.displayed, Red.normal());
+        assert_eq!(finding.dropped, vec![Attribute::Italic]);
+        assert_eq!(finding.suggestion, Red.underline());
+        assert!(finding.is_downgraded());
+
+        let finding = check("alert", Red.blink().strikethrough(), &caps).unwrap();
+        assert_eq!(finding.displayed, Red.strikethrough());
+        assert_eq!(finding.dropped, vec![Attribute::Blink]);
+        assert_eq!(finding.suggestion, Red.strikethrough());
+        assert!(!finding.is_downgraded());
+    }
+}
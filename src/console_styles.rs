@@ -0,0 +1,110 @@
+//! Parsing Git-syntax specs into `console` styles.
+//!
+//! `console` styles the output of `indicatif` and `dialoguer`, among
+//! others. This module converts a `StyleSpec` into a `console::Style`, so
+//! that progress bars and prompts can take their styles from Git-syntax
+//! config.
+//!
+//! # Examples
+//!
+//!     # extern crate colorparse;
+//!     # extern crate console;
+//!     use console::Style;
+//!
+//!     # fn main() {
+//!     let style = colorparse::console_styles::parse("bold red blue").unwrap();
+//!     assert_eq!(style, Style::new().red().on_blue().bold());
+//!     println!("{}", style.apply_to("Bold red on blue"));
+//!     # }
+
+use ansi_term::Color;
+use console::{Attribute as ConsoleAttribute, Color as ConsoleColor, Style as ConsoleStyle};
+
+use crate::style_builder::build;
+use crate::{parse_spec, Attribute, ColorValue, Error, StyleBuilder, StyleSpec};
+
+/// Convert `color` to the `console::Color` that gives the same escape
+/// sequence.
+pub fn to_console_color(color: Color) -> ConsoleColor {
+    match color {
+        Color::Black => ConsoleColor::Black,
+        Color::Red => ConsoleColor::Red,
+        Color::Green => ConsoleColor::Green,
+        Color::Yellow => ConsoleColor::Yellow,
+        Color::Blue => ConsoleColor::Blue,
+        Color::Purple => ConsoleColor::Magenta,
+        Color::Cyan => ConsoleColor::Cyan,
+        Color::White => ConsoleColor::White,
+        Color::Fixed(n) => ConsoleColor::Color256(n),
+        Color::RGB(r, g, b) => ConsoleColor::TrueColor(r, g, b),
+    }
+}
+
+/// Parse a string in Git's color configuration syntax, as `parse_spec`
+/// does, into a `console::Style`.
+pub fn parse(s: &str) -> Result<ConsoleStyle, Error> {
+    parse_spec(s).map(ConsoleStyle::from)
+}
+
+/// Builds a `console::Style`, with rapid blink as `Attribute::BlinkFast`.
+impl StyleBuilder for ConsoleStyle {
+    fn set_fg(&mut self, color: ColorValue) {
+        *self = self.clone().fg(to_console_color(color.into()));
+    }
+
+    fn set_bg(&mut self, color: ColorValue) {
+        *self = self.clone().bg(to_console_color(color.into()));
+    }
+
+    fn set_attr(&mut self, attribute: Attribute) {
+        let attribute = match attribute {
+            Attribute::Bold => ConsoleAttribute::Bold,
+            Attribute::Dimmed => ConsoleAttribute::Dim,
+            Attribute::Italic => ConsoleAttribute::Italic,
+            Attribute::Underline => ConsoleAttribute::Underlined,
+            Attribute::Blink => ConsoleAttribute::Blink,
+            Attribute::RapidBlink => ConsoleAttribute::BlinkFast,
+            Attribute::Reverse => ConsoleAttribute::Reverse,
+            Attribute::Hidden => ConsoleAttribute::Hidden,
+            Attribute::Strikethrough => ConsoleAttribute::StrikeThrough,
+            Attribute::Overline => return,
+        };
+        *self = self.clone().attr(attribute);
+    }
+}
+
+/// Converts to a `console::Style`, as `StyleBuilder` builds it, dropping
+/// the underline color and shape, overline, cleared attributes, reset, and
+/// default colors.
+impl From<StyleSpec> for ConsoleStyle {
+    fn from(spec: StyleSpec) -> Self {
+        build(&spec)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(parse("ul dim 208 #102030"), Ok(ConsoleStyle::new().color256(208).on_true_color(16, 32, 48).underlined().dim()));
+        assert_eq!(parse("conceal strike rapidblink reverse italic magenta"),
+                   Ok(ConsoleStyle::new().magenta().italic().blink_fast().reverse().hidden().strikethrough()));
+        assert_eq!(parse("default brightred"), Ok(ConsoleStyle::new().on_color256(9)));
+        assert_eq!(parse("nobold overline curly-ul ul=red"), Ok(ConsoleStyle::new().underlined()));
+        assert_eq!(parse(""), Ok(ConsoleStyle::new()));
+        assert!(parse("bold sparkly").is_err());
+    }
+
+    #[test]
+    fn test_escape_sequences() {
+        for s in ["red blue", "black white", "8 15", "bold ul 208 #ff8000", "italic reverse strike"].iter() {
+            let painted = parse(s).unwrap().force_styling(true).apply_to("x").to_string();
+            let (sgr, rest) = painted.split_at(painted.find('x').unwrap());
+            let params: Vec<&str> = sgr.split("\x1b[").filter(|p| !p.is_empty()).map(|p| p.trim_end_matches('m')).collect();
+            assert_eq!(crate::parse_sgr(&params.join(";")), crate::parse_spec(s), "{}", s);
+            assert_eq!(rest, "x\x1b[0m");
+        }
+    }
+}
@@ -0,0 +1,151 @@
+//! Parsing Git-syntax specs into `crossterm` styles.
+//!
+//! `crossterm` is the terminal backend of many TUIs. This module converts
+//! a `StyleSpec` into a `crossterm::style::ContentStyle`, which can hold
+//! nearly everything a spec can: Git's `default` color, the underline
+//! color and shape, overline, and rapid blink.
+//!
+//! # Examples
+//!
+//!     # extern crate colorparse;
+//!     # extern crate crossterm;
+//!     use crossterm::style::{Attribute, Color, ContentStyle, Stylize};
+//!
+//!     # fn main() {
+//!     let style = colorparse::crossterm_styles::parse("bold red blue").unwrap();
+//!     assert_eq!(style, ContentStyle::new().dark_red().on_dark_blue().attribute(Attribute::Bold));
+//!     println!("{}", style.apply("Bold red on blue"));
+//!     # }
+
+use ansi_term::Color;
+use crossterm::style::{Attribute as CrosstermAttribute, Color as CrosstermColor, ContentStyle};
+
+use crate::style_builder::build;
+use crate::{parse_spec, Attribute, ColorValue, Error, StyleBuilder, StyleSpec, UnderlineStyle};
+
+/// The `crossterm::style::Color` for each of colors 0 through 15.
+const ANSI: [CrosstermColor; 16] = [
+    CrosstermColor::Black, CrosstermColor::DarkRed, CrosstermColor::DarkGreen, CrosstermColor::DarkYellow,
+    CrosstermColor::DarkBlue, CrosstermColor::DarkMagenta, CrosstermColor::DarkCyan, CrosstermColor::Grey,
+    CrosstermColor::DarkGrey, CrosstermColor::Red, CrosstermColor::Green, CrosstermColor::Yellow,
+    CrosstermColor::Blue, CrosstermColor::Magenta, CrosstermColor::Cyan, CrosstermColor::White,
+];
+
+/// Convert `color` to the `crossterm::style::Color` that gives the same
+/// color, with colors 0–15 as the named colors, so that `red` and `1`
+/// become `DarkRed` and `9` becomes `Red`.
+pub fn to_crossterm_color(color: Color) -> CrosstermColor {
+    match color {
+        Color::Black => CrosstermColor::Black,
+        Color::Red => CrosstermColor::DarkRed,
+        Color::Green => CrosstermColor::DarkGreen,
+        Color::Yellow => CrosstermColor::DarkYellow,
+        Color::Blue => CrosstermColor::DarkBlue,
+        Color::Purple => CrosstermColor::DarkMagenta,
+        Color::Cyan => CrosstermColor::DarkCyan,
+        Color::White => CrosstermColor::Grey,
+        Color::Fixed(n) if n < 16 => ANSI[n as usize],
+        Color::Fixed(n) => CrosstermColor::AnsiValue(n),
+        Color::RGB(r, g, b) => CrosstermColor::Rgb { r, g, b },
+    }
+}
+
+/// Parse a string in Git's color configuration syntax, as `parse_spec`
+/// does, into a `crossterm::style::ContentStyle`.
+pub fn parse(s: &str) -> Result<ContentStyle, Error> {
+    parse_spec(s).map(ContentStyle::from)
+}
+
+/// Builds a `crossterm::style::ContentStyle`, with a `default` color as
+/// `Color::Reset` and the underline shapes as `Attribute::Undercurled`
+/// and the like.
+impl StyleBuilder for ContentStyle {
+    fn set_fg(&mut self, color: ColorValue) {
+        self.foreground_color = Some(to_crossterm_color(color.into()));
+    }
+
+    fn set_bg(&mut self, color: ColorValue) {
+        self.background_color = Some(to_crossterm_color(color.into()));
+    }
+
+    fn set_attr(&mut self, attribute: Attribute) {
+        self.attributes.set(match attribute {
+            Attribute::Bold => CrosstermAttribute::Bold,
+            Attribute::Dimmed => CrosstermAttribute::Dim,
+            Attribute::Italic => CrosstermAttribute::Italic,
+            Attribute::Underline => CrosstermAttribute::Underlined,
+            Attribute::Blink => CrosstermAttribute::SlowBlink,
+            Attribute::RapidBlink => CrosstermAttribute::RapidBlink,
+            Attribute::Reverse => CrosstermAttribute::Reverse,
+            Attribute::Hidden => CrosstermAttribute::Hidden,
+            Attribute::Strikethrough => CrosstermAttribute::CrossedOut,
+            Attribute::Overline => CrosstermAttribute::OverLined,
+        });
+    }
+
+    fn set_default_fg(&mut self) {
+        self.foreground_color = Some(CrosstermColor::Reset);
+    }
+
+    fn set_default_bg(&mut self) {
+        self.background_color = Some(CrosstermColor::Reset);
+    }
+
+    fn set_underline_style(&mut self, style: UnderlineStyle) {
+        let shape = match style {
+            UnderlineStyle::Single => return,
+            UnderlineStyle::Double => CrosstermAttribute::DoubleUnderlined,
+            UnderlineStyle::Curly => CrosstermAttribute::Undercurled,
+            UnderlineStyle::Dotted => CrosstermAttribute::Underdotted,
+            UnderlineStyle::Dashed => CrosstermAttribute::Underdashed,
+        };
+        self.attributes.unset(CrosstermAttribute::Underlined);
+        self.attributes.set(shape);
+    }
+
+    fn set_underline_color(&mut self, color: ColorValue) {
+        self.underline_color = Some(to_crossterm_color(color.into()));
+    }
+}
+
+/// Converts to a `crossterm::style::ContentStyle`, as `StyleBuilder`
+/// builds it, dropping cleared attributes and reset.
+impl From<StyleSpec> for ContentStyle {
+    fn from(spec: StyleSpec) -> Self {
+        build(&spec)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::style::Attributes;
+
+    fn style(fg: Option<CrosstermColor>, bg: Option<CrosstermColor>, attributes: &[CrosstermAttribute]) -> ContentStyle {
+        let attributes = attributes.iter().fold(Attributes::default(), |attributes, &a| attributes | a);
+        ContentStyle { foreground_color: fg, background_color: bg, underline_color: None, attributes }
+    }
+
+    #[test]
+    fn test_parse() {
+        use crossterm::style::Attribute::*;
+        use crossterm::style::Color::{self, *};
+
+        assert_eq!(parse("ul dim 208 #102030"), Ok(style(Some(AnsiValue(208)), Some(Rgb { r: 16, g: 32, b: 48 }), &[Underlined, Dim])));
+        assert_eq!(parse("conceal strike blink reverse italic magenta 7"),
+                   Ok(style(Some(DarkMagenta), Some(Grey), &[Italic, SlowBlink, Reverse, Hidden, CrossedOut])));
+        assert_eq!(parse("default brightred rapidblink overline"), Ok(style(Some(Color::Reset), Some(Red), &[RapidBlink, OverLined])));
+        assert_eq!(parse("curly-ul ul=#ff0000"),
+                   Ok(ContentStyle { underline_color: Some(Rgb { r: 255, g: 0, b: 0 }), ..style(None, None, &[Undercurled]) }));
+        assert_eq!(parse("reset nobold white 15"), Ok(style(Some(Grey), Some(White), &[])));
+        assert_eq!(parse(""), Ok(ContentStyle::new()));
+        assert!(parse("bold sparkly").is_err());
+    }
+
+    #[test]
+    fn test_escape_sequences() {
+        let painted = |s| parse(s).unwrap().apply("x").to_string();
+        assert_eq!(painted("bold red"), "\x1b[38;5;1m\x1b[1mx\x1b[0m");
+        assert_eq!(painted("normal #010203 dashed-ul ul=9"), "\x1b[48;2;1;2;3m\x1b[58;5;9m\x1b[4:5mx\x1b[0m");
+    }
+}
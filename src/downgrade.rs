@@ -0,0 +1,190 @@
+use ansi_term::{Color, Style};
+
+use crate::{ColorLevel, Palette};
+
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+/// Return the palette index of a basic color, or of a `Color::Fixed`.
+pub(crate) fn index(color: Color) -> Option<u8> {
+    Some(match color {
+        Color::Black => 0,
+        Color::Red => 1,
+        Color::Green => 2,
+        Color::Yellow => 3,
+        Color::Blue => 4,
+        Color::Purple => 5,
+        Color::Cyan => 6,
+        Color::White => 7,
+        Color::Fixed(n) => n,
+        Color::RGB(..) => return None,
+    })
+}
+
+/// Return the color for a palette index below 16, using the named colors
+/// for the first eight.
+pub(crate) fn from_index(n: u8) -> Color {
+    match n {
+        0 => Color::Black,
+        1 => Color::Red,
+        2 => Color::Green,
+        3 => Color::Yellow,
+        4 => Color::Blue,
+        5 => Color::Purple,
+        6 => Color::Cyan,
+        7 => Color::White,
+        n => Color::Fixed(n),
+    }
+}
+
+/// Return the RGB value of a color, using `ansi` for the 16 basic colors
+/// and the xterm palette for the rest of the 256 colors.
+pub(crate) fn to_rgb(color: Color, ansi: &[(u8, u8, u8); 16]) -> (u8, u8, u8) {
+    match index(color) {
+        None => match color {
+            Color::RGB(r, g, b) => (r, g, b),
+            _ => unreachable!(),
+        },
+        Some(n) if n < 16 => ansi[n as usize],
+        Some(n) if n < 232 => {
+            let n = n - 16;
+            (CUBE_LEVELS[(n / 36) as usize], CUBE_LEVELS[(n / 6 % 6) as usize], CUBE_LEVELS[(n % 6) as usize])
+        }
+        Some(n) => {
+            let v = 8 + 10 * (n - 232);
+            (v, v, v)
+        }
+    }
+}
+
+fn distance((r1, g1, b1): (u8, u8, u8), (r2, g2, b2): (u8, u8, u8)) -> u32 {
+    let d = |a: u8, b: u8| (i32::from(a) - i32::from(b)).pow(2) as u32;
+    d(r1, r2) + d(g1, g2) + d(b1, b2)
+}
+
+fn nearest_cube_level(v: u8) -> usize {
+    (0..CUBE_LEVELS.len())
+        .min_by_key(|&i| (i32::from(CUBE_LEVELS[i]) - i32::from(v)).abs())
+        .unwrap()
+}
+
+fn rgb_to_256(rgb: (u8, u8, u8)) -> u8 {
+    let (r, g, b) = rgb;
+    let (ri, gi, bi) = (nearest_cube_level(r), nearest_cube_level(g), nearest_cube_level(b));
+    let cube = (16 + 36 * ri + 6 * gi + bi) as u8;
+    let average = (u32::from(r) + u32::from(g) + u32::from(b)) / 3;
+    let gray = 232 + (average.saturating_sub(3) / 10).min(23) as u8;
+    let xterm = |n| to_rgb(Color::Fixed(n), &[(0, 0, 0); 16]);
+    if distance(rgb, xterm(gray)) < distance(rgb, xterm(cube)) {
+        gray
+    } else {
+        cube
+    }
+}
+
+fn rgb_to_16(rgb: (u8, u8, u8), ansi: &[(u8, u8, u8); 16]) -> u8 {
+    (0..16u8).min_by_key(|&n| distance(rgb, ansi[n as usize])).unwrap()
+}
+
+/// Convert `color` to the nearest color displayable at `level`, assuming
+/// `palette` for the 16 basic colors.
+pub(crate) fn downgrade_with(color: Color, level: ColorLevel, palette: &Palette) -> Color {
+    if ColorLevel::of(color) <= level {
+        return color;
+    }
+    match level {
+        ColorLevel::TrueColor => color,
+        ColorLevel::Ansi256 => Color::Fixed(rgb_to_256(to_rgb(color, &palette.ansi))),
+        ColorLevel::Ansi16 => from_index(rgb_to_16(to_rgb(color, &palette.ansi), &palette.ansi)),
+    }
+}
+
+/// Convert `color` to the nearest color displayable at `level`.
+///
+/// Colors are approximated using the VGA palette for the 16 basic colors
+/// and the xterm palette for the 256-color range. Colors already within
+/// `level` are returned unchanged.
+///
+/// # Examples
+///
+///     # extern crate ansi_term;
+///     # extern crate colorparse;
+///     use ansi_term::Color;
+///     use colorparse::{downgrade, ColorLevel};
+///
+///     # fn main() {
+///     assert_eq!(downgrade(Color::RGB(0xff, 0, 0), ColorLevel::Ansi256), Color::Fixed(196));
+///     assert_eq!(downgrade(Color::RGB(0xff, 0, 0), ColorLevel::Ansi16), Color::Red);
+///     # }
+pub fn downgrade(color: Color, level: ColorLevel) -> Color {
+    downgrade_with(color, level, &Palette::default())
+}
+
+/// Convert the colors of `style` to the nearest colors displayable at
+/// `level`, keeping its attributes.
+pub fn downgrade_style(style: Style, level: ColorLevel) -> Style {
+    Palette::default().downgrade_style(style, level)
+}
+
+fn canonical(style: Style, level: ColorLevel) -> Style {
+    let canonical_color = |c: Color| match index(c) {
+        Some(n) if n < 16 => from_index(n),
+        _ => c,
+    };
+    let style = downgrade_style(style, level);
+    Style {
+        foreground: style.foreground.map(canonical_color),
+        background: style.background.map(canonical_color),
+        ..style
+    }
+}
+
+/// Return true if `a` and `b` look the same on a terminal limited to
+/// `level`, after downgrading both with `downgrade_style`.
+///
+/// # Examples
+///
+///     use colorparse::{equivalent_at, parse, ColorLevel};
+///
+///     let a = parse("#ff0000 bold").unwrap();
+///     let b = parse("red bold").unwrap();
+///     assert!(equivalent_at(a, b, ColorLevel::Ansi16));
+///     assert!(!equivalent_at(a, b, ColorLevel::TrueColor));
+pub fn equivalent_at(a: Style, b: Style, level: ColorLevel) -> bool {
+    canonical(a, level) == canonical(b, level)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ansi_term::Color::*;
+    use crate::ColorLevel::*;
+
+    #[test]
+    fn test_downgrade() {
+        assert_eq!(downgrade(Red, Ansi16), Red);
+        assert_eq!(downgrade(Fixed(9), Ansi16), Fixed(9));
+        assert_eq!(downgrade(Fixed(196), Ansi16), Red);
+        assert_eq!(downgrade(Fixed(231), Ansi16), Fixed(15));
+        assert_eq!(downgrade(Fixed(16), Ansi16), Black);
+        assert_eq!(downgrade(Fixed(200), Ansi256), Fixed(200));
+        assert_eq!(downgrade(RGB(0, 0, 0), Ansi256), Fixed(16));
+        assert_eq!(downgrade(RGB(255, 255, 255), Ansi256), Fixed(231));
+        assert_eq!(downgrade(RGB(0x80, 0x80, 0x80), Ansi256), Fixed(244));
+        assert_eq!(downgrade(RGB(0x5f, 0x87, 0xaf), Ansi256), Fixed(67));
+        assert_eq!(downgrade(RGB(0, 0xff, 0), Ansi16), Green);
+        assert_eq!(downgrade(RGB(0x20, 0x40, 0x60), TrueColor), RGB(0x20, 0x40, 0x60));
+    }
+
+    #[test]
+    fn test_equivalent_at() {
+        let rgb_red = RGB(0xff, 0, 0).normal();
+        assert!(equivalent_at(rgb_red, Red.normal(), Ansi16));
+        assert!(equivalent_at(rgb_red, Fixed(1).normal(), Ansi16));
+        assert!(equivalent_at(rgb_red, Fixed(196).normal(), Ansi256));
+        assert!(!equivalent_at(rgb_red, Red.normal(), Ansi256));
+        assert!(!equivalent_at(rgb_red, Fixed(196).normal(), TrueColor));
+        assert!(equivalent_at(Red.normal(), Fixed(1).normal(), TrueColor));
+        assert!(!equivalent_at(rgb_red, Red.bold(), Ansi16));
+        assert!(!equivalent_at(rgb_red, Style::new().on(Red), Ansi16));
+    }
+}
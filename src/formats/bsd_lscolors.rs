@@ -0,0 +1,121 @@
+use std::fmt;
+
+use ansi_term::Style;
+
+use crate::downgrade::from_index;
+use crate::Theme;
+
+/// The `LS_COLORS` names of the file types in BSD `LSCOLORS`, in order.
+const KEYS: [&str; 11] = ["di", "ln", "so", "pi", "ex", "bd", "cd", "su", "sg", "tw", "ow"];
+
+/// Parse a BSD `LSCOLORS` value, as used by the `ls` of FreeBSD and macOS,
+/// into a theme keyed by `LS_COLORS` file type codes, ready for
+/// `LsColors::from`.
+///
+/// The value is a string of up to 11 letter pairs, a foreground and a
+/// background for each of directories (`di`), symbolic links (`ln`),
+/// sockets (`so`), pipes (`pi`), executables (`ex`), block and character
+/// devices (`bd` and `cd`), setuid and setgid executables (`su` and `sg`),
+/// and other-writable directories with and without the sticky bit (`tw`
+/// and `ow`). The letters `a` through `h` are black, red, green, brown
+/// (yellow), blue, magenta, cyan, and light grey (white); `A` through `H`
+/// are the same colors in bold, and `x` and `X` the default color and the
+/// same in bold. The digits `0` through `7`, from older versions of `ls`,
+/// also give the colors. File types after the end of a shorter value are
+/// left out of the theme.
+///
+/// # Examples
+///
+///     # extern crate ansi_term;
+///     # extern crate colorparse;
+///     use ansi_term::Color::{Blue, Cyan, Purple};
+///     use colorparse::formats::{parse_bsd_lscolors, LsColors};
+///
+///     # fn main() {
+///     let theme = parse_bsd_lscolors("Exfxcxdxbxegedabagacad").unwrap();
+///     assert_eq!(theme.get("di"), Some(Blue.bold()));
+///     assert_eq!(theme.get("ln"), Some(Purple.normal()));
+///     assert_eq!(theme.get("bd"), Some(Blue.on(Cyan)));
+///     assert_eq!(LsColors::from(theme).theme().len(), 11);
+///     # }
+pub fn parse_bsd_lscolors(s: &str) -> Result<Theme, BsdLscolorsError> {
+    let chars: Vec<char> = s.chars().collect();
+    if !chars.len().is_multiple_of(2) || chars.len() > 2 * KEYS.len() {
+        return Err(BsdLscolorsError::InvalidLength(chars.len()));
+    }
+    let mut theme = Theme::new();
+    for (i, (pair, key)) in chars.chunks(2).zip(KEYS.iter()).enumerate() {
+        let mut style = Style::new();
+        for (j, &c) in pair.iter().enumerate() {
+            let (color, bold) = match c {
+                'a'..='h' => (Some(c as u8 - b'a'), false),
+                'A'..='H' => (Some(c as u8 - b'A'), true),
+                '0'..='7' => (Some(c as u8 - b'0'), false),
+                'x' => (None, false),
+                'X' => (None, true),
+                _ => return Err(BsdLscolorsError::InvalidColor(2 * i + j, c)),
+            };
+            let color = color.map(from_index);
+            if j == 0 { style.foreground = color } else { style.background = color }
+            style.is_bold |= bold;
+        }
+        theme.insert(*key, style);
+    }
+    Ok(theme)
+}
+
+/// Type for errors returned by `parse_bsd_lscolors`.
+#[derive(Debug, PartialEq)]
+pub enum BsdLscolorsError {
+    /// The value has an odd number of characters, or more than 22;
+    /// carries the number.
+    InvalidLength(usize),
+    /// A character is not a color; carries its position, counting from 0,
+    /// and the character.
+    InvalidColor(usize, char),
+}
+
+impl fmt::Display for BsdLscolorsError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::InvalidLength(len) => {
+                write!(fmt, "Error parsing LSCOLORS: {} characters, not an even number up to 22", len)
+            }
+            Self::InvalidColor(i, c) => write!(fmt, "Error parsing LSCOLORS: invalid color {:?} at position {}", c, i),
+        }
+    }
+}
+
+impl std::error::Error for BsdLscolorsError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ansi_term::Color::*;
+
+    #[test]
+    fn test_parse_bsd_lscolors() {
+        let theme = parse_bsd_lscolors("exfxcxdxbxegedabagacad").unwrap();
+        assert_eq!(theme.get("di"), Some(Blue.normal()));
+        assert_eq!(theme.get("pi"), Some(Yellow.normal()));
+        assert_eq!(theme.get("su"), Some(Black.on(Red)));
+        assert_eq!(theme.get("ow"), Some(Black.on(Yellow)));
+        assert_eq!(theme.len(), 11);
+
+        let theme = parse_bsd_lscolors("xXHh6x").unwrap();
+        assert_eq!(theme.get("di"), Some(Style::new().bold()));
+        assert_eq!(theme.get("ln"), Some(White.on(White).bold()));
+        assert_eq!(theme.get("so"), Some(Cyan.normal()));
+        assert_eq!(theme.get("pi"), None);
+        assert!(parse_bsd_lscolors("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_parse_bsd_lscolors_err() {
+        assert_eq!(parse_bsd_lscolors("exf"), Err(BsdLscolorsError::InvalidLength(3)));
+        assert_eq!(parse_bsd_lscolors(&"x".repeat(24)), Err(BsdLscolorsError::InvalidLength(24)));
+        assert_eq!(parse_bsd_lscolors("exfi"), Err(BsdLscolorsError::InvalidColor(3, 'i')));
+        assert_eq!(BsdLscolorsError::InvalidColor(0, '8').to_string(),
+                   "Error parsing LSCOLORS: invalid color '8' at position 0");
+    }
+}
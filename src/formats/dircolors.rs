@@ -0,0 +1,227 @@
+use std::fmt;
+
+use ansi_term::Style;
+
+use super::glob_match;
+use crate::{parse_sgr, Theme};
+
+/// The `dircolors` keywords for file types, and their `LS_COLORS` keys.
+pub(crate) const KEYWORDS: [(&str, &str); 26] = [
+    ("NORMAL", "no"), ("NORM", "no"), ("FILE", "fi"), ("RESET", "rs"), ("DIR", "di"), ("LNK", "ln"),
+    ("LINK", "ln"), ("SYMLINK", "ln"), ("ORPHAN", "or"), ("MISSING", "mi"), ("FIFO", "pi"),
+    ("PIPE", "pi"), ("SOCK", "so"), ("BLK", "bd"), ("BLOCK", "bd"), ("CHR", "cd"), ("CHAR", "cd"),
+    ("DOOR", "do"), ("EXEC", "ex"), ("SETUID", "su"), ("SETGID", "sg"), ("STICKY", "st"),
+    ("OTHER_WRITABLE", "ow"), ("STICKY_OTHER_WRITABLE", "tw"), ("CAPABILITY", "ca"), ("MULTIHARDLINK", "mh"),
+];
+
+/// Keywords that take a value but set no color.
+const IGNORED: [&str; 6] = ["OPTIONS", "COLOR", "EIGHTBIT", "LEFTCODE", "RIGHTCODE", "ENDCODE"];
+
+/// A group of settings that applies to the terminals matching any of its
+/// `TERM` or `COLORTERM` patterns, or to every terminal if it has none.
+#[derive(Clone, Debug, Default, PartialEq)]
+struct Block {
+    terms: Vec<String>,
+    colorterms: Vec<String>,
+    theme: Theme,
+}
+
+impl Block {
+    fn matches(&self, term: &str, colorterm: &str) -> bool {
+        (self.terms.is_empty() && self.colorterms.is_empty())
+            || self.terms.iter().any(|pattern| glob_match(pattern, term))
+            || self.colorterms.iter().any(|pattern| glob_match(pattern, colorterm))
+    }
+}
+
+/// A `dircolors` database, as written by `dircolors --print-database` or
+/// kept in a `~/.dircolors` file.
+///
+/// The database holds a `Theme` for each group of `TERM` and `COLORTERM`
+/// lines, keyed by `LS_COLORS` names: two-letter file type codes such as
+/// `di` and `ln`, and globs such as `*.tar` for file name patterns (a
+/// `.tar` line becomes `*.tar`). `theme_for` combines the themes that
+/// apply to a terminal, as `dircolors` does.
+///
+/// Values that are not SGR parameters, such as `LINK target`, and the
+/// settings unrelated to colors, such as `OPTIONS` and `EIGHTBIT`, are
+/// skipped.
+///
+/// # Examples
+///
+///     # extern crate ansi_term;
+///     # extern crate colorparse;
+///     use ansi_term::Color::{Blue, Red};
+///     use colorparse::formats::Dircolors;
+///
+///     # fn main() {
+///     let database = Dircolors::parse("
+///     ## Global settings
+///     DIR 01;34
+///     TERM xterm*
+///     TERM *color*
+///     .tar 01;31 # archives
+///     ").unwrap();
+///     let theme = database.theme_for("xterm-256color", None);
+///     assert_eq!(theme.get("di"), Some(Blue.bold()));
+///     assert_eq!(theme.get("*.tar"), Some(Red.bold()));
+///     assert_eq!(database.theme_for("vt100", None).get("*.tar"), None);
+///     # }
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Dircolors {
+    blocks: Vec<Block>,
+}
+
+impl Dircolors {
+    /// Parse a `dircolors` database.
+    pub fn parse(s: &str) -> Result<Self, DircolorsError> {
+        let mut blocks = vec![Block::default()];
+        let mut in_terms = false;
+        for (i, line) in s.lines().enumerate() {
+            let line_number = i + 1;
+            let content = line.split('#').next().unwrap_or("").trim();
+            if content.is_empty() {
+                continue;
+            }
+            let mut words = content.split_whitespace();
+            let keyword = words.next().unwrap_or("");
+            let value = match words.next() {
+                Some(value) => value,
+                None => return Err(DircolorsError::MissingValue(line_number, line.to_string())),
+            };
+            let upper = keyword.to_ascii_uppercase();
+            if upper == "TERM" || upper == "COLORTERM" {
+                if !in_terms {
+                    blocks.push(Block::default());
+                    in_terms = true;
+                }
+                let block = blocks.last_mut().unwrap();
+                if upper == "TERM" { &mut block.terms } else { &mut block.colorterms }.push(value.to_string());
+                continue;
+            }
+            in_terms = false;
+            let key = if keyword.starts_with('.') {
+                format!("*{}", keyword)
+            } else if keyword.starts_with('*') {
+                keyword.to_string()
+            } else if let Some(&(_, code)) = KEYWORDS.iter().find(|&&(name, _)| name == upper) {
+                code.to_string()
+            } else if IGNORED.contains(&upper.as_str()) {
+                continue;
+            } else {
+                return Err(DircolorsError::UnknownKeyword(line_number, keyword.to_string()));
+            };
+            if key == "ln" && value.eq_ignore_ascii_case("target") {
+                continue;
+            }
+            match parse_sgr(value) {
+                Ok(spec) => { blocks.last_mut().unwrap().theme.insert(&key, Style::from(spec)); }
+                Err(_) => return Err(DircolorsError::InvalidCode(line_number, value.to_string())),
+            }
+        }
+        Ok(Dircolors { blocks })
+    }
+
+    /// Return the theme `dircolors` would produce for a terminal with the
+    /// given `TERM` and `COLORTERM` values, combining every group that
+    /// applies to it, with later settings taking precedence.
+    pub fn theme_for(&self, term: &str, colorterm: Option<&str>) -> Theme {
+        let mut theme = Theme::new();
+        for block in self.blocks.iter().filter(|block| block.matches(term, colorterm.unwrap_or(""))) {
+            for (name, style) in block.theme.iter() {
+                theme.insert(name, style);
+            }
+        }
+        theme
+    }
+
+    /// Return an iterator over the `TERM` patterns in the database.
+    pub fn terms(&self) -> impl Iterator<Item = &str> {
+        self.blocks.iter().flat_map(|block| block.terms.iter().map(String::as_str))
+    }
+}
+
+/// Type for errors returned by `Dircolors::parse`.
+#[derive(Debug, PartialEq)]
+pub enum DircolorsError {
+    /// A line has a keyword but no value; carries the line number and
+    /// the line.
+    MissingValue(usize, String),
+    /// A line starts with an unknown keyword; carries the line number and
+    /// the keyword.
+    UnknownKeyword(usize, String),
+    /// A value is not a sequence of SGR parameters; carries the line
+    /// number and the value.
+    InvalidCode(usize, String),
+}
+
+impl fmt::Display for DircolorsError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::MissingValue(line, s) => write!(fmt, "Error parsing dircolors line {}: missing value: \"{}\"", line, s),
+            Self::UnknownKeyword(line, word) => {
+                write!(fmt, "Error parsing dircolors line {}: unknown keyword \"{}\"", line, word)
+            }
+            Self::InvalidCode(line, code) => {
+                write!(fmt, "Error parsing dircolors line {}: invalid color code \"{}\"", line, code)
+            }
+        }
+    }
+}
+
+impl std::error::Error for DircolorsError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ansi_term::Color::*;
+
+    const DATABASE: &str = "
+# Configuration file for dircolors.
+COLOR tty
+OPTIONS -F -T 0
+TERM linux
+TERM xterm*
+COLORTERM ?*
+RESET 0 # reset to no color
+DIR 01;34
+LINK target
+FIFO 40;33
+ORPHAN 40;31;01
+EXEC 01;32
+.tar 01;31
+*.tgz 01;31
+*~ 00;90
+TERM vt100
+DIR 00
+";
+
+    #[test]
+    fn test_dircolors() {
+        let database = Dircolors::parse(DATABASE).unwrap();
+        let theme = database.theme_for("xterm-256color", None);
+        assert_eq!(theme.get("rs"), Some(Style::new()));
+        assert_eq!(theme.get("di"), Some(Blue.bold()));
+        assert_eq!(theme.get("ln"), None);
+        assert_eq!(theme.get("pi"), Some(Yellow.on(Black)));
+        assert_eq!(theme.get("or"), Some(Red.on(Black).bold()));
+        assert_eq!(theme.get("*.tar"), Some(Red.bold()));
+        assert_eq!(theme.get("*.tgz"), Some(Red.bold()));
+        assert_eq!(theme.get("*~"), Some(Fixed(8).normal()));
+        assert_eq!(theme.len(), 8);
+
+        assert_eq!(database.theme_for("dumb", Some("truecolor")), theme);
+        assert!(database.theme_for("dumb", None).is_empty());
+        assert_eq!(database.theme_for("vt100", None).get("di"), Some(Style::new()));
+        assert_eq!(database.terms().collect::<Vec<_>>(), ["linux", "xterm*", "vt100"]);
+    }
+
+    #[test]
+    fn test_dircolors_err() {
+        assert_eq!(Dircolors::parse("DIR 01;34\nDIR\n"), Err(DircolorsError::MissingValue(2, "DIR".to_string())));
+        assert_eq!(Dircolors::parse("FOLDER 01;34"), Err(DircolorsError::UnknownKeyword(1, "FOLDER".to_string())));
+        assert_eq!(Dircolors::parse("\n.tar 01;x"), Err(DircolorsError::InvalidCode(2, "01;x".to_string())));
+        assert_eq!(DircolorsError::UnknownKeyword(1, "FOLDER".to_string()).to_string(),
+                   "Error parsing dircolors line 1: unknown keyword \"FOLDER\"");
+    }
+}
@@ -0,0 +1,70 @@
+use ansi_term::{Color, Style};
+
+use crate::downgrade::{index, to_rgb};
+use crate::Palette;
+
+const NAMES: [&str; 8] = ["black", "red", "green", "yellow", "blue", "magenta", "cyan", "white"];
+
+/// Return the `set_color` name for `color`: a basic or `br` bright color
+/// name, or `rrggbb` for the rest of the 256-color palette and 24-bit
+/// colors, which `set_color` has no numbers for.
+fn color_name(color: Color) -> String {
+    match index(color) {
+        Some(n) if n < 8 => NAMES[n as usize].to_string(),
+        Some(n) if n < 16 => format!("br{}", NAMES[n as usize - 8]),
+        _ => {
+            let (r, g, b) = to_rgb(color, &Palette::VGA.ansi);
+            format!("{:02x}{:02x}{:02x}", r, g, b)
+        }
+    }
+}
+
+/// Return the arguments to fish's `set_color` command that switch to
+/// `style`, such as `--bold red --background blue`.
+///
+/// `set_color` has no options for blink, hidden, or strikethrough text, so
+/// those attributes are dropped. A plain style gives `normal`.
+///
+/// # Examples
+///
+///     let style = colorparse::parse("bold red blue").unwrap();
+///     assert_eq!(colorparse::formats::fish_set_color(&style), "--bold red --background blue");
+///
+///     let style = colorparse::parse("ul 208").unwrap();
+///     assert_eq!(colorparse::formats::fish_set_color(&style), "--underline ff8700");
+pub fn fish_set_color(style: &Style) -> String {
+    let mut args: Vec<String> = [
+        (style.is_bold, "--bold"),
+        (style.is_dimmed, "--dim"),
+        (style.is_italic, "--italics"),
+        (style.is_underline, "--underline"),
+        (style.is_reverse, "--reverse"),
+    ].iter().filter(|&&(set, _)| set).map(|&(_, arg)| arg.to_string()).collect();
+    match style.foreground {
+        Some(fg) => args.push(color_name(fg)),
+        None if args.is_empty() && style.background.is_none() => args.push("normal".to_string()),
+        None => {}
+    }
+    if let Some(bg) = style.background {
+        args.push("--background".to_string());
+        args.push(color_name(bg));
+    }
+    args.join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ansi_term::Color::*;
+
+    #[test]
+    fn test_fish_set_color() {
+        assert_eq!(fish_set_color(&Style::new()), "normal");
+        assert_eq!(fish_set_color(&Purple.normal()), "magenta");
+        assert_eq!(fish_set_color(&Fixed(12).on(Fixed(0))), "brblue --background black");
+        assert_eq!(fish_set_color(&Style::new().on(RGB(0x12, 0x34, 0x56))), "--background 123456");
+        assert_eq!(fish_set_color(&Fixed(244).dimmed().italic().reverse()), "--dim --italics --reverse 808080");
+        assert_eq!(fish_set_color(&Style::new().blink().hidden().strikethrough()), "normal");
+        assert_eq!(fish_set_color(&Style::new().bold()), "--bold");
+    }
+}
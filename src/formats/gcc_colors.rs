@@ -0,0 +1,108 @@
+use std::fmt;
+
+use ansi_term::Style;
+
+use crate::{parse_sgr, Theme};
+
+/// The diagnostic kinds GCC colors, and its default for each.
+const DEFAULTS: [(&str, &str); 21] = [
+    ("error", "01;31"), ("warning", "01;35"), ("note", "01;36"), ("range1", "32"), ("range2", "34"),
+    ("locus", "01"), ("quote", "01"), ("path", "01;36"), ("fixit-insert", "32"), ("fixit-delete", "31"),
+    ("diff-filename", "01"), ("diff-hunk", "32"), ("diff-delete", "31"), ("diff-insert", "32"),
+    ("type-diff", "01;32"), ("fnname", "01;32"), ("targs", "35"), ("valid", "01;31"), ("invalid", "01;32"),
+    ("highlight-a", "01;32"), ("highlight-b", "01;34"),
+];
+
+/// Parse a `GCC_COLORS` value into a theme keyed by diagnostic kind, such
+/// as `error`, `warning`, `note`, `locus`, and `quote`.
+///
+/// The value is a `:`-separated list of `kind=value` entries, each value
+/// a list of SGR parameters. As in GCC, the theme starts with GCC's
+/// defaults for every kind it knows, and an entry replaces the default
+/// for its kind; entries for other kinds are kept as well, for tools with
+/// kinds of their own. GCC turns colors off entirely when `GCC_COLORS` is
+/// set but empty, which is for the caller to check; this returns the
+/// defaults.
+///
+/// # Examples
+///
+///     # extern crate ansi_term;
+///     # extern crate colorparse;
+///     use ansi_term::Color::{Purple, Red};
+///
+///     # fn main() {
+///     let theme = colorparse::formats::parse_gcc_colors("error=01;31:warning=35:locus=").unwrap();
+///     assert_eq!(theme.get("error"), Some(Red.bold()));
+///     assert_eq!(theme.get("warning"), Some(Purple.normal()));
+///     assert_eq!(theme.get("locus"), Some(ansi_term::Style::new()));
+///     assert_eq!(theme.get("note"), Some(ansi_term::Color::Cyan.bold()));
+///     # }
+pub fn parse_gcc_colors(s: &str) -> Result<Theme, GccColorsError> {
+    let mut theme = Theme::new();
+    for &(kind, code) in &DEFAULTS {
+        theme.insert(kind, Style::from(parse_sgr(code).unwrap()));
+    }
+    for entry in s.split(':').filter(|entry| !entry.is_empty()) {
+        let (kind, value) = match entry.find('=') {
+            Some(pos) => (&entry[..pos], &entry[pos + 1..]),
+            None => return Err(GccColorsError::MissingValue(entry.to_string())),
+        };
+        match parse_sgr(value) {
+            Ok(spec) => { theme.insert(kind, Style::from(spec)); }
+            Err(_) => return Err(GccColorsError::InvalidCode(kind.to_string(), value.to_string())),
+        }
+    }
+    Ok(theme)
+}
+
+/// Type for errors returned by `parse_gcc_colors`.
+#[derive(Debug, PartialEq)]
+pub enum GccColorsError {
+    /// An entry has no `=`; carries the entry.
+    MissingValue(String),
+    /// A value is not a sequence of SGR parameters; carries the kind and
+    /// the value.
+    InvalidCode(String, String),
+}
+
+impl fmt::Display for GccColorsError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::MissingValue(entry) => write!(fmt, "Error parsing GCC_COLORS: missing value: \"{}\"", entry),
+            Self::InvalidCode(kind, code) => {
+                write!(fmt, "Error parsing GCC_COLORS: invalid color code \"{}\" for \"{}\"", code, kind)
+            }
+        }
+    }
+}
+
+impl std::error::Error for GccColorsError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ansi_term::Color::*;
+
+    #[test]
+    fn test_parse_gcc_colors() {
+        let defaults = parse_gcc_colors("").unwrap();
+        assert_eq!(defaults.get("warning"), Some(Purple.bold()));
+        assert_eq!(defaults.get("locus"), Some(Style::new().bold()));
+        assert_eq!(defaults.get("highlight-b"), Some(Blue.bold()));
+        assert_eq!(defaults.len(), 21);
+
+        let theme = parse_gcc_colors("error=38;5;196::fixit-insert=4;32:lint=33:error=01;38;5;160").unwrap();
+        assert_eq!(theme.get("error"), Some(Fixed(160).bold()));
+        assert_eq!(theme.get("fixit-insert"), Some(Green.underline()));
+        assert_eq!(theme.get("lint"), Some(Yellow.normal()));
+        assert_eq!(theme.len(), 22);
+    }
+
+    #[test]
+    fn test_parse_gcc_colors_err() {
+        assert_eq!(parse_gcc_colors("error=1:note"), Err(GccColorsError::MissingValue("note".to_string())));
+        let err = parse_gcc_colors("quote=1;x").unwrap_err();
+        assert_eq!(err, GccColorsError::InvalidCode("quote".to_string(), "1;x".to_string()));
+        assert_eq!(err.to_string(), "Error parsing GCC_COLORS: invalid color code \"1;x\" for \"quote\"");
+    }
+}
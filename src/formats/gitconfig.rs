@@ -0,0 +1,369 @@
+use std::fmt;
+#[cfg(feature = "git-config")]
+use std::path::Path;
+#[cfg(feature = "git-config")]
+use std::process::Command;
+
+use crate::{Error, Theme};
+
+/// Parse a section header, `[section]`, `[section "subsection"]`, or the
+/// older `[section.subsection]`, returning the lowercased section, the
+/// subsection, and the rest of the line after the `]`.
+fn header(line: &str) -> Option<(String, Option<String>, &str)> {
+    let inner = line.strip_prefix('[')?;
+    let len = inner.find(|c: char| !(c.is_ascii_alphanumeric() || c == '-' || c == '.')).unwrap_or(inner.len());
+    let (mut name, mut rest) = inner.split_at(len);
+    let mut subsection = None;
+    if let Some(quoted) = rest.trim_start().strip_prefix('"').filter(|_| !name.contains('.')) {
+        let mut sub = String::new();
+        let mut chars = quoted.char_indices();
+        loop {
+            match chars.next()? {
+                (i, '"') => {
+                    rest = &quoted[i + 1..];
+                    break;
+                }
+                (_, '\\') => sub.push(chars.next()?.1),
+                (_, c) => sub.push(c),
+            }
+        }
+        subsection = Some(sub);
+    } else if let Some(pos) = name.find('.') {
+        subsection = Some(name[pos + 1..].to_lowercase());
+        name = &name[..pos];
+    }
+    let rest = rest.strip_prefix(']')?;
+    if name.is_empty() {
+        return None;
+    }
+    Some((name.to_lowercase(), subsection, rest))
+}
+
+/// Parse the value of a variable, the text after its `=`, as Git does,
+/// reading on from `lines` when a `\` continues it; return `None` for an
+/// unterminated quote or an unknown escape.
+fn value<'a, I: Iterator<Item = (usize, &'a str)>>(first: &'a str, lines: &mut I) -> Option<String> {
+    let mut value = String::new();
+    let (mut quoted, mut keep) = (false, 0);
+    let mut rest = first.trim_start();
+    'lines: loop {
+        let mut chars = rest.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '"' => quoted = !quoted,
+                '#' | ';' if !quoted => break 'lines,
+                '\\' => match chars.next() {
+                    None => {
+                        rest = lines.next()?.1;
+                        continue 'lines;
+                    }
+                    Some('n') => value.push('\n'),
+                    Some('t') => value.push('\t'),
+                    Some('b') => { value.pop(); }
+                    Some(c @ '"') | Some(c @ '\\') => value.push(c),
+                    Some(_) => return None,
+                },
+                c => value.push(c),
+            }
+            // Whitespace outside quotes is kept only when more follows.
+            if quoted || !c.is_whitespace() {
+                keep = value.len();
+            }
+        }
+        break;
+    }
+    if quoted {
+        return None;
+    }
+    value.truncate(keep);
+    Some(value)
+}
+
+/// Parse the `[color "..."]` sections of a Git configuration file, such
+/// as `~/.gitconfig`, into a theme keyed by slot, such as `diff.new` for
+/// `new` in `[color "diff"]`, parsing each value as Git does.
+///
+/// The file follows Git's syntax: section names and variable names are
+/// case-insensitive and become lowercase, while subsection names keep
+/// their case, except in the older `[color.diff]` form; values may be
+/// quoted, use `\` escapes, and continue onto the next line after a `\`;
+/// and `#` and `;` start comments. A later setting of a slot replaces
+/// an earlier one. Other sections, and the variables of `[color]` itself,
+/// such as `ui = auto`, which turn colors on and off, are skipped, as is
+/// `color.blame.highlightRecent`, which is a list of colors and dates
+/// rather than a style. Files named by `[include]` are not read; use
+/// `load_from_repo` for the settings Git itself would use.
+///
+/// # Examples
+///
+///     # extern crate ansi_term;
+///     # extern crate colorparse;
+///     use ansi_term::Color::{Green, Red, Yellow};
+///
+///     # fn main() {
+///     let theme = colorparse::formats::parse_gitconfig(r#"
+///     [user]
+///         name = A U Thor
+///     [color]
+///         ui = auto
+///     [color "diff"]
+///         new = green
+///         old = "red bold"  # removed lines
+///     [color "branch"]
+///         current = yellow reverse
+///     "#).unwrap();
+///     assert_eq!(theme.get("diff.new"), Some(Green.normal()));
+///     assert_eq!(theme.get("diff.old"), Some(Red.bold()));
+///     assert_eq!(theme.get("branch.current"), Some(Yellow.reverse()));
+///     assert_eq!(theme.len(), 3);
+///     # }
+pub fn parse_gitconfig(s: &str) -> Result<Theme, GitconfigError> {
+    let mut theme = Theme::new();
+    let mut subsection = None;
+    let mut lines = s.lines().enumerate().map(|(i, line)| (i + 1, line));
+    while let Some((line_number, line)) = lines.next() {
+        let invalid = || GitconfigError::InvalidLine(line_number, line.to_string());
+        let mut content = line.trim_start();
+        if content.starts_with('[') {
+            let (name, sub, rest) = header(content).ok_or_else(invalid)?;
+            subsection = if name == "color" { sub } else { None };
+            content = rest.trim_start();
+        }
+        if content.is_empty() || content.starts_with('#') || content.starts_with(';') {
+            continue;
+        }
+        let len = content.find(|c: char| !(c.is_ascii_alphanumeric() || c == '-')).unwrap_or(content.len());
+        let (key, rest) = content.split_at(len);
+        let rest = rest.trim_start();
+        if !key.starts_with(|c: char| c.is_ascii_alphabetic()) {
+            return Err(invalid());
+        }
+        let value = match rest.strip_prefix('=') {
+            Some(rest) => Some(value(rest, &mut lines).ok_or_else(invalid)?),
+            None if rest.is_empty() || rest.starts_with('#') || rest.starts_with(';') => None,
+            None => return Err(invalid()),
+        };
+        let slot = match &subsection {
+            Some(subsection) => format!("{}.{}", subsection, key.to_lowercase()),
+            None => continue,
+        };
+        if slot == "blame.highlightrecent" {
+            continue;
+        }
+        let value = value.ok_or_else(|| GitconfigError::MissingValue(line_number, slot.clone()))?;
+        let style = crate::parse(&value).map_err(|e| GitconfigError::InvalidStyle(line_number, slot.clone(), e))?;
+        theme.insert(slot, style);
+    }
+    Ok(theme)
+}
+
+/// Type for errors returned by `parse_gitconfig`.
+#[derive(Debug, PartialEq)]
+pub enum GitconfigError {
+    /// A line is not a section header, a variable, or a comment, or has
+    /// an unterminated quote or an unknown escape; carries the line number
+    /// and the line.
+    InvalidLine(usize, String),
+    /// A slot has no `=` and value; carries the line number and the slot.
+    MissingValue(usize, String),
+    /// A slot's value failed to parse; carries the line number, the slot,
+    /// and the error.
+    InvalidStyle(usize, String, Error),
+}
+
+impl fmt::Display for GitconfigError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::InvalidLine(line, s) => write!(fmt, "Error parsing gitconfig line {}: invalid line: \"{}\"", line, s),
+            Self::MissingValue(line, slot) => {
+                write!(fmt, "Error parsing gitconfig line {}: missing value for \"{}\"", line, slot)
+            }
+            Self::InvalidStyle(line, slot, e) => write!(fmt, "Error parsing gitconfig line {} for \"{}\": {}", line, slot, e),
+        }
+    }
+}
+
+impl std::error::Error for GitconfigError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::InvalidStyle(_, _, e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// Read the `color.*` settings from the output of `git config --null
+/// --get-regexp`, run by `git`.
+#[cfg(feature = "git-config")]
+fn load_from_git(git: &mut Command) -> Result<Theme, RepoConfigError> {
+    let output = git.args(["config", "--null", "--get-regexp", "^color\\."]).output()
+        .map_err(|e| RepoConfigError::Git(e.to_string()))?;
+    // `git config --get-regexp` exits with 1 when no variable matches.
+    let no_match = output.status.code() == Some(1) && output.stderr.is_empty();
+    if !output.status.success() && !no_match {
+        return Err(RepoConfigError::Git(String::from_utf8_lossy(&output.stderr).trim_end().to_string()));
+    }
+    let mut theme = Theme::new();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    for entry in stdout.split_terminator('\0') {
+        let (key, value) = match entry.split_once('\n') {
+            Some((key, value)) => (key, Some(value)),
+            None => (entry, None),
+        };
+        let slot = match key.strip_prefix("color.") {
+            Some(slot) if slot.contains('.') && slot != "blame.highlightrecent" => slot,
+            _ => continue,
+        };
+        let value = value.ok_or_else(|| RepoConfigError::MissingValue(slot.to_string()))?;
+        let style = crate::parse(value).map_err(|e| RepoConfigError::InvalidStyle(slot.to_string(), e))?;
+        theme.insert(slot, style);
+    }
+    Ok(theme)
+}
+
+/// Load the color settings Git uses in the repository at `path` into a
+/// theme keyed by slot, as `parse_gitconfig` keys them, by running
+/// `git config`. Requires the `git-config` feature.
+///
+/// The settings are Git's effective configuration for the repository: the
+/// system, global, and repository files, the files they `[include]` or
+/// `[includeIf]`, and the `GIT_CONFIG_*` environment variables, with a
+/// later setting of a slot replacing an earlier one, just as `git` itself
+/// reads them. A `path` outside any repository gives the system and global
+/// settings alone.
+///
+/// # Examples
+///
+///     # extern crate ansi_term;
+///     # extern crate colorparse;
+///     # fn main() {
+///     let theme = colorparse::formats::load_from_repo(".").unwrap();
+///     if let Some(style) = theme.resolve("diff.new") {
+///         println!("{}", style.paint("added line"));
+///     }
+///     # }
+#[cfg(feature = "git-config")]
+pub fn load_from_repo<P: AsRef<Path>>(path: P) -> Result<Theme, RepoConfigError> {
+    load_from_git(Command::new("git").arg("-C").arg(path.as_ref()))
+}
+
+/// Type for errors returned by `load_from_repo`.
+#[cfg(feature = "git-config")]
+#[derive(Debug, PartialEq)]
+pub enum RepoConfigError {
+    /// Running `git config` failed; carries the reason, or what Git wrote
+    /// to standard error.
+    Git(String),
+    /// A slot is set with no `=` and value; carries the slot.
+    MissingValue(String),
+    /// A slot's value failed to parse; carries the slot and the error.
+    InvalidStyle(String, Error),
+}
+
+#[cfg(feature = "git-config")]
+impl fmt::Display for RepoConfigError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Git(message) => write!(fmt, "Error loading git config: {}", message),
+            Self::MissingValue(slot) => write!(fmt, "Error loading git config: missing value for \"{}\"", slot),
+            Self::InvalidStyle(slot, e) => write!(fmt, "Error loading git config for \"{}\": {}", slot, e),
+        }
+    }
+}
+
+#[cfg(feature = "git-config")]
+impl std::error::Error for RepoConfigError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::InvalidStyle(_, e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ansi_term::Color::*;
+    use ansi_term::Style;
+
+    #[test]
+    fn test_parse_gitconfig() {
+        let theme = parse_gitconfig(r#"
+; comment
+[Color "diff"] meta = "yellow" ; trailing
+	New = green \
+	  bold
+	commit = "  blue  "  # spaces
+[color.Status]
+	added = 208 ul
+[color "Decorate"]
+	HEAD = "bold\tcyan" # escaped tab
+[alias]
+	lg = "log --color=always \
+	  --graph"
+[color "blame"]
+	highlightRecent = blue,12 month ago,white
+	repeatedLines = 8
+[color "diff"]
+	meta =
+"#);
+        let theme = theme.unwrap();
+        assert_eq!(theme.get("diff.meta"), Some(Style::new()));
+        assert_eq!(theme.get("diff.new"), Some(Green.bold()));
+        assert_eq!(theme.get("diff.commit"), Some(Blue.normal()));
+        assert_eq!(theme.get("status.added"), Some(Fixed(208).underline()));
+        assert_eq!(theme.get("Decorate.head"), Some(Cyan.bold()));
+        assert_eq!(theme.get("blame.repeatedlines"), Some(Fixed(8).normal()));
+        assert_eq!(theme.len(), 6);
+        assert!(parse_gitconfig("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_parse_gitconfig_err() {
+        let invalid = |line, s: &str| Err(GitconfigError::InvalidLine(line, s.to_string()));
+        assert_eq!(parse_gitconfig("[color \"diff\"\nnew = green"), invalid(1, "[color \"diff\""));
+        assert_eq!(parse_gitconfig("[color \"diff\"]\nnew = \"green"), invalid(2, "new = \"green"));
+        assert_eq!(parse_gitconfig("[user]\nname = a\\qb"), invalid(2, "name = a\\qb"));
+        assert_eq!(parse_gitconfig("[]"), invalid(1, "[]"));
+        assert_eq!(parse_gitconfig("new green"), invalid(1, "new green"));
+        assert_eq!(parse_gitconfig("[color \"diff\"]\n= red"), invalid(2, "= red"));
+        assert_eq!(parse_gitconfig("[color \"diff\"]\n\nnew # no value"),
+                   Err(GitconfigError::MissingValue(3, "diff.new".to_string())));
+        let err = parse_gitconfig("[color \"diff\"]\nnew = gren").unwrap_err();
+        assert!(matches!(&err, GitconfigError::InvalidStyle(2, slot, _) if slot == "diff.new"));
+        assert!(err.to_string().starts_with("Error parsing gitconfig line 2 for \"diff.new\": Error parsing style"));
+    }
+
+    #[cfg(feature = "git-config")]
+    #[test]
+    fn test_load_from_repo() {
+        let dir = std::env::temp_dir().join(format!("colorparse-load-from-repo-{}", std::process::id()));
+        let repo = dir.join("repo");
+        let git = |args: &[&str]| {
+            let mut command = Command::new("git");
+            command.arg("-C").arg(&repo).env("GIT_CONFIG_NOSYSTEM", "1").env("GIT_CONFIG_GLOBAL", dir.join("global"));
+            command.args(args);
+            command
+        };
+        std::fs::create_dir_all(&repo).unwrap();
+        std::fs::write(dir.join("global"), "[color]\n\tui = auto\n[color \"diff\"]\n\tnew = green\n\told = red\n").unwrap();
+        std::fs::write(dir.join("included"), "[color \"Status\"]\n\tadded = 208 ul\n").unwrap();
+        assert!(git(&["init", "-q"]).status().unwrap().success());
+        assert_eq!(load_from_git(&mut git(&[])), Ok(parse_gitconfig("[color \"diff\"]\nnew = green\nold = red").unwrap()));
+
+        let include = format!("[include]\n\tpath = {}\n[color \"diff\"]\n\tnew = \"bold blue\"\n", dir.join("included").display());
+        let config = std::fs::read_to_string(repo.join(".git/config")).unwrap() + &include;
+        std::fs::write(repo.join(".git/config"), config).unwrap();
+        let theme = load_from_git(&mut git(&[])).unwrap();
+        assert_eq!(theme.iter().collect::<Vec<_>>(),
+                   vec![("Status.added", Fixed(208).underline()), ("diff.new", Blue.bold()), ("diff.old", Red.normal())]);
+
+        std::fs::write(dir.join("global"), "[color \"diff\"]\n\tmeta\n").unwrap();
+        assert_eq!(load_from_git(&mut git(&[])), Err(RepoConfigError::MissingValue("diff.meta".to_string())));
+        std::fs::write(dir.join("global"), "[color \"diff\"]\n\tmeta = gren\n").unwrap();
+        assert!(matches!(load_from_git(&mut git(&[])), Err(RepoConfigError::InvalidStyle(slot, _)) if slot == "diff.meta"));
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert!(matches!(load_from_git(&mut git(&[])), Err(RepoConfigError::Git(_))));
+    }
+}
@@ -0,0 +1,169 @@
+use std::fmt;
+
+use ansi_term::Style;
+
+use crate::{parse_sgr, Theme};
+
+/// The capabilities that take SGR parameters, and GNU grep's default for
+/// each.
+const DEFAULTS: [(&str, &str); 8] = [
+    ("ms", "01;31"), ("mc", "01;31"), ("sl", ""), ("cx", ""), ("fn", "35"), ("ln", "32"), ("bn", "32"), ("se", "36"),
+];
+
+/// The colors of GNU grep's output, as set by the `GREP_COLORS`
+/// environment variable.
+///
+/// `GREP_COLORS` is a `:`-separated list of capabilities. Those with a
+/// value, such as `fn=35`, give the SGR parameters for a part of the
+/// output: `ms` and `mc` for matches in selected and context lines (`mt`
+/// sets both), `sl` and `cx` for the whole of selected and context lines,
+/// `fn`, `ln`, and `bn` for file names, line numbers, and byte offsets,
+/// and `se` for separators. The boolean `rv` swaps `sl` and `cx` when
+/// grep is run with `-v`, and `ne` stops grep from erasing to the end of
+/// the line after each colored part.
+///
+/// # Examples
+///
+///     # extern crate ansi_term;
+///     # extern crate colorparse;
+///     use ansi_term::Color::{Green, Purple, Red};
+///     use colorparse::formats::GrepColors;
+///
+///     # fn main() {
+///     let colors = GrepColors::parse("ms=01;32:fn=:ne").unwrap();
+///     assert_eq!(colors.theme().get("ms"), Some(Green.bold()));
+///     assert_eq!(colors.theme().get("mc"), Some(Red.bold()));
+///     assert_eq!(colors.theme().get("fn"), Some(ansi_term::Style::new()));
+///     assert!(colors.no_erase() && !colors.reverse());
+///     assert_eq!(GrepColors::default().theme().get("fn"), Some(Purple.normal()));
+///     # }
+#[derive(Clone, Debug, PartialEq)]
+pub struct GrepColors {
+    theme: Theme,
+    reverse: bool,
+    no_erase: bool,
+}
+
+/// Returns grep's colors when `GREP_COLORS` is unset.
+impl Default for GrepColors {
+    fn default() -> Self {
+        let mut theme = Theme::new();
+        for &(cap, code) in &DEFAULTS {
+            theme.insert(cap, Style::from(parse_sgr(code).unwrap()));
+        }
+        GrepColors { theme, reverse: false, no_erase: false }
+    }
+}
+
+impl GrepColors {
+    /// Parse a `GREP_COLORS` value. As in grep, the capabilities it does
+    /// not set keep their defaults, and a later capability overrides an
+    /// earlier one.
+    pub fn parse(s: &str) -> Result<Self, GrepColorsError> {
+        let mut colors = GrepColors::default();
+        for cap in s.split(':').filter(|cap| !cap.is_empty()) {
+            let (name, value) = match cap.find('=') {
+                Some(pos) => (&cap[..pos], Some(&cap[pos + 1..])),
+                None => (cap, None),
+            };
+            let known = name == "mt" || DEFAULTS.iter().any(|&(known, _)| known == name);
+            match (name, value) {
+                ("rv", None) => colors.reverse = true,
+                ("ne", None) => colors.no_erase = true,
+                ("rv", Some(_)) | ("ne", Some(_)) => return Err(GrepColorsError::UnexpectedValue(cap.to_string())),
+                (_, Some(value)) if known => {
+                    let style = parse_sgr(value).map(Style::from)
+                        .map_err(|_| GrepColorsError::InvalidCode(name.to_string(), value.to_string()))?;
+                    if name == "mt" {
+                        colors.theme.insert("ms", style);
+                        colors.theme.insert("mc", style);
+                    } else {
+                        colors.theme.insert(name, style);
+                    }
+                }
+                (_, None) if known => return Err(GrepColorsError::MissingValue(name.to_string())),
+                _ => return Err(GrepColorsError::UnknownCapability(name.to_string())),
+            }
+        }
+        Ok(colors)
+    }
+
+    /// Return the style of each capability with a value, keyed by its
+    /// two-letter name.
+    pub fn theme(&self) -> &Theme {
+        &self.theme
+    }
+
+    /// Return true if `rv` is set.
+    pub fn reverse(&self) -> bool {
+        self.reverse
+    }
+
+    /// Return true if `ne` is set.
+    pub fn no_erase(&self) -> bool {
+        self.no_erase
+    }
+}
+
+/// Type for errors returned by `GrepColors::parse`.
+#[derive(Debug, PartialEq)]
+pub enum GrepColorsError {
+    /// A capability is not one grep knows; carries its name.
+    UnknownCapability(String),
+    /// A capability that takes SGR parameters has no `=`; carries its
+    /// name.
+    MissingValue(String),
+    /// A boolean capability has a value; carries the capability as
+    /// written.
+    UnexpectedValue(String),
+    /// A value is not a sequence of SGR parameters; carries the
+    /// capability's name and the value.
+    InvalidCode(String, String),
+}
+
+impl fmt::Display for GrepColorsError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::UnknownCapability(name) => write!(fmt, "Error parsing GREP_COLORS: unknown capability \"{}\"", name),
+            Self::MissingValue(name) => write!(fmt, "Error parsing GREP_COLORS: capability \"{}\" needs a value", name),
+            Self::UnexpectedValue(cap) => {
+                write!(fmt, "Error parsing GREP_COLORS: boolean capability \"{}\" takes no value", cap)
+            }
+            Self::InvalidCode(name, code) => {
+                write!(fmt, "Error parsing GREP_COLORS: invalid color code \"{}\" for \"{}\"", code, name)
+            }
+        }
+    }
+}
+
+impl std::error::Error for GrepColorsError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ansi_term::Color::*;
+
+    #[test]
+    fn test_grep_colors() {
+        let colors = GrepColors::parse("mt=01;04;38;5;208:sl=7::cx=:rv:ln=1;32").unwrap();
+        assert_eq!(colors.theme().get("ms"), Some(Fixed(208).bold().underline()));
+        assert_eq!(colors.theme().get("mc"), Some(Fixed(208).bold().underline()));
+        assert_eq!(colors.theme().get("sl"), Some(Style::new().reverse()));
+        assert_eq!(colors.theme().get("ln"), Some(Green.bold()));
+        assert_eq!(colors.theme().get("se"), Some(Cyan.normal()));
+        assert_eq!(colors.theme().get("mt"), None);
+        assert_eq!(colors.theme().len(), 8);
+        assert!(colors.reverse() && !colors.no_erase());
+        assert_eq!(GrepColors::parse(""), Ok(GrepColors::default()));
+    }
+
+    #[test]
+    fn test_grep_colors_invalid() {
+        assert_eq!(GrepColors::parse("ms=1:xx=2"), Err(GrepColorsError::UnknownCapability("xx".to_string())));
+        assert_eq!(GrepColors::parse("fn"), Err(GrepColorsError::MissingValue("fn".to_string())));
+        assert_eq!(GrepColors::parse("ne=1"), Err(GrepColorsError::UnexpectedValue("ne=1".to_string())));
+        let err = GrepColors::parse("bn=1;x").unwrap_err();
+        assert_eq!(err, GrepColorsError::InvalidCode("bn".to_string(), "1;x".to_string()));
+        assert_eq!(err.to_string(), "Error parsing GREP_COLORS: invalid color code \"1;x\" for \"bn\"");
+    }
+}
@@ -0,0 +1,110 @@
+use ansi_term::{Color, Style};
+
+use crate::{color_word, Attribute, Error, ErrorKind};
+
+/// Return the color for a name in the dialects of lazygit and gitui, or
+/// `None` for a name neither uses.
+fn dialect_color(name: &str) -> Option<Color> {
+    Some(match name {
+        "gray" | "grey" => Color::White,
+        "darkgray" | "darkgrey" => Color::Fixed(8),
+        "lightred" => Color::Fixed(9),
+        "lightgreen" => Color::Fixed(10),
+        "lightyellow" => Color::Fixed(11),
+        "lightblue" => Color::Fixed(12),
+        "lightmagenta" => Color::Fixed(13),
+        "lightcyan" => Color::Fixed(14),
+        _ => return None,
+    })
+}
+
+fn dialect_attribute(name: &str) -> Option<Attribute> {
+    Some(match name {
+        "bold" => Attribute::Bold,
+        "dim" => Attribute::Dimmed,
+        "italic" => Attribute::Italic,
+        "underline" | "ul" => Attribute::Underline,
+        "blink" => Attribute::Blink,
+        "reverse" => Attribute::Reverse,
+        "strikethrough" | "strike" => Attribute::Strikethrough,
+        _ => return None,
+    })
+}
+
+/// Parse a style written as an array of words, as in the themes of
+/// lazygit (`activeBorderColor: [green, bold]`) and gitui, once the
+/// configuration file has been read into a list of strings.
+///
+/// Each word is a color or an attribute, matched case-insensitively.
+/// Colors may be named in Git's vocabulary (including numbers and
+/// `#rrggbb`) or in gitui's (`Gray`, `DarkGray`, and `LightRed` through
+/// `LightCyan`, for the bright colors); as in lazygit, `white` is color 7,
+/// the same as gitui's `Gray`. The first color is the foreground and the
+/// second the background. The words `default` and `reset` do nothing.
+///
+/// lazygit applies a single color as the background for settings such as
+/// `selectedLineBgColor`; swap the colors of the result with
+/// `StyleExt::variant(Variant::Inverted)` for those.
+///
+/// Errors name the words joined by spaces as the input.
+///
+/// # Examples
+///
+///     let style = colorparse::formats::lazygit_style(&["green", "bold"]).unwrap();
+///     assert_eq!(style, colorparse::parse("bold green").unwrap());
+///
+///     let style = colorparse::formats::lazygit_style(&["LightBlue", "#303030", "Underline"]).unwrap();
+///     assert_eq!(style, colorparse::parse("ul 12 #303030").unwrap());
+///
+///     assert!(colorparse::formats::lazygit_style(&["green", "shiny"]).is_err());
+pub fn lazygit_style<S: AsRef<str>>(words: &[S]) -> Result<Style, Error> {
+    let input = || words.iter().map(|w| w.as_ref()).collect::<Vec<_>>().join(" ");
+    let mut style = Style::new();
+    let mut colors = 0;
+    let mut start = 0;
+    for word in words {
+        let word = word.as_ref();
+        let span = start..start + word.len();
+        start = span.end + 1;
+        let lower = word.to_lowercase();
+        if lower == "default" || lower == "reset" {
+            continue;
+        }
+        if let Some(attribute) = dialect_attribute(&lower) {
+            style = attribute.apply(style);
+            continue;
+        }
+        let color = match dialect_color(&lower) {
+            Some(color) => Some(color),
+            None => color_word(&lower).map_err(|()| Error::new(ErrorKind::UnknownWord, &input(), word, span.clone()))?,
+        };
+        match colors {
+            0 => style.foreground = color,
+            1 => style.background = color,
+            _ => return Err(Error::new(ErrorKind::ExtraColor, &input(), word, span)),
+        }
+        colors += 1;
+    }
+    Ok(style)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ansi_term::Color::*;
+
+    #[test]
+    fn test_lazygit_style() {
+        let empty: [&str; 0] = [];
+        assert_eq!(lazygit_style(&empty), Ok(Style::new()));
+        assert_eq!(lazygit_style(&["default"]), Ok(Style::new()));
+        assert_eq!(lazygit_style(&["REVERSE", "strikethrough"]), Ok(Style::new().reverse().strikethrough()));
+        assert_eq!(lazygit_style(&["magenta"]), Ok(Purple.normal()));
+        assert_eq!(lazygit_style(&["Gray", "DarkGray"]), Ok(White.on(Fixed(8))));
+        assert_eq!(lazygit_style(&["normal", "red"]), Ok(Style::new().on(Red)));
+        assert_eq!(lazygit_style(&["red", "blue", "green"]),
+                   Err(Error::new(ErrorKind::ExtraColor, "red blue green", "green", 9..14)));
+        assert_eq!(lazygit_style(&["bold".to_string(), "Sparkly".to_string()]),
+                   Err(Error::new(ErrorKind::UnknownWord, "bold Sparkly", "Sparkly", 5..12)));
+    }
+}
@@ -0,0 +1,220 @@
+use std::fmt;
+
+use ansi_term::{Color, Style};
+
+use super::glob_match;
+use crate::downgrade::index;
+use crate::render::style_params;
+use crate::{downgrade_style, parse_sgr, ColorLevel, Theme};
+
+/// Append the SGR parameters for `color`, using `base` (30 or 40) for the
+/// eight basic colors and `base + 60` for their bright variants.
+fn color_params(color: Color, base: u8, params: &mut Vec<String>) {
+    match (index(color), color) {
+        (Some(n), _) if n < 8 => params.push((base + n).to_string()),
+        (Some(n), _) if n < 16 => params.push((base + 60 + n - 8).to_string()),
+        (Some(n), _) => params.push(format!("{};5;{}", base + 8, n)),
+        (None, Color::RGB(r, g, b)) => params.push(format!("{};2;{};{};{}", base + 8, r, g, b)),
+        (None, _) => unreachable!(),
+    }
+}
+
+/// Return the `LS_COLORS` value for `style`, such as `1;34`.
+fn sgr(style: Style, level: ColorLevel) -> String {
+    let style = downgrade_style(style, level);
+    let mut attributes = Vec::new();
+    style_params(&Style { foreground: None, background: None, ..style }, &mut attributes);
+    let mut params: Vec<String> = attributes.iter().map(u8::to_string).collect();
+    if let Some(fg) = style.foreground {
+        color_params(fg, 30, &mut params);
+    }
+    if let Some(bg) = style.background {
+        color_params(bg, 40, &mut params);
+    }
+    if params.is_empty() {
+        params.push("0".to_string());
+    }
+    params.join(";")
+}
+
+/// Write an `LS_COLORS` value from a theme keyed by `LS_COLORS` names:
+/// two-letter file type codes such as `di` and `ln`, and globs such as
+/// `*.tar`.
+///
+/// Colors are downgraded to `level`, and the bright basic colors are
+/// written with the 16-color codes 90–97 and 100–107, which every
+/// terminal supporting `ls --color` understands. Names that cannot appear
+/// in `LS_COLORS`, because they contain `=` or `:`, are skipped.
+///
+/// # Examples
+///
+///     use colorparse::{formats, ColorLevel, Theme};
+///
+///     let mut theme = Theme::new();
+///     theme.set("di", "bold blue").unwrap();
+///     theme.set("*.tar", "#ff0000").unwrap();
+///     theme.set("ex", "9").unwrap();
+///     assert_eq!(formats::to_ls_colors(&theme, ColorLevel::Ansi16), "*.tar=31:di=1;34:ex=91");
+///     assert_eq!(formats::to_ls_colors(&theme, ColorLevel::TrueColor), "*.tar=38;2;255;0;0:di=1;34:ex=91");
+pub fn to_ls_colors(theme: &Theme, level: ColorLevel) -> String {
+    theme.iter()
+        .filter(|(name, _)| !name.is_empty() && !name.contains(['=', ':']))
+        .map(|(name, style)| format!("{}={}", name, sgr(style, level)))
+        .collect::<Vec<_>>()
+        .join(":")
+}
+
+/// The file colors of `ls`, as set by the `LS_COLORS` environment variable
+/// or produced by a `dircolors` database, for looking up the style of a
+/// file.
+///
+/// The styles are keyed by `LS_COLORS` names: two-letter file type codes
+/// such as `di` and `ln`, looked up with `theme().get()`, and globs such
+/// as `*.tar`, matched against file names by `style_for_name`.
+///
+/// # Examples
+///
+///     # extern crate ansi_term;
+///     # extern crate colorparse;
+///     use ansi_term::Color::{Blue, Red};
+///     use colorparse::formats::{Dircolors, LsColors};
+///
+///     # fn main() {
+///     let colors = LsColors::parse("di=01;34:*.tar=01;31:*.tar.gz=31:ln=target").unwrap();
+///     assert_eq!(colors.theme().get("di"), Some(Blue.bold()));
+///     assert_eq!(colors.style_for_name("backup.tar"), Some(Red.bold()));
+///     assert_eq!(colors.style_for_name("src.tar.gz"), Some(Red.normal()));
+///     assert_eq!(colors.style_for_name("notes.txt"), None);
+///
+///     let database = Dircolors::parse(".tar 01;31").unwrap();
+///     let colors = LsColors::from(database.theme_for("xterm", None));
+///     assert_eq!(colors.style_for_name("backup.tar"), Some(Red.bold()));
+///     # }
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LsColors {
+    theme: Theme,
+}
+
+impl LsColors {
+    /// Parse an `LS_COLORS` value: `:`-separated `key=value` entries, each
+    /// value a list of SGR parameters. As in `ls`, a later entry for a key
+    /// replaces an earlier one, and `ln=target`, which colors links like
+    /// their targets, is skipped.
+    pub fn parse(s: &str) -> Result<Self, LsColorsError> {
+        let mut theme = Theme::new();
+        for entry in s.split(':').filter(|entry| !entry.is_empty()) {
+            let (key, value) = match entry.find('=') {
+                Some(pos) => (&entry[..pos], &entry[pos + 1..]),
+                None => return Err(LsColorsError::MissingValue(entry.to_string())),
+            };
+            if key == "ln" && value == "target" {
+                continue;
+            }
+            match parse_sgr(value) {
+                Ok(spec) => { theme.insert(key, Style::from(spec)); }
+                Err(_) => return Err(LsColorsError::InvalidCode(key.to_string(), value.to_string())),
+            }
+        }
+        Ok(LsColors { theme })
+    }
+
+    /// Return the style of each key.
+    pub fn theme(&self) -> &Theme {
+        &self.theme
+    }
+
+    /// Return the style of the glob matching the file name `name`, or
+    /// `None` if none matches. When several match, as `*.gz` and `*.tar.gz`
+    /// both match `a.tar.gz`, the longest glob wins.
+    pub fn style_for_name(&self, name: &str) -> Option<Style> {
+        self.theme.iter()
+            .filter(|(key, _)| key.starts_with('*') && glob_match(key, name))
+            .max_by_key(|(key, _)| key.len())
+            .map(|(_, style)| style)
+    }
+}
+
+/// Uses a theme keyed by `LS_COLORS` names, such as the one
+/// `Dircolors::theme_for` returns.
+impl From<Theme> for LsColors {
+    fn from(theme: Theme) -> Self {
+        LsColors { theme }
+    }
+}
+
+/// Type for errors returned by `LsColors::parse`.
+#[derive(Debug, PartialEq)]
+pub enum LsColorsError {
+    /// An entry has no `=`; carries the entry.
+    MissingValue(String),
+    /// A value is not a sequence of SGR parameters; carries the key and
+    /// the value.
+    InvalidCode(String, String),
+}
+
+impl fmt::Display for LsColorsError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::MissingValue(entry) => write!(fmt, "Error parsing LS_COLORS: missing value: \"{}\"", entry),
+            Self::InvalidCode(key, code) => {
+                write!(fmt, "Error parsing LS_COLORS: invalid color code \"{}\" for \"{}\"", code, key)
+            }
+        }
+    }
+}
+
+impl std::error::Error for LsColorsError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ansi_term::Color::*;
+    use crate::ColorLevel::*;
+
+    #[test]
+    fn test_to_ls_colors() {
+        let mut theme = Theme::new();
+        assert_eq!(to_ls_colors(&theme, TrueColor), "");
+        theme.insert("rs", Style::new());
+        theme.insert("ln", Cyan.bold().underline());
+        theme.insert("or", Fixed(9).on(Fixed(0)));
+        theme.insert("*.png", Fixed(208).on(RGB(0x10, 0x20, 0x30)));
+        theme.insert("bad=key", Red.normal());
+        theme.insert("bad:key", Red.normal());
+        assert_eq!(to_ls_colors(&theme, TrueColor), "*.png=38;5;208;48;2;16;32;48:ln=1;4;36:or=91;40:rs=0");
+        assert_eq!(to_ls_colors(&theme, Ansi256), "*.png=38;5;208;48;5;234:ln=1;4;36:or=91;40:rs=0");
+        assert_eq!(to_ls_colors(&theme, Ansi16), "*.png=33;40:ln=1;4;36:or=91;40:rs=0");
+    }
+
+    #[test]
+    fn test_ls_colors() {
+        let colors = LsColors::parse("rs=0:di=01;34::ex=32:ex=01;32:*~=90:*.GZ=31:*.tar.gz=1;31:*README*=4").unwrap();
+        assert_eq!(colors.theme().get("rs"), Some(Style::new()));
+        assert_eq!(colors.theme().get("ex"), Some(Green.bold()));
+        assert_eq!(colors.theme().len(), 7);
+        assert_eq!(colors.style_for_name("notes~"), Some(Fixed(8).normal()));
+        assert_eq!(colors.style_for_name("a.tar.gz"), Some(Red.bold()));
+        assert_eq!(colors.style_for_name("a.GZ"), Some(Red.normal()));
+        assert_eq!(colors.style_for_name("a.gz"), None);
+        assert_eq!(colors.style_for_name("README.md"), Some(Style::new().underline()));
+        assert_eq!(colors.style_for_name("di"), None);
+        assert_eq!(LsColors::parse(""), Ok(LsColors::default()));
+    }
+
+    #[test]
+    fn test_ls_colors_round_trip() {
+        let mut theme = Theme::new();
+        theme.insert("ln", Cyan.bold().underline());
+        theme.insert("*.png", Fixed(208).on(RGB(0x10, 0x20, 0x30)));
+        theme.insert("or", Fixed(9).on(Black));
+        assert_eq!(LsColors::parse(&to_ls_colors(&theme, TrueColor)), Ok(LsColors::from(theme)));
+    }
+
+    #[test]
+    fn test_ls_colors_err() {
+        assert_eq!(LsColors::parse("di=1:ex"), Err(LsColorsError::MissingValue("ex".to_string())));
+        let err = LsColors::parse("*.c=1;x").unwrap_err();
+        assert_eq!(err, LsColorsError::InvalidCode("*.c".to_string(), "1;x".to_string()));
+        assert_eq!(err.to_string(), "Error parsing LS_COLORS: invalid color code \"1;x\" for \"*.c\"");
+    }
+}
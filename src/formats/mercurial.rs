@@ -0,0 +1,213 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use ansi_term::{Color, Style};
+
+use crate::downgrade::from_index;
+use crate::{Attribute, Theme};
+
+const COLORS: [&str; 8] = ["black", "red", "green", "yellow", "blue", "magenta", "cyan", "white"];
+
+/// How Mercurial's `color` extension writes styles, as set by
+/// `color.mode`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum HgMode {
+    /// ANSI escape sequences, with only the basic effects. Mercurial also
+    /// uses this for the `auto`, `win32`, and `debug` modes.
+    Ansi,
+    /// Sequences looked up in the terminfo database, with the terminfo
+    /// effects and any colors defined with `color.<name> = <number>`.
+    Terminfo,
+}
+
+/// The `[color]` section of a Mercurial configuration file (`hgrc`).
+///
+/// The section sets the style of each label, such as `status.modified`,
+/// to a list of effects: the colors `black` through `white`, the same
+/// with `_background` for the background, `bold`, `dim`, `italic`,
+/// `underline`, `inverse`, `blink`, and `none`. In terminfo mode, effects
+/// may also be the terminfo names `standout`, `reverse`, and `invisible`,
+/// and colors defined in the section by number, such as
+/// `color.orange = 208`. As in Mercurial, when effects set a color twice,
+/// the last one wins.
+///
+/// # Examples
+///
+///     # extern crate ansi_term;
+///     # extern crate colorparse;
+///     use ansi_term::Color::{Blue, Fixed, Red};
+///     use colorparse::formats::{HgColors, HgMode};
+///
+///     # fn main() {
+///     let colors = HgColors::parse("
+///     mode = terminfo
+///     color.orange = 208
+///     status.modified = blue bold underline red_background
+///     status.unknown = orange standout
+///     ").unwrap();
+///     assert_eq!(colors.mode(), HgMode::Terminfo);
+///     assert_eq!(colors.theme().get("status.modified"), Some(Blue.on(Red).bold().underline()));
+///     assert_eq!(colors.theme().get("status.unknown"), Some(Fixed(208).reverse()));
+///     assert_eq!(colors.effects("green_background orange"), Ok(Fixed(208).on(ansi_term::Color::Green)));
+///     # }
+#[derive(Clone, Debug, PartialEq)]
+pub struct HgColors {
+    mode: HgMode,
+    colors: BTreeMap<String, u8>,
+    theme: Theme,
+}
+
+impl HgColors {
+    /// Parse the `key = value` lines of a `[color]` section, without the
+    /// section header.
+    ///
+    /// `mode` sets the mode, defaulting to `HgMode::Ansi`, and
+    /// `color.<name>` lines define colors; `pagermode` is skipped; every
+    /// other key is a label.
+    pub fn parse(section: &str) -> Result<Self, HgError> {
+        let mut entries = Vec::new();
+        let mut mode = HgMode::Ansi;
+        let mut colors = BTreeMap::new();
+        for (i, line) in section.lines().enumerate() {
+            let line_number = i + 1;
+            let content = line.trim();
+            if content.is_empty() || content.starts_with('#') || content.starts_with(';') {
+                continue;
+            }
+            let (key, value) = match content.find('=') {
+                Some(pos) => (content[..pos].trim(), content[pos + 1..].trim()),
+                None => return Err(HgError::InvalidLine(line_number, line.to_string())),
+            };
+            if key == "mode" {
+                mode = if value == "terminfo" { HgMode::Terminfo } else { HgMode::Ansi };
+            } else if let Some(name) = key.strip_prefix("color.") {
+                match value.parse::<u8>() {
+                    Ok(n) => { colors.insert(name.to_lowercase(), n); }
+                    Err(_) => return Err(HgError::InvalidColor(line_number, value.to_string())),
+                }
+            } else if key != "pagermode" {
+                entries.push((key, value));
+            }
+        }
+        let mut hg = HgColors { mode, colors, theme: Theme::new() };
+        for (label, effects) in entries {
+            let style = hg.effects(effects)?;
+            hg.theme.insert(label, style);
+        }
+        Ok(hg)
+    }
+
+    /// Return the mode set by `mode`.
+    pub fn mode(&self) -> HgMode {
+        self.mode
+    }
+
+    /// Return the style of each label.
+    pub fn theme(&self) -> &Theme {
+        &self.theme
+    }
+
+    /// Parse a list of effects, as given for a label, in this section's
+    /// mode and with its defined colors.
+    pub fn effects(&self, effects: &str) -> Result<Style, HgError> {
+        let mut style = Style::new();
+        for word in effects.split_whitespace() {
+            let lower = word.to_lowercase();
+            let (name, background) = match lower.strip_suffix("_background") {
+                Some(name) => (name, true),
+                None => (lower.as_str(), false),
+            };
+            if let Some(color) = self.color(name) {
+                if background { style.background = Some(color) } else { style.foreground = Some(color) }
+                continue;
+            }
+            let attribute = match (lower.as_str(), self.mode) {
+                ("none", _) => continue,
+                ("bold", _) => Attribute::Bold,
+                ("dim", _) => Attribute::Dimmed,
+                ("italic", _) => Attribute::Italic,
+                ("underline", _) => Attribute::Underline,
+                ("blink", _) => Attribute::Blink,
+                ("inverse", _) | ("reverse", HgMode::Terminfo) | ("standout", HgMode::Terminfo) => Attribute::Reverse,
+                ("invisible", HgMode::Terminfo) => Attribute::Hidden,
+                _ => return Err(HgError::UnknownEffect(effects.to_string(), word.to_string())),
+            };
+            style = attribute.apply(style);
+        }
+        Ok(style)
+    }
+
+    fn color(&self, name: &str) -> Option<Color> {
+        if let Some(n) = COLORS.iter().position(|&c| c == name) {
+            return Some(from_index(n as u8));
+        }
+        match self.mode {
+            HgMode::Terminfo => self.colors.get(name).map(|&n| Color::Fixed(n)),
+            HgMode::Ansi => None,
+        }
+    }
+}
+
+/// Type for errors returned by `HgColors`.
+#[derive(Debug, PartialEq)]
+pub enum HgError {
+    /// A line is not a `key = value` setting; carries the line number and
+    /// the line.
+    InvalidLine(usize, String),
+    /// A `color.<name>` value is not a color number; carries the line
+    /// number and the value.
+    InvalidColor(usize, String),
+    /// An effect is unknown, or not available in the mode; carries the
+    /// list of effects and the effect.
+    UnknownEffect(String, String),
+}
+
+impl fmt::Display for HgError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::InvalidLine(line, s) => write!(fmt, "Error parsing hgrc line {}: not a setting: \"{}\"", line, s),
+            Self::InvalidColor(line, s) => {
+                write!(fmt, "Error parsing hgrc line {}: invalid color number \"{}\"", line, s)
+            }
+            Self::UnknownEffect(s, word) => {
+                write!(fmt, "Error parsing effects \"{}\": unknown effect \"{}\"", s, word)
+            }
+        }
+    }
+}
+
+impl std::error::Error for HgError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ansi_term::Color::*;
+
+    #[test]
+    fn test_hg_colors() {
+        let colors = HgColors::parse("
+# Status colors
+status.added = green bold
+status.removed = red red_background inverse blue
+diff.trailingwhitespace = bold red_background
+pagermode = terminfo
+color.gray = 244
+").unwrap();
+        assert_eq!(colors.mode(), HgMode::Ansi);
+        assert_eq!(colors.theme().get("status.added"), Some(Green.bold()));
+        assert_eq!(colors.theme().get("status.removed"), Some(Blue.on(Red).reverse()));
+        assert_eq!(colors.theme().get("diff.trailingwhitespace"), Some(Style::new().on(Red).bold()));
+        assert_eq!(colors.theme().len(), 3);
+        assert_eq!(colors.effects("gray"), Err(HgError::UnknownEffect("gray".to_string(), "gray".to_string())));
+        assert_eq!(colors.effects("standout"),
+                   Err(HgError::UnknownEffect("standout".to_string(), "standout".to_string())));
+    }
+
+    #[test]
+    fn test_hg_colors_terminfo() {
+        let colors = HgColors::parse("log.tag = Gray_Background invisible reverse\ncolor.gray = 244\nmode = terminfo").unwrap();
+        assert_eq!(colors.theme().get("log.tag"), Some(Style::new().on(Fixed(244)).hidden().reverse()));
+        assert_eq!(HgColors::parse("mode terminfo"), Err(HgError::InvalidLine(1, "mode terminfo".to_string())));
+        assert_eq!(HgColors::parse("color.gray = grey"), Err(HgError::InvalidColor(1, "grey".to_string())));
+    }
+}
@@ -0,0 +1,99 @@
+//! Readers and writers for the color settings of other programs.
+
+mod bsd_lscolors;
+mod dircolors;
+mod fish;
+mod gcc_colors;
+mod gitconfig;
+mod grep_colors;
+#[cfg(feature = "lazygit")]
+mod lazygit;
+mod ls_colors;
+mod mercurial;
+mod powershell;
+mod tig;
+mod tmux;
+mod vim;
+mod zsh;
+
+pub use self::bsd_lscolors::{parse_bsd_lscolors, BsdLscolorsError};
+pub use self::dircolors::{Dircolors, DircolorsError};
+pub use self::fish::fish_set_color;
+pub use self::gcc_colors::{parse_gcc_colors, GccColorsError};
+pub use self::gitconfig::{parse_gitconfig, GitconfigError};
+#[cfg(feature = "git-config")]
+pub use self::gitconfig::{load_from_repo, RepoConfigError};
+pub use self::grep_colors::{GrepColors, GrepColorsError};
+#[cfg(feature = "lazygit")]
+pub use self::lazygit::lazygit_style;
+pub use self::ls_colors::{to_ls_colors, LsColors, LsColorsError};
+pub use self::mercurial::{HgColors, HgError, HgMode};
+pub use self::powershell::{powershell_escape, powershell_psstyle};
+pub use self::tig::{parse_tigrc, TigrcError};
+pub use self::tmux::{parse_tmux_style, to_tmux_style, TmuxStyleError};
+pub use self::vim::{parse_vim_highlight, VimHighlightError, VimPreference};
+pub use self::zsh::{parse_zsh_prompt, ZshSegments};
+
+/// Return true if `text` matches the shell glob `pattern`, which may use
+/// `*`, `?`, and `[...]` character classes with ranges and `!` or `^`
+/// negation.
+pub(crate) fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    glob_match_chars(&pattern, &text)
+}
+
+fn glob_match_chars(pattern: &[char], text: &[char]) -> bool {
+    match pattern.first() {
+        None => text.is_empty(),
+        Some('*') => (0..=text.len()).any(|i| glob_match_chars(&pattern[1..], &text[i..])),
+        Some('?') => !text.is_empty() && glob_match_chars(&pattern[1..], &text[1..]),
+        Some('[') => {
+            let c = match text.first() {
+                Some(&c) => c,
+                None => return false,
+            };
+            let negated = matches!(pattern.get(1), Some('!') | Some('^'));
+            let mut i = if negated { 2 } else { 1 };
+            let mut matched = false;
+            let mut first = true;
+            while i < pattern.len() && (first || pattern[i] != ']') {
+                if pattern.get(i + 1) == Some(&'-') && pattern.get(i + 2).is_some_and(|&end| end != ']') {
+                    matched |= pattern[i] <= c && c <= pattern[i + 2];
+                    i += 3;
+                } else {
+                    matched |= pattern[i] == c;
+                    i += 1;
+                }
+                first = false;
+            }
+            if i >= pattern.len() {
+                // An unterminated class matches a literal `[`.
+                return c == '[' && glob_match_chars(&pattern[1..], &text[1..]);
+            }
+            matched != negated && glob_match_chars(&pattern[i + 1..], &text[1..])
+        }
+        Some(&p) => text.first() == Some(&p) && glob_match_chars(&pattern[1..], &text[1..]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("xterm*", "xterm-256color"));
+        assert!(glob_match("*color*", "screen-256color-bce"));
+        assert!(!glob_match("*color*", "vt100"));
+        assert!(glob_match("con[0-9]*x[0-9]*", "con132x25"));
+        assert!(!glob_match("con[0-9]*x[0-9]*", "conx25"));
+        assert!(glob_match("vt?00", "vt100"));
+        assert!(glob_match("[!a-z]*", "9term"));
+        assert!(!glob_match("[!a-z]*", "linux"));
+        assert!(glob_match("[]]", "]"));
+        assert!(glob_match("a[b", "a[b"));
+        assert!(glob_match("", ""));
+        assert!(!glob_match("?", ""));
+    }
+}
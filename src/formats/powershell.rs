@@ -0,0 +1,91 @@
+use ansi_term::{Color, Style};
+
+use crate::downgrade::{index, to_rgb};
+use crate::render::write_style;
+use crate::Palette;
+
+const NAMES: [&str; 8] = ["Black", "Red", "Green", "Yellow", "Blue", "Magenta", "Cyan", "White"];
+
+/// Return the `$PSStyle` expression for `color` within `group`
+/// (`Foreground` or `Background`).
+fn color_expression(color: Color, group: &str) -> String {
+    match index(color) {
+        Some(n) if n < 8 => format!("$PSStyle.{}.{}", group, NAMES[n as usize]),
+        Some(n) if n < 16 => format!("$PSStyle.{}.Bright{}", group, NAMES[n as usize - 8]),
+        _ => {
+            let (r, g, b) = to_rgb(color, &Palette::VGA.ansi);
+            format!("$PSStyle.{}.FromRgb({}, {}, {})", group, r, g, b)
+        }
+    }
+}
+
+/// Return a PowerShell expression that builds the escape sequence for
+/// `style` from `$PSStyle` (PowerShell 7.2 and later), such as
+/// `$PSStyle.Bold + $PSStyle.Foreground.Red`.
+///
+/// Colors outside the 16 basic colors use `FromRgb`. A plain style gives
+/// `""`.
+///
+/// # Examples
+///
+///     let style = colorparse::parse("bold red 236").unwrap();
+///     assert_eq!(colorparse::formats::powershell_psstyle(&style),
+///                "$PSStyle.Bold + $PSStyle.Foreground.Red + $PSStyle.Background.FromRgb(48, 48, 48)");
+pub fn powershell_psstyle(style: &Style) -> String {
+    let mut terms: Vec<String> = [
+        (style.is_bold, "Bold"),
+        (style.is_dimmed, "Dim"),
+        (style.is_italic, "Italic"),
+        (style.is_underline, "Underline"),
+        (style.is_blink, "Blink"),
+        (style.is_reverse, "Reverse"),
+        (style.is_hidden, "Hidden"),
+        (style.is_strikethrough, "Strikethrough"),
+    ].iter().filter(|&&(set, _)| set).map(|&(_, name)| format!("$PSStyle.{}", name)).collect();
+    if let Some(fg) = style.foreground {
+        terms.push(color_expression(fg, "Foreground"));
+    }
+    if let Some(bg) = style.background {
+        terms.push(color_expression(bg, "Background"));
+    }
+    if terms.is_empty() {
+        return "\"\"".to_string();
+    }
+    terms.join(" + ")
+}
+
+/// Return a double-quoted PowerShell string containing the escape
+/// sequence for `style`, written with `$([char]27)` so it works in
+/// Windows PowerShell 5.1 as well as PowerShell 7.
+///
+/// # Examples
+///
+///     let style = colorparse::parse("bold red").unwrap();
+///     assert_eq!(colorparse::formats::powershell_escape(&style), "\"$([char]27)[1;31m\"");
+pub fn powershell_escape(style: &Style) -> String {
+    let mut sequence = String::new();
+    write_style(&mut sequence, style).unwrap();
+    format!("\"{}\"", sequence.replace('\x1b', "$([char]27)"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ansi_term::Color::*;
+
+    #[test]
+    fn test_powershell_psstyle() {
+        assert_eq!(powershell_psstyle(&Style::new()), "\"\"");
+        assert_eq!(powershell_psstyle(&Fixed(9).on(Blue)), "$PSStyle.Foreground.BrightRed + $PSStyle.Background.Blue");
+        assert_eq!(powershell_psstyle(&RGB(1, 2, 3).italic().strikethrough()),
+                   "$PSStyle.Italic + $PSStyle.Strikethrough + $PSStyle.Foreground.FromRgb(1, 2, 3)");
+        assert_eq!(powershell_psstyle(&Style::new().dimmed().blink().reverse().hidden().underline()),
+                   "$PSStyle.Dim + $PSStyle.Underline + $PSStyle.Blink + $PSStyle.Reverse + $PSStyle.Hidden");
+    }
+
+    #[test]
+    fn test_powershell_escape() {
+        assert_eq!(powershell_escape(&Style::new()), "\"\"");
+        assert_eq!(powershell_escape(&Fixed(208).on(Black).underline()), "\"$([char]27)[4;38;5;208;40m\"");
+    }
+}
@@ -0,0 +1,180 @@
+use std::fmt;
+
+use ansi_term::{Color, Style};
+
+use crate::{color_word, Attribute, Theme};
+
+/// Split a tigrc line into words, keeping quoted strings together without
+/// their quotes and stopping at a `#` comment.
+fn words(line: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut chars = line.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+        } else if c == '#' {
+            break;
+        } else if c == '"' || c == '\'' {
+            chars.next();
+            words.push(chars.by_ref().take_while(|&d| d != c).collect());
+        } else {
+            let mut word = String::new();
+            while let Some(&d) = chars.peek() {
+                if d.is_whitespace() {
+                    break;
+                }
+                word.push(d);
+                chars.next();
+            }
+            words.push(word);
+        }
+    }
+    words
+}
+
+fn color(word: &str) -> Result<Option<Color>, ()> {
+    let word = word.to_lowercase();
+    if word == "default" {
+        return Ok(None);
+    }
+    if let Some(n) = word.strip_prefix("color") {
+        if let Ok(n) = n.parse::<u8>() {
+            return Ok(Some(Color::Fixed(n)));
+        }
+    }
+    color_word(&word)
+}
+
+fn attribute(word: &str) -> Result<Option<Attribute>, ()> {
+    Ok(Some(match word.to_lowercase().as_ref() {
+        "normal" => return Ok(None),
+        "blink" => Attribute::Blink,
+        "bold" => Attribute::Bold,
+        "dim" => Attribute::Dimmed,
+        "italic" => Attribute::Italic,
+        "reverse" | "standout" => Attribute::Reverse,
+        "underline" => Attribute::Underline,
+        _ => return Err(()),
+    }))
+}
+
+/// Parse the `color` lines of a tig configuration file (`~/.tigrc`) into
+/// a theme keyed by area.
+///
+/// Each `color <area> <fg> <bg> [attributes]` line sets the style of its
+/// area. Areas are named as written, lowercased with `_` replaced by `-`,
+/// so `main_commit` and `Main-Commit` both become `main-commit`; quoted
+/// areas, which match the start of a line of output, keep their exact
+/// text without the quotes. Colors are `default`, `color0` through
+/// `color255`, or a color name or number in Git's vocabulary (a `#`
+/// outside quotes starts a comment, as in tig); attributes are tig's
+/// `normal`, `blink`, `bold`, `dim`, `italic`, `reverse`, `standout` (the
+/// same as `reverse`), and `underline`. Other lines, such as `set` and
+/// `bind`, are skipped, and a later line for an area replaces an earlier
+/// one.
+///
+/// # Examples
+///
+///     # extern crate ansi_term;
+///     # extern crate colorparse;
+///     use ansi_term::Color::{Blue, Fixed, Yellow};
+///
+///     # fn main() {
+///     let theme = colorparse::formats::parse_tigrc(r#"
+///     set main-view = date author commit-title
+///     color cursor white blue bold
+///     color main_commit color244 default
+///     color "diff --git " yellow default underline  ## file headers
+///     "#).unwrap();
+///     assert_eq!(theme.get("cursor"), Some(ansi_term::Color::White.on(Blue).bold()));
+///     assert_eq!(theme.get("main-commit"), Some(Fixed(244).normal()));
+///     assert_eq!(theme.get("diff --git "), Some(Yellow.underline()));
+///     # }
+pub fn parse_tigrc(s: &str) -> Result<Theme, TigrcError> {
+    let mut theme = Theme::new();
+    for (i, line) in s.lines().enumerate() {
+        let line_number = i + 1;
+        let words = words(line);
+        if words.first().map(String::as_str) != Some("color") {
+            continue;
+        }
+        if words.len() < 4 {
+            return Err(TigrcError::MissingColor(line_number, line.to_string()));
+        }
+        let quoted = line.trim_start()["color".len()..].trim_start().starts_with(['"', '\'']);
+        let area = if quoted { words[1].clone() } else { words[1].to_lowercase().replace('_', "-") };
+        let mut style = Style::new();
+        style.foreground = color(&words[2]).map_err(|()| TigrcError::UnknownColor(line_number, words[2].clone()))?;
+        style.background = color(&words[3]).map_err(|()| TigrcError::UnknownColor(line_number, words[3].clone()))?;
+        for word in &words[4..] {
+            match attribute(word) {
+                Ok(Some(attribute)) => style = attribute.apply(style),
+                Ok(None) => {}
+                Err(()) => return Err(TigrcError::UnknownAttribute(line_number, word.clone())),
+            }
+        }
+        theme.insert(area, style);
+    }
+    Ok(theme)
+}
+
+/// Type for errors returned by `parse_tigrc`.
+#[derive(Debug, PartialEq)]
+pub enum TigrcError {
+    /// A `color` line lacks an area, a foreground, or a background;
+    /// carries the line number and the line.
+    MissingColor(usize, String),
+    /// A color is not one tig supports; carries the line number and the
+    /// color.
+    UnknownColor(usize, String),
+    /// An attribute is not one tig supports; carries the line number and
+    /// the attribute.
+    UnknownAttribute(usize, String),
+}
+
+impl fmt::Display for TigrcError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::MissingColor(line, s) => write!(fmt, "Error parsing tigrc line {}: missing color: \"{}\"", line, s),
+            Self::UnknownColor(line, word) => write!(fmt, "Error parsing tigrc line {}: unknown color \"{}\"", line, word),
+            Self::UnknownAttribute(line, word) => {
+                write!(fmt, "Error parsing tigrc line {}: unknown attribute \"{}\"", line, word)
+            }
+        }
+    }
+}
+
+impl std::error::Error for TigrcError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ansi_term::Color::*;
+
+    #[test]
+    fn test_parse_tigrc() {
+        let theme = parse_tigrc("
+# Colors
+color default 15 235
+color date cyan default bold normal
+color 'Author:' 208 default italic # quoted
+	color	Title_Focus	default	red	standout	blink
+bind generic G move-last-line
+").unwrap();
+        assert_eq!(theme.get("default"), Some(Fixed(15).on(Fixed(235))));
+        assert_eq!(theme.get("date"), Some(Cyan.bold()));
+        assert_eq!(theme.get("Author:"), Some(Fixed(208).italic()));
+        assert_eq!(theme.get("title-focus"), Some(Style::new().on(Red).reverse().blink()));
+        assert_eq!(theme.len(), 4);
+    }
+
+    #[test]
+    fn test_parse_tigrc_err() {
+        assert_eq!(parse_tigrc("\ncolor cursor white"), Err(TigrcError::MissingColor(2, "color cursor white".to_string())));
+        assert_eq!(parse_tigrc("color cursor white bleu"), Err(TigrcError::UnknownColor(1, "bleu".to_string())));
+        assert_eq!(parse_tigrc("color cursor white blue shiny"),
+                   Err(TigrcError::UnknownAttribute(1, "shiny".to_string())));
+        assert_eq!(TigrcError::UnknownColor(3, "bleu".to_string()).to_string(),
+                   "Error parsing tigrc line 3: unknown color \"bleu\"");
+    }
+}
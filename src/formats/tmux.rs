@@ -0,0 +1,247 @@
+use std::fmt;
+
+use ansi_term::Color;
+
+use crate::downgrade::index;
+use crate::{color_word, Attribute, StyleSpec, UnderlineStyle};
+
+const NAMES: [&str; 8] = ["black", "red", "green", "yellow", "blue", "magenta", "cyan", "white"];
+
+/// The attributes tmux names, and the attribute and underline shape each
+/// selects, in the order `to_tmux_style` writes them.
+const ATTRIBUTES: [(&str, Attribute, UnderlineStyle); 13] = [
+    ("bold", Attribute::Bold, UnderlineStyle::Single),
+    ("dim", Attribute::Dimmed, UnderlineStyle::Single),
+    ("italics", Attribute::Italic, UnderlineStyle::Single),
+    ("underscore", Attribute::Underline, UnderlineStyle::Single),
+    ("double-underscore", Attribute::Underline, UnderlineStyle::Double),
+    ("curly-underscore", Attribute::Underline, UnderlineStyle::Curly),
+    ("dotted-underscore", Attribute::Underline, UnderlineStyle::Dotted),
+    ("dashed-underscore", Attribute::Underline, UnderlineStyle::Dashed),
+    ("blink", Attribute::Blink, UnderlineStyle::Single),
+    ("reverse", Attribute::Reverse, UnderlineStyle::Single),
+    ("hidden", Attribute::Hidden, UnderlineStyle::Single),
+    ("strikethrough", Attribute::Strikethrough, UnderlineStyle::Single),
+    ("overline", Attribute::Overline, UnderlineStyle::Single),
+];
+
+/// Words of tmux styles that control the layout of the status line rather
+/// than the look of text.
+const LAYOUT: [&str; 7] = ["push-default", "pop-default", "ignore", "noignore", "noalign", "nolist", "norange"];
+
+/// Return the attribute and underline shape named `name`, accepting
+/// `bright` for bold as tmux does.
+fn attribute(name: &str) -> Option<(Attribute, UnderlineStyle)> {
+    let name = if name == "bright" { "bold" } else { name };
+    ATTRIBUTES.iter().find(|&&(known, _, _)| known == name).map(|&(_, attribute, style)| (attribute, style))
+}
+
+/// Parse a tmux color: `default`, `terminal`, `colour0` through
+/// `colour255` (or `color`), a name, a `bright` name, or `#rrggbb`.
+fn color(word: &str) -> Result<Option<Color>, ()> {
+    if word == "default" || word == "terminal" {
+        return Ok(None);
+    }
+    if let Some(n) = word.strip_prefix("colour").or_else(|| word.strip_prefix("color")) {
+        return n.parse::<u8>().map(|n| Some(Color::Fixed(n))).map_err(|_| ());
+    }
+    match color_word(word) {
+        Ok(Some(color)) if word != "normal" => Ok(Some(color)),
+        _ => Err(()),
+    }
+}
+
+/// Parse a tmux style, as given to options such as `status-style` and in
+/// `#[...]` in formats, into a spec.
+///
+/// Words are separated by commas or spaces, and case does not matter.
+/// `fg=`, `bg=`, and `us=` set the foreground, background, and underline
+/// color to `default` (or `terminal`), one of tmux's color names,
+/// `colour0` through `colour255`, or `#rrggbb`; `default` alone starts
+/// over with a reset; `none` clears every attribute; and the attributes
+/// are tmux's names, such as `bright` or `bold`, `underscore`,
+/// `curly-underscore`, `italics`, and `strikethrough`, with a `no` prefix
+/// to clear one. The words that lay out the status line, such as
+/// `align=left` and `list=on`, are skipped.
+///
+/// # Examples
+///
+///     # extern crate ansi_term;
+///     # extern crate colorparse;
+///     use ansi_term::Color::{Black, Fixed};
+///     use colorparse::formats::{parse_tmux_style, to_tmux_style};
+///     use colorparse::{parse_spec, StyleSpec};
+///
+///     # fn main() {
+///     let spec = parse_tmux_style("fg=colour208,bg=black,bold,underscore").unwrap();
+///     assert_eq!(spec, StyleSpec::builder().fg(Fixed(208)).bg(Black).bold().underline().build());
+///     assert_eq!(spec, parse_spec("208 black bold ul").unwrap());
+///     assert_eq!(to_tmux_style(&parse_spec("italic curly-ul #ff0000").unwrap()),
+///                "italics,curly-underscore,fg=#ff0000");
+///     # }
+pub fn parse_tmux_style(s: &str) -> Result<StyleSpec, TmuxStyleError> {
+    let mut spec = StyleSpec::new();
+    for word in s.split(|c: char| c == ',' || c.is_whitespace()).filter(|word| !word.is_empty()) {
+        let lower = word.to_lowercase();
+        let unknown = || TmuxStyleError::UnknownWord(word.to_string());
+        let builder = StyleSpec::builder();
+        if let Some(pos) = lower.find('=') {
+            let (key, value) = (&lower[..pos], &lower[pos + 1..]);
+            let builder = match key {
+                "fg" | "bg" | "us" => match (key, color(value).map_err(|()| unknown())?) {
+                    ("fg", Some(color)) => builder.fg(color),
+                    ("fg", None) => builder.default_fg(),
+                    ("bg", Some(color)) => builder.bg(color),
+                    ("bg", None) => builder.default_bg(),
+                    (_, Some(color)) => builder.underline_color(color),
+                    (_, None) => {
+                        spec.underline_color = None;
+                        continue;
+                    }
+                },
+                "align" | "list" | "range" | "fill" | "width" => continue,
+                _ => return Err(unknown()),
+            };
+            spec += builder.build();
+        } else if lower == "default" {
+            spec = builder.reset().build();
+        } else if lower == "none" {
+            spec += Attribute::ALL.iter().fold(builder, |builder, &attribute| builder.no(attribute)).build();
+        } else if let Some((attribute, style)) = attribute(&lower) {
+            let builder = builder.attribute(attribute);
+            spec += if attribute == Attribute::Underline { builder.underline_style(style) } else { builder }.build();
+        } else if let Some((attribute, _)) = lower.strip_prefix("no").and_then(attribute) {
+            spec += builder.no(attribute).build();
+        } else if !LAYOUT.contains(&lower.as_str()) {
+            return Err(unknown());
+        }
+    }
+    Ok(spec)
+}
+
+/// Return the tmux name of `color`.
+fn color_name(color: Color) -> String {
+    match (color, index(color)) {
+        (Color::Fixed(n), _) => format!("colour{}", n),
+        (Color::RGB(r, g, b), _) => format!("#{:02x}{:02x}{:02x}", r, g, b),
+        (_, Some(n)) => NAMES[n as usize].to_string(),
+        (_, None) => unreachable!(),
+    }
+}
+
+/// Write `spec` as a tmux style, the inverse of `parse_tmux_style`.
+///
+/// Attributes come first, then `fg=`, `bg=`, and `us=`, separated by
+/// commas, with `default` first for a reset and `fg=default` and
+/// `bg=default` for the default colors. tmux has no rapid blink, so it
+/// becomes `blink`. A plain spec gives an empty string.
+pub fn to_tmux_style(spec: &StyleSpec) -> String {
+    let mut words = Vec::new();
+    if spec.is_reset() {
+        words.push("default".to_string());
+    }
+    let blink = spec.has(Attribute::Blink) || spec.has(Attribute::RapidBlink);
+    for &(name, attribute, style) in &ATTRIBUTES {
+        let set = match attribute {
+            Attribute::Underline => spec.has(attribute) && spec.underline_style() == style,
+            Attribute::Blink => blink,
+            _ => spec.has(attribute),
+        };
+        if set {
+            words.push(name.to_string());
+        }
+    }
+    for &(name, attribute, _) in ATTRIBUTES.iter().filter(|&&(_, _, style)| style == UnderlineStyle::Single) {
+        let cleared = match attribute {
+            Attribute::Blink => !blink && (spec.clears(Attribute::Blink) || spec.clears(Attribute::RapidBlink)),
+            _ => spec.clears(attribute),
+        };
+        if cleared {
+            words.push(format!("no{}", name));
+        }
+    }
+    match spec.foreground() {
+        Some(fg) => words.push(format!("fg={}", color_name(fg))),
+        None if spec.is_default_fg() => words.push("fg=default".to_string()),
+        None => {}
+    }
+    match spec.background() {
+        Some(bg) => words.push(format!("bg={}", color_name(bg))),
+        None if spec.is_default_bg() => words.push("bg=default".to_string()),
+        None => {}
+    }
+    if let Some(ul) = spec.underline_color() {
+        words.push(format!("us={}", color_name(ul)));
+    }
+    words.join(",")
+}
+
+/// Type for errors returned by `parse_tmux_style`.
+#[derive(Debug, PartialEq)]
+pub enum TmuxStyleError {
+    /// A word is not part of tmux's style syntax, or gives an invalid
+    /// color; carries the word.
+    UnknownWord(String),
+}
+
+impl fmt::Display for TmuxStyleError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::UnknownWord(word) => write!(fmt, "Error parsing tmux style: unknown word \"{}\"", word),
+        }
+    }
+}
+
+impl std::error::Error for TmuxStyleError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_spec;
+
+    #[test]
+    fn test_parse_tmux_style() {
+        macro_rules! test {
+            ($s:expr => $spec:expr) => {
+                assert_eq!(parse_tmux_style($s), Ok(parse_spec($spec).unwrap()), "{}", $s);
+            };
+        }
+
+        test!("" => "");
+        test!("fg=Red bg=colour0, Bright" => "red 0 bold");
+        test!("fg=brightblue,bg=#102030,us=yellow,double-underscore" => "12 #102030 double-ul ul=yellow");
+        test!("fg=default,bg=terminal" => "default default");
+        test!("bold,italics,default,fg=green" => "reset green");
+        test!("bold,reverse,none" => "nobold nodim noitalic noul noblink noreverse nohidden nostrike nooverline norapidblink");
+        test!("nobold,noblink,nounderscore,dim,overline,strikethrough" => "nobold noblink noul dim overline strike");
+        test!("align=centre,list=on,push-default,fill=blue,range=window|1,fg=red" => "red");
+        assert_eq!(parse_tmux_style("us=default,us=red,us=default"), Ok(StyleSpec::new()));
+    }
+
+    #[test]
+    fn test_to_tmux_style() {
+        macro_rules! test {
+            ($spec:expr => $s:expr) => {
+                let spec = parse_spec($spec).unwrap();
+                assert_eq!(to_tmux_style(&spec), $s, "{}", $spec);
+                assert_eq!(parse_tmux_style($s), Ok(spec), "{}", $s);
+            };
+        }
+
+        test!("" => "");
+        test!("bold red 208" => "bold,fg=red,bg=colour208");
+        test!("reset dim curly-ul ul=#010203 default" => "default,dim,curly-underscore,fg=default,us=#010203");
+        test!("nobold noul nodim noblink 9" => "nobold,nodim,nounderscore,noblink,fg=colour9");
+        test!("normal default hidden reverse strike overline" => "reverse,hidden,strikethrough,overline,bg=default");
+        assert_eq!(to_tmux_style(&parse_spec("rapidblink norapidblink").unwrap()), "noblink");
+        assert_eq!(to_tmux_style(&parse_spec("rapidblink noblink").unwrap()), "blink");
+    }
+
+    #[test]
+    fn test_parse_tmux_style_err() {
+        for &word in &["fg=redd", "bg=colour256", "us", "sparkly", "nofoo", "fg=normal"] {
+            assert_eq!(parse_tmux_style(&format!("bold,{}", word)), Err(TmuxStyleError::UnknownWord(word.to_string())));
+        }
+        assert_eq!(TmuxStyleError::UnknownWord("x".to_string()).to_string(), "Error parsing tmux style: unknown word \"x\"");
+    }
+}
@@ -0,0 +1,230 @@
+use std::fmt;
+
+use ansi_term::Color;
+
+use crate::downgrade::from_index;
+use crate::{Attribute, StyleSpec, UnderlineStyle};
+
+/// Vim's color names, and the index of each among the 16 basic colors.
+const COLORS: [(&str, u8); 27] = [
+    ("black", 0), ("darkblue", 4), ("darkgreen", 2), ("darkcyan", 6), ("darkred", 1), ("darkmagenta", 5),
+    ("brown", 3), ("darkyellow", 3), ("gray", 7), ("grey", 7), ("lightgray", 7), ("lightgrey", 7),
+    ("darkgray", 8), ("darkgrey", 8), ("blue", 12), ("lightblue", 12), ("green", 10), ("lightgreen", 10),
+    ("cyan", 14), ("lightcyan", 14), ("red", 9), ("lightred", 9), ("magenta", 13), ("lightmagenta", 13),
+    ("yellow", 11), ("lightyellow", 11), ("white", 15),
+];
+
+/// Vim's attribute names, and the attribute and underline shape each
+/// selects.
+const ATTRIBUTES: [(&str, Attribute, UnderlineStyle); 12] = [
+    ("bold", Attribute::Bold, UnderlineStyle::Single),
+    ("italic", Attribute::Italic, UnderlineStyle::Single),
+    ("underline", Attribute::Underline, UnderlineStyle::Single),
+    ("underdouble", Attribute::Underline, UnderlineStyle::Double),
+    ("undercurl", Attribute::Underline, UnderlineStyle::Curly),
+    ("underdotted", Attribute::Underline, UnderlineStyle::Dotted),
+    ("underdashed", Attribute::Underline, UnderlineStyle::Dashed),
+    ("reverse", Attribute::Reverse, UnderlineStyle::Single),
+    ("inverse", Attribute::Reverse, UnderlineStyle::Single),
+    ("standout", Attribute::Reverse, UnderlineStyle::Single),
+    ("strikethrough", Attribute::Strikethrough, UnderlineStyle::Single),
+    ("blink", Attribute::Blink, UnderlineStyle::Single),
+];
+
+/// Which of Vim's two sets of highlight arguments `parse_vim_highlight`
+/// prefers when both are given.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum VimPreference {
+    /// The arguments for the GUI and for terminals with `'termguicolors'`:
+    /// `gui`, `guifg`, `guibg`, and `guisp`.
+    Gui,
+    /// The arguments for color terminals: `cterm`, `ctermfg`, `ctermbg`,
+    /// and `ctermul`.
+    Cterm,
+}
+
+/// The highlighting one set of arguments gives, each part `None` if the
+/// set leaves it out, and each color `None` for `NONE`.
+#[derive(Default)]
+struct Side {
+    fg: Option<Option<Color>>,
+    bg: Option<Option<Color>>,
+    ul: Option<Option<Color>>,
+    attributes: Option<Vec<(Attribute, UnderlineStyle)>>,
+}
+
+/// Parse a Vim color: a number, one of Vim's names, `#rrggbb`, or `NONE`,
+/// `fg`, or `bg` (and their long forms), which leave the color to the
+/// terminal.
+fn color(value: &str) -> Option<Option<Color>> {
+    let lower = value.to_lowercase();
+    if ["none", "fg", "bg", "foreground", "background"].contains(&lower.as_str()) {
+        return Some(None);
+    }
+    if let Ok(n) = lower.parse::<u8>() {
+        return Some(Some(if n < 16 { from_index(n) } else { Color::Fixed(n) }));
+    }
+    if let Some(hex) = lower.strip_prefix('#').filter(|hex| hex.len() == 6) {
+        let channel = |i: usize| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok();
+        return Some(Some(Color::RGB(channel(0)?, channel(2)?, channel(4)?)));
+    }
+    COLORS.iter().find(|&&(name, _)| name == lower).map(|&(_, n)| Some(from_index(n)))
+}
+
+/// Parse a comma-separated list of Vim attributes, or `NONE`.
+fn attributes(value: &str) -> Result<Vec<(Attribute, UnderlineStyle)>, String> {
+    let mut list = Vec::new();
+    for name in value.split(',') {
+        let lower = name.to_lowercase();
+        if lower == "none" || lower == "nocombine" {
+            continue;
+        }
+        match ATTRIBUTES.iter().find(|&&(known, _, _)| known == lower) {
+            Some(&(_, attribute, style)) => list.push((attribute, style)),
+            None => return Err(name.to_string()),
+        }
+    }
+    Ok(list)
+}
+
+/// Parse the arguments of a Vim `:highlight` command, such as
+/// `ctermfg=red ctermbg=235 cterm=bold guifg=#ff0000 gui=italic`, into a
+/// spec, taking each part from the arguments of `preference` where they
+/// give it and from the others where not.
+///
+/// Colors are numbers, which give the basic colors below 16 and the
+/// 256-color palette above; Vim's color names, such as `DarkBlue` and
+/// `LightGray`, which give the basic colors as they do in a 16-color
+/// terminal; `#rrggbb`; or `NONE`, `fg`, and `bg`, which leave the color
+/// unset. Attributes are comma-separated lists of Vim's names, such as
+/// `bold,undercurl`, or `NONE`. `guisp` and `ctermul` give the underline
+/// color. Arguments for other purposes, such as `term`, `font`, and
+/// `blend`, are skipped.
+///
+/// # Examples
+///
+///     # extern crate ansi_term;
+///     # extern crate colorparse;
+///     use ansi_term::Color::{Fixed, Red, RGB};
+///     use colorparse::formats::{parse_vim_highlight, VimPreference};
+///     use colorparse::StyleSpec;
+///
+///     # fn main() {
+///     let args = "ctermfg=red ctermbg=235 cterm=bold guifg=#ff0000 gui=italic";
+///     assert_eq!(parse_vim_highlight(args, VimPreference::Cterm),
+///                Ok(StyleSpec::builder().fg(Fixed(9)).bg(Fixed(235)).bold().build()));
+///     assert_eq!(parse_vim_highlight(args, VimPreference::Gui),
+///                Ok(StyleSpec::builder().fg(RGB(255, 0, 0)).bg(Fixed(235)).italic().build()));
+///     assert_eq!(parse_vim_highlight("ctermfg=1", VimPreference::Gui),
+///                Ok(StyleSpec::builder().fg(Red).build()));
+///     # }
+pub fn parse_vim_highlight(s: &str, preference: VimPreference) -> Result<StyleSpec, VimHighlightError> {
+    let (mut gui, mut cterm) = (Side::default(), Side::default());
+    for arg in s.split_whitespace() {
+        let (key, value) = match arg.find('=') {
+            Some(pos) => (arg[..pos].to_lowercase(), &arg[pos + 1..]),
+            None => return Err(VimHighlightError::InvalidArgument(arg.to_string())),
+        };
+        let invalid_color = || VimHighlightError::InvalidColor(key.clone(), value.to_string());
+        match key.as_str() {
+            "guifg" => gui.fg = Some(color(value).ok_or_else(invalid_color)?),
+            "guibg" => gui.bg = Some(color(value).ok_or_else(invalid_color)?),
+            "guisp" => gui.ul = Some(color(value).ok_or_else(invalid_color)?),
+            "ctermfg" => cterm.fg = Some(color(value).ok_or_else(invalid_color)?),
+            "ctermbg" => cterm.bg = Some(color(value).ok_or_else(invalid_color)?),
+            "ctermul" => cterm.ul = Some(color(value).ok_or_else(invalid_color)?),
+            "gui" | "cterm" => {
+                let list = attributes(value).map_err(VimHighlightError::UnknownAttribute)?;
+                if key == "gui" { gui.attributes = Some(list) } else { cterm.attributes = Some(list) }
+            }
+            "term" | "start" | "stop" | "font" | "blend" => {}
+            _ => return Err(VimHighlightError::InvalidArgument(arg.to_string())),
+        }
+    }
+    let (first, second) = match preference {
+        VimPreference::Gui => (gui, cterm),
+        VimPreference::Cterm => (cterm, gui),
+    };
+    let mut builder = StyleSpec::builder();
+    if let Some(fg) = first.fg.or(second.fg).and_then(|fg| fg) {
+        builder = builder.fg(fg);
+    }
+    if let Some(bg) = first.bg.or(second.bg).and_then(|bg| bg) {
+        builder = builder.bg(bg);
+    }
+    for (attribute, style) in first.attributes.or(second.attributes).unwrap_or_default() {
+        builder = match attribute {
+            Attribute::Underline => builder.underline_style(style),
+            _ => builder.attribute(attribute),
+        };
+    }
+    if let Some(ul) = first.ul.or(second.ul).and_then(|ul| ul) {
+        builder = builder.underline_color(ul);
+    }
+    Ok(builder.build())
+}
+
+/// Type for errors returned by `parse_vim_highlight`.
+#[derive(Debug, PartialEq)]
+pub enum VimHighlightError {
+    /// An argument is not `key=value` with one of Vim's keys; carries the
+    /// argument.
+    InvalidArgument(String),
+    /// A color is not one Vim knows; carries the key and the color.
+    InvalidColor(String, String),
+    /// An attribute is not one Vim knows; carries the attribute.
+    UnknownAttribute(String),
+}
+
+impl fmt::Display for VimHighlightError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::InvalidArgument(arg) => write!(fmt, "Error parsing Vim highlight: invalid argument \"{}\"", arg),
+            Self::InvalidColor(key, color) => {
+                write!(fmt, "Error parsing Vim highlight: invalid color \"{}\" for \"{}\"", color, key)
+            }
+            Self::UnknownAttribute(name) => write!(fmt, "Error parsing Vim highlight: unknown attribute \"{}\"", name),
+        }
+    }
+}
+
+impl std::error::Error for VimHighlightError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::VimPreference::*;
+    use crate::parse_spec;
+
+    #[test]
+    fn test_parse_vim_highlight() {
+        macro_rules! test {
+            ($s:expr, $preference:expr => $spec:expr) => {
+                assert_eq!(parse_vim_highlight($s, $preference), Ok(parse_spec($spec).unwrap()), "{}", $s);
+            };
+        }
+
+        test!("", Gui => "");
+        test!("term=bold ctermfg=DarkBlue ctermbg=NONE cterm=NONE", Cterm => "blue");
+        test!("term=bold ctermfg=DarkBlue ctermbg=NONE cterm=NONE", Gui => "blue");
+        test!("ctermfg=LightGrey ctermbg=8 guifg=#FFA500 guibg=bg", Gui => "#ffa500");
+        test!("cterm=bold,undercurl ctermul=196 gui=underline guisp=Red", Cterm => "bold curly-ul ul=196");
+        test!("cterm=bold,undercurl ctermul=196 gui=underline guisp=Red", Gui => "ul ul=9");
+        test!("cterm=reverse gui=NONE", Gui => "");
+        test!("CTERM=Standout,Strikethrough,nocombine font=Monospace blend=20", Cterm => "reverse strike");
+    }
+
+    #[test]
+    fn test_parse_vim_highlight_err() {
+        assert_eq!(parse_vim_highlight("Comment ctermfg=1", Gui),
+                   Err(VimHighlightError::InvalidArgument("Comment".to_string())));
+        assert_eq!(parse_vim_highlight("ctermfg=256", Gui),
+                   Err(VimHighlightError::InvalidColor("ctermfg".to_string(), "256".to_string())));
+        assert_eq!(parse_vim_highlight("guibg=#12345", Gui),
+                   Err(VimHighlightError::InvalidColor("guibg".to_string(), "#12345".to_string())));
+        assert_eq!(parse_vim_highlight("gui=bold,sparkly", Cterm),
+                   Err(VimHighlightError::UnknownAttribute("sparkly".to_string())));
+        assert_eq!(parse_vim_highlight("link=Comment", Cterm),
+                   Err(VimHighlightError::InvalidArgument("link=Comment".to_string())));
+    }
+}
@@ -0,0 +1,160 @@
+use ansi_term::Color;
+
+use crate::downgrade::from_index;
+use crate::render::appearance;
+use crate::{color_word, Attribute, StyleSpec};
+
+const NAMES: [&str; 8] = ["black", "red", "green", "yellow", "blue", "magenta", "cyan", "white"];
+
+/// Parse the color of a `%F` or `%K` escape: a name, a number, which
+/// gives the basic colors below 8 as zsh does, `#rrggbb`, or `default`,
+/// returning `None` for one zsh does not know.
+fn color(arg: &str) -> Option<Option<Color>> {
+    let lower = arg.to_lowercase();
+    if lower == "default" {
+        return Some(None);
+    }
+    if let Some(n) = NAMES.iter().position(|&name| name == lower) {
+        return Some(Some(from_index(n as u8)));
+    }
+    if let Ok(n) = lower.parse::<u8>() {
+        return Some(Some(if n < 8 { from_index(n) } else { Color::Fixed(n) }));
+    }
+    match color_word(&lower) {
+        Ok(Some(color @ Color::RGB(..))) if lower.starts_with('#') => Some(Some(color)),
+        _ => None,
+    }
+}
+
+/// An iterator over the runs of text in a zsh prompt string, each with
+/// the style its prompt escapes give it, as returned by
+/// `parse_zsh_prompt`.
+#[derive(Clone, Debug)]
+pub struct ZshSegments<'a> {
+    rest: &'a str,
+    spec: StyleSpec,
+}
+
+impl<'a> ZshSegments<'a> {
+    /// Apply the escape at the start of `rest`, which starts with `%`, and
+    /// return its length, or `None` if it is not one that sets the style.
+    fn escape(&mut self) -> Option<usize> {
+        let bytes = self.rest.as_bytes();
+        let digits = bytes[1..].iter().take_while(|b| b.is_ascii_digit()).count();
+        let number = &self.rest[1..1 + digits];
+        let mut len = 2 + digits;
+        let builder = StyleSpec::builder();
+        let overlay = match *bytes.get(1 + digits)? {
+            c @ b'F' | c @ b'K' => {
+                let arg = if bytes.get(len) == Some(&b'{') {
+                    let end = self.rest[len..].find('}')?;
+                    let arg = &self.rest[len + 1..len + end];
+                    len += end + 1;
+                    arg
+                } else {
+                    number
+                };
+                match (c, if arg.is_empty() { Some(None) } else { color(arg) }) {
+                    (b'F', Some(Some(color))) => builder.fg(color),
+                    (b'F', Some(None)) => { self.spec.foreground = None; builder }
+                    (_, Some(Some(color))) => builder.bg(color),
+                    (_, Some(None)) => { self.spec.background = None; builder }
+                    (_, None) => builder,
+                }
+            }
+            b'f' => { self.spec.foreground = None; builder }
+            b'k' => { self.spec.background = None; builder }
+            b'B' => builder.bold(),
+            b'b' => builder.no(Attribute::Bold),
+            b'U' => builder.underline(),
+            b'u' => builder.no(Attribute::Underline),
+            b'S' => builder.reverse(),
+            b's' => builder.no(Attribute::Reverse),
+            b'{' => return Some(self.rest.find("%}").map_or(self.rest.len(), |end| end + 2)),
+            _ => return None,
+        };
+        self.spec += overlay.build();
+        Some(len)
+    }
+}
+
+impl<'a> Iterator for ZshSegments<'a> {
+    type Item = (StyleSpec, &'a str);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.rest.is_empty() {
+            if self.rest.starts_with("%%") {
+                let text = &self.rest[1..2];
+                self.rest = &self.rest[2..];
+                return Some((appearance(&self.spec), text));
+            }
+            let len = if self.rest.starts_with('%') { self.escape() } else { None };
+            if let Some(len) = len {
+                self.rest = &self.rest[len..];
+                continue;
+            }
+            let end = self.rest[1..].find('%').map_or(self.rest.len(), |i| i + 1);
+            let (text, rest) = self.rest.split_at(end);
+            self.rest = rest;
+            return Some((appearance(&self.spec), text));
+        }
+        None
+    }
+}
+
+/// Split a zsh prompt string, such as the value of `PROMPT`, into runs of
+/// text, each with the style that the prompt escapes before it give it,
+/// for analyzing or converting zsh prompts.
+///
+/// The escapes that set the style are `%F{color}` and `%K{color}` for the
+/// foreground and background, with the color a name, a number, `#rrggbb`,
+/// or `default`, and also written as a number argument as in `%208F`;
+/// `%f` and `%k` to restore the default colors; and `%B`, `%U`, and `%S`
+/// to start bold, underline, and standout (reverse video), which `%b`,
+/// `%u`, and `%s` end. Each style is the complete appearance of its text,
+/// as in `parse_ansi`. Raw escape sequences in `%{...%}` are skipped, a
+/// `%%` gives `%`, and the other escapes, such as `%n` and `%~`, are kept
+/// in the text as written, as are colors zsh does not know.
+///
+/// # Examples
+///
+///     use colorparse::formats::parse_zsh_prompt;
+///     use colorparse::{parse_spec, StyleSpec};
+///
+///     let segments: Vec<_> = parse_zsh_prompt("%B%F{red}%n%f@%m%b 100%% ").collect();
+///     assert_eq!(segments, [
+///         (parse_spec("bold red").unwrap(), "%n"),
+///         (parse_spec("bold").unwrap(), "@"),
+///         (parse_spec("bold").unwrap(), "%m"),
+///         (StyleSpec::new(), " 100"),
+///         (StyleSpec::new(), "%"),
+///         (StyleSpec::new(), " "),
+///     ]);
+pub fn parse_zsh_prompt(s: &str) -> ZshSegments<'_> {
+    ZshSegments { rest: s, spec: StyleSpec::new() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_spec;
+
+    fn segments(s: &str) -> Vec<(StyleSpec, &str)> {
+        parse_zsh_prompt(s).collect()
+    }
+
+    #[test]
+    fn test_parse_zsh_prompt() {
+        let seg = |spec, text| (parse_spec(spec).unwrap(), text);
+        assert_eq!(segments(""), []);
+        assert_eq!(segments("plain $ "), [seg("", "plain $ ")]);
+        assert_eq!(segments("%K{blue}%208Fa%F{#FF8000}b%Fc%kd"),
+                   [seg("208 blue", "a"), seg("#ff8000 blue", "b"), seg("normal blue", "c"), seg("", "d")]);
+        assert_eq!(segments("%F{default}%U%Sx%u%s%F{1}y%12Fz"),
+                   [seg("ul reverse", "x"), seg("red", "y"), seg("12", "z")]);
+        assert_eq!(segments("%{\x1b[1m%}%1~ %F{sparkly}%#"), [seg("", "%1~ "), seg("", "%#")]);
+        assert_eq!(segments("%{unterminated"), []);
+        assert_eq!(segments("%F{red"), [seg("", "%F{red")]);
+        assert_eq!(segments("100%"), [seg("", "100"), seg("", "%")]);
+    }
+}
@@ -0,0 +1,117 @@
+use ansi_term::Color;
+
+use crate::is_separator;
+
+/// Return the arguments of a call to the color function `name`, such as
+/// `["1", "2", "3"]` for `rgb(1, 2, 3)`, or `None` if `word` does not call it.
+fn args<'a>(word: &'a str, name: &str) -> Option<Vec<&'a str>> {
+    let args = word.strip_prefix(name)?.strip_prefix('(')?.strip_suffix(')')?;
+    Some(args.split(',').map(|arg| arg.trim_matches(is_separator)).collect())
+}
+
+/// Parse a non-negative decimal number with an optional fraction, such as
+/// `50` or `62.5`, rejecting the exponents and special values `f64`
+/// accepts.
+fn decimal(s: &str) -> Option<f64> {
+    let s = s.strip_prefix('+').unwrap_or(s);
+    let digits = s.bytes().filter(u8::is_ascii_digit).count();
+    let points = s.bytes().filter(|&b| b == b'.').count();
+    if digits == 0 || digits + points != s.len() || points > 1 {
+        return None;
+    }
+    s.parse().ok()
+}
+
+/// Parse a hue in degrees, with an optional `deg` suffix, as a fraction of
+/// a full turn.
+fn hue(s: &str) -> Option<f64> {
+    Some(decimal(s.strip_suffix("deg").unwrap_or(s))? % 360.0 / 360.0)
+}
+
+/// Parse a percentage from 0 to 100, with an optional `%` suffix, as a
+/// fraction.
+fn percentage(s: &str) -> Option<f64> {
+    let value = decimal(s.strip_suffix('%').unwrap_or(s))?;
+    if value <= 100.0 { Some(value / 100.0) } else { None }
+}
+
+fn rgb(r: f64, g: f64, b: f64) -> Color {
+    let channel = |c: f64| (c * 255.0).round() as u8;
+    Color::RGB(channel(r), channel(g), channel(b))
+}
+
+/// Convert a color from hue, saturation, and value, each from 0 to 1.
+fn from_hsv(h: f64, s: f64, v: f64) -> Color {
+    let h = h * 6.0;
+    let c = v * s;
+    let x = c * (1.0 - (h % 2.0 - 1.0).abs());
+    let m = v - c;
+    let (r, g, b) = match h as u8 {
+        0 => (c, x, 0.0),
+        1 => (x, c, 0.0),
+        2 => (0.0, c, x),
+        3 => (0.0, x, c),
+        4 => (x, 0.0, c),
+        _ => (c, 0.0, x),
+    };
+    rgb(r + m, g + m, b + m)
+}
+
+/// Convert a color from hue, saturation, and lightness, each from 0 to 1.
+fn from_hsl(h: f64, s: f64, l: f64) -> Color {
+    let v = l + s * l.min(1.0 - l);
+    let s = if v == 0.0 { 0.0 } else { 2.0 * (1.0 - l / v) };
+    from_hsv(h, s, v)
+}
+
+/// Parse a color written as a function: `rgb(r, g, b)` with components
+/// from 0 to 255, or `hsl(h, s%, l%)` or `hsv(h, s%, v%)` with a hue in
+/// degrees and percentages. Returns `None` if `word` is not a call to one
+/// of these functions, and `Some(Err(()))` if its arguments are invalid.
+pub(crate) fn parse_function(word: &str) -> Option<Result<Color, ()>> {
+    if let Some(args) = args(word, "rgb") {
+        return Some(match args[..] {
+            [r, g, b] => match (r.parse(), g.parse(), b.parse()) {
+                (Ok(r), Ok(g), Ok(b)) => Ok(Color::RGB(r, g, b)),
+                _ => Err(()),
+            },
+            _ => Err(()),
+        });
+    }
+    for &(name, convert) in &[("hsl", from_hsl as fn(f64, f64, f64) -> Color), ("hsv", from_hsv)] {
+        if let Some(args) = args(word, name) {
+            return Some(match args[..] {
+                [h, s, l] => match (hue(h), percentage(s), percentage(l)) {
+                    (Some(h), Some(s), Some(l)) => Ok(convert(h, s, l)),
+                    _ => Err(()),
+                },
+                _ => Err(()),
+            });
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ansi_term::Color::*;
+
+    #[test]
+    fn test_parse_function() {
+        assert_eq!(parse_function("red"), None);
+        assert_eq!(parse_function("rgb(1, 2, 3)"), Some(Ok(RGB(1, 2, 3))));
+        assert_eq!(parse_function("hsl(0, 100%, 50%)"), Some(Ok(RGB(255, 0, 0))));
+        assert_eq!(parse_function("hsl(120deg, 100%, 25%)"), Some(Ok(RGB(0, 128, 0))));
+        assert_eq!(parse_function("hsl(220, 80%, 50%)"), Some(Ok(RGB(26, 93, 230))));
+        assert_eq!(parse_function("hsl(360, 0%, 100%)"), Some(Ok(RGB(255, 255, 255))));
+        assert_eq!(parse_function("hsl(30, 50, 0)"), Some(Ok(RGB(0, 0, 0))));
+        assert_eq!(parse_function("hsv(240, 100%, 100%)"), Some(Ok(RGB(0, 0, 255))));
+        assert_eq!(parse_function("hsv(60, 50%, 50%)"), Some(Ok(RGB(128, 128, 64))));
+        assert_eq!(parse_function("hsv(300.5, 12.5%, 0%)"), Some(Ok(RGB(0, 0, 0))));
+        for word in &["hsl(0, 101%, 50%)", "hsl(-1, 50%, 50%)", "hsl(1e2, 50%, 50%)", "hsl(inf, 50%, 50%)",
+                      "hsl(0, 50%)", "hsv(0, 50%, 50%, 1)", "hsv(0, 50%%, 50%)", "hsv(0..1, 5, 5)", "hsl(., 5, 5)"] {
+            assert_eq!(parse_function(word), Some(Err(())), "{}", word);
+        }
+    }
+}
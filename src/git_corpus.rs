@@ -0,0 +1,118 @@
+//! Color values and their expected escape sequences from Git's own test
+//! suite (`t/t4026-color.sh`), for checking compatibility with Git.
+//!
+//! Each case gives an input as written in a Git config file, and the exact
+//! escape sequence `git config --get-color` prints for it, or `None` if Git
+//! rejects the value.
+//!
+//! # Examples
+//!
+//!     for case in colorparse::git_corpus::cases() {
+//!         match case.expected {
+//!             Some(sgr) => println!("{:?} => {:?}", case.input, sgr),
+//!             None => println!("{:?} is invalid", case.input),
+//!         }
+//!     }
+
+/// The Git release whose test suite the corpus was extracted from.
+pub const GIT_VERSION: &str = "2.26.0";
+
+/// One color value from Git's test suite.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Case {
+    /// The name of the Git test the case comes from.
+    pub name: &'static str,
+    /// The color value, as written in a Git config file.
+    pub input: &'static str,
+    /// The escape sequence Git emits for the value, or `None` if Git
+    /// rejects it.
+    pub expected: Option<&'static str>,
+}
+
+const fn valid(name: &'static str, input: &'static str, expected: &'static str) -> Case {
+    Case { name, input, expected: Some(expected) }
+}
+
+const fn invalid(name: &'static str, input: &'static str) -> Case {
+    Case { name, input, expected: None }
+}
+
+const CASES: [Case; 28] = [
+    valid("reset", "reset", "\x1b[m"),
+    valid("empty color is empty", "", ""),
+    valid("attribute before color name", "bold red", "\x1b[1;31m"),
+    valid("aixterm bright fg color", "brightred", "\x1b[91m"),
+    valid("aixterm bright bg color", "green brightblue", "\x1b[32;104m"),
+    valid("color name before attribute", "red bold", "\x1b[1;31m"),
+    valid("attr fg bg", "ul blue red", "\x1b[4;34;41m"),
+    valid("fg attr bg", "blue ul red", "\x1b[4;34;41m"),
+    valid("fg bg attr", "blue red ul", "\x1b[4;34;41m"),
+    valid("fg bg attr...", "blue bold dim ul blink reverse", "\x1b[1;2;4;5;7;34m"),
+    valid("attr negation", "nobold nodim noul noblink noreverse", "\x1b[22;24;25;27m"),
+    valid("\"no-\" variant of negation", "no-bold no-blink", "\x1b[22;25m"),
+    valid("long color specification", "254 255 bold dim ul blink reverse",
+          "\x1b[1;2;4;5;7;38;5;254;48;5;255m"),
+    valid("absurdly long color specification",
+          "#ffffff #ffffff bold nobold dim nodim italic noitalic ul noul blink noblink reverse noreverse strike nostrike",
+          "\x1b[1;2;3;4;5;7;9;22;23;24;25;27;29;38;2;255;255;255;48;2;255;255;255m"),
+    valid("0-7 are aliases for basic ANSI color names", "0 7", "\x1b[30;47m"),
+    valid("256 colors", "254 bold 255", "\x1b[1;38;5;254;48;5;255m"),
+    valid("24-bit colors", "#ff00ff black", "\x1b[38;2;255;0;255;40m"),
+    valid("\"default\" foreground", "default", "\x1b[39m"),
+    valid("\"normal default\" to specify only background", "normal default", "\x1b[49m"),
+    valid("\"default\" can be combined with attributes", "default default no-reverse bold",
+          "\x1b[1;27;39;49m"),
+    valid("\"normal\" yields no color at all", "normal black", "\x1b[40m"),
+    valid("-1 is a synonym for \"normal\"", "-1 black", "\x1b[40m"),
+    invalid("color too small", "-2"),
+    invalid("color too big", "256"),
+    invalid("extra character after color number", "3X"),
+    invalid("extra character after color name", "redX"),
+    invalid("extra character after attribute", "dimX"),
+    invalid("too many colors", "red blue green"),
+];
+
+/// Iterate over every case in the corpus, in the order of Git's tests.
+pub fn cases() -> impl Iterator<Item = &'static Case> {
+    CASES.iter()
+}
+
+/// Iterate over the `(input, expected SGR)` pairs for the values Git
+/// accepts.
+pub fn pairs() -> impl Iterator<Item = (&'static str, &'static str)> {
+    CASES.iter().filter_map(|case| case.expected.map(|expected| (case.input, expected)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_spec, prefix_sequence};
+
+    /// Cases whose handling in this crate differs from Git's, and this
+    /// crate's output for each: a spec keeps only the net effect of an
+    /// attribute both set and cleared.
+    const DIVERGENT: [(&str, &str); 1] = [
+        ("absurdly long color specification", "\x1b[22;23;24;25;27;29;38;2;255;255;255;48;2;255;255;255m"),
+    ];
+
+    #[test]
+    fn test_corpus() {
+        for case in cases() {
+            match case.expected {
+                Some(expected) => {
+                    let spec = parse_spec(case.input).unwrap();
+                    let expected = match DIVERGENT.iter().find(|&&(name, _)| name == case.name) {
+                        Some(&(_, own)) => {
+                            assert_ne!(own, expected, "{}", case.name);
+                            own
+                        }
+                        None => expected,
+                    };
+                    assert_eq!(prefix_sequence(&spec), expected, "{}", case.name);
+                }
+                None => assert!(parse_spec(case.input).is_err(), "{}", case.name),
+            }
+        }
+        assert_eq!(pairs().count(), cases().filter(|c| c.expected.is_some()).count());
+    }
+}
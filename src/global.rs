@@ -0,0 +1,53 @@
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::OnceLock;
+
+const UNSET: u8 = 0;
+const OFF: u8 = 1;
+const ON: u8 = 2;
+
+static OVERRIDE: AtomicU8 = AtomicU8::new(UNSET);
+
+/// Whether `NO_COLOR` disables styling, read from the environment once so
+/// that painting does not look it up each time.
+static NO_COLOR: OnceLock<bool> = OnceLock::new();
+
+/// Force styling on (`Some(true)`) or off (`Some(false)`) application-wide,
+/// or restore the default behavior (`None`).
+///
+/// This lets a single `--color=always` or `--color=never` option take
+/// effect everywhere the crate emits styled output: `cformat!`,
+/// `paintln!`, `paint`, `paint_if`, and `Painter`. An override takes
+/// precedence over the conditions passed to `paint_if` and `Painter`.
+pub fn set_override(enabled: Option<bool>) {
+    let value = match enabled {
+        None => UNSET,
+        Some(false) => OFF,
+        Some(true) => ON,
+    };
+    OVERRIDE.store(value, Ordering::Relaxed);
+}
+
+/// Return true if styled output should be emitted: the value passed to
+/// `set_override` if any, otherwise true unless the `NO_COLOR` environment
+/// variable is set to a non-empty value. `NO_COLOR` is read the first time
+/// it is needed, so later changes to it have no effect.
+pub fn colors_enabled() -> bool {
+    enabled_if(true)
+}
+
+/// Return the value passed to `set_override` if any, otherwise `condition`
+/// unless `NO_COLOR` disables styling.
+pub(crate) fn enabled_if(condition: bool) -> bool {
+    match OVERRIDE.load(Ordering::Relaxed) {
+        ON => true,
+        OFF => false,
+        _ => condition && !*NO_COLOR.get_or_init(|| std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty())),
+    }
+}
+
+/// Serialize the tests that depend on the override, since it is global.
+#[cfg(test)]
+pub(crate) fn lock_override() -> std::sync::MutexGuard<'static, ()> {
+    static LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+    LOCK.lock().unwrap_or_else(|e| e.into_inner())
+}
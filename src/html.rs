@@ -0,0 +1,159 @@
+use ansi_term::Color;
+
+use crate::{Attribute, Palette, StyleSpec, UnderlineStyle};
+
+/// Write `rgb` as a CSS hex color.
+fn hex((r, g, b): (u8, u8, u8)) -> String {
+    format!("#{:02x}{:02x}{:02x}", r, g, b)
+}
+
+/// Return the CSS declarations that give text the appearance of `spec`,
+/// such as `color:#aa0000; font-weight:bold`, with the colors as the VGA
+/// palette displays them.
+///
+/// # Examples
+///
+///     use colorparse::{parse_spec, to_css};
+///
+///     let spec = parse_spec("bold ul #ff0000 #0000ff").unwrap();
+///     assert_eq!(to_css(&spec),
+///                "color:#ff0000; background:#0000ff; font-weight:bold; text-decoration:underline");
+pub fn to_css(spec: &StyleSpec) -> String {
+    to_css_with(spec, &Palette::default())
+}
+
+/// Return the CSS declarations that give text the appearance of `spec`,
+/// with the colors as `palette` displays them.
+///
+/// The declarations come in a fixed order, separated by `; `: `color` and
+/// `background`; `font-weight:bold` for bold, `opacity:0.5` for dim, and
+/// `font-style:italic`; a `text-decoration` listing `underline`,
+/// `line-through`, and `overline`, with the `text-decoration-style` of a
+/// shaped underline and the `text-decoration-color` of a colored one; and
+/// `visibility:hidden` for hidden text. Reverse video swaps the colors,
+/// using the palette's default foreground and background for those not
+/// set. Blinking, attributes the spec clears, and a default color, which
+/// is left to the page like an unset one, give no declarations. A plain
+/// spec gives an empty string.
+pub fn to_css_with(spec: &StyleSpec, palette: &Palette) -> String {
+    let color = |color: Option<Color>| color.map(|color| hex(palette.rgb(color)));
+    let (mut fg, mut bg) = (color(spec.foreground()), color(spec.background()));
+    if spec.has(Attribute::Reverse) {
+        let swapped = (bg.unwrap_or_else(|| hex(palette.background)), fg.unwrap_or_else(|| hex(palette.foreground)));
+        fg = Some(swapped.0);
+        bg = Some(swapped.1);
+    }
+    let mut declarations: Vec<String> = Vec::new();
+    declarations.extend(fg.map(|fg| format!("color:{}", fg)));
+    declarations.extend(bg.map(|bg| format!("background:{}", bg)));
+    let fixed = [
+        (Attribute::Bold, "font-weight:bold"),
+        (Attribute::Dimmed, "opacity:0.5"),
+        (Attribute::Italic, "font-style:italic"),
+    ];
+    declarations.extend(fixed.iter().filter(|&&(a, _)| spec.has(a)).map(|&(_, d)| d.to_string()));
+    let lines = [
+        (Attribute::Underline, "underline"),
+        (Attribute::Strikethrough, "line-through"),
+        (Attribute::Overline, "overline"),
+    ];
+    let lines: Vec<&str> = lines.iter().filter(|&&(a, _)| spec.has(a)).map(|&(_, line)| line).collect();
+    if !lines.is_empty() {
+        declarations.push(format!("text-decoration:{}", lines.join(" ")));
+    }
+    if spec.has(Attribute::Underline) {
+        let shape = match spec.underline_style() {
+            UnderlineStyle::Single => None,
+            UnderlineStyle::Double => Some("double"),
+            UnderlineStyle::Curly => Some("wavy"),
+            UnderlineStyle::Dotted => Some("dotted"),
+            UnderlineStyle::Dashed => Some("dashed"),
+        };
+        declarations.extend(shape.map(|shape| format!("text-decoration-style:{}", shape)));
+        declarations.extend(color(spec.underline_color()).map(|ul| format!("text-decoration-color:{}", ul)));
+    }
+    if spec.has(Attribute::Hidden) {
+        declarations.push("visibility:hidden".to_string());
+    }
+    declarations.join("; ")
+}
+
+/// Escape the characters of `text` that HTML gives meaning to.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Return `text` as HTML displayed in the style of `spec`: escaped, and
+/// wrapped in a `<span>` with the declarations of `to_css` as its `style`,
+/// or left bare if the spec gives no declarations.
+///
+/// # Examples
+///
+///     use colorparse::{parse_spec, to_html};
+///
+///     let spec = parse_spec("bold red").unwrap();
+///     assert_eq!(to_html(&spec, "a < b"),
+///                r#"<span style="color:#aa0000; font-weight:bold">a &lt; b</span>"#);
+///     assert_eq!(to_html(&parse_spec("").unwrap(), "a & b"), "a &amp; b");
+pub fn to_html(spec: &StyleSpec, text: &str) -> String {
+    let css = to_css(spec);
+    if css.is_empty() {
+        escape(text)
+    } else {
+        format!("<span style=\"{}\">{}</span>", css, escape(text))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_spec;
+
+    #[test]
+    fn test_to_css() {
+        macro_rules! test {
+            ($s:expr => $css:expr) => {
+                assert_eq!(to_css(&parse_spec($s).unwrap()), $css, "{}", $s);
+            };
+        }
+
+        test!("" => "");
+        test!("nobold default blink" => "");
+        test!("red 12" => "color:#aa0000; background:#5555ff");
+        test!("208 #102030" => "color:#ff8700; background:#102030");
+        test!("normal green dim italic" => "background:#00aa00; opacity:0.5; font-style:italic");
+        test!("strike overline ul" => "text-decoration:underline line-through overline");
+        test!("curly-ul ul=yellow" => "text-decoration:underline; text-decoration-style:wavy; text-decoration-color:#aa5500");
+        test!("ul=yellow" => "");
+        test!("reverse red" => "color:#000000; background:#aa0000");
+        test!("reverse normal blue" => "color:#0000aa; background:#aaaaaa");
+        test!("conceal" => "visibility:hidden");
+    }
+
+    #[test]
+    fn test_to_css_with() {
+        let palette = Palette { foreground: (1, 2, 3), background: (4, 5, 6), ..Palette::default() };
+        let spec = parse_spec("reverse").unwrap();
+        assert_eq!(to_css_with(&spec, &palette), "color:#040506; background:#010203");
+    }
+
+    #[test]
+    fn test_to_html() {
+        let spec = parse_spec("ul 208").unwrap();
+        assert_eq!(to_html(&spec, ""), r#"<span style="color:#ff8700; text-decoration:underline"></span>"#);
+        assert_eq!(to_html(&spec, "<a href='x'>\"&\"</a>"),
+                   r#"<span style="color:#ff8700; text-decoration:underline">&lt;a href=&#39;x&#39;&gt;&quot;&amp;&quot;&lt;/a&gt;</span>"#);
+        assert_eq!(to_html(&parse_spec("blink").unwrap(), "é"), "é");
+    }
+}
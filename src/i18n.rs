@@ -0,0 +1,230 @@
+//! Localized formatting of error messages, using Fluent.
+//!
+//! A `Catalog` formats each `Error` from a message in a Fluent resource,
+//! passing the error's fields as variables, so applications can present
+//! diagnostics in the user's language. Messages missing from a translation
+//! fall back to the built-in English catalog, whose messages match the
+//! `Display` implementations.
+//!
+//! The message identifiers and variables are those of `ENGLISH`:
+//!
+//! - `error-extra-color`: `$input`, `$word`
+//! - `error-unknown-word`: `$input`, `$word`
+//! - `error-unsupported-color`: `$input`, `$word`, `$required`, `$allowed`,
+//!   with the levels already formatted by `color-level-ansi16`,
+//!   `color-level-ansi256`, or `color-level-truecolor`
+//! - `error-input-too-long`: `$length`, `$max`
+//! - `error-too-many-words`: `$input`, `$max`
+//! - `error-word-too-long`: `$input`, `$word`, `$max`
+//! - `error-invalid-color`: `$input`
+//! - `error-invalid-sgr`: `$input`, `$word`
+//!
+//! # Examples
+//!
+//!     # extern crate colorparse;
+//!     # fn main() {
+//!     use colorparse::i18n::Catalog;
+//!
+//!     let catalog = Catalog::new("de", r#"
+//!     error-unknown-word = Fehler beim Parsen von „{ $input }“: unbekanntes Wort „{ $word }“
+//!     "#).unwrap();
+//!     let err = colorparse::parse("fett rot").unwrap_err();
+//!     assert_eq!(catalog.format_error(&err), "Fehler beim Parsen von „fett rot“: unbekanntes Wort „fett“");
+//!     # }
+
+use std::borrow::Cow;
+use std::fmt;
+
+use fluent_bundle::{FluentArgs, FluentBundle, FluentResource};
+use unic_langid::LanguageIdentifier;
+
+use crate::{ColorLevel, Error, ErrorKind};
+
+/// The built-in English messages, in Fluent syntax.
+pub const ENGLISH: &str = r#"
+color-level-ansi16 = 16 colors
+color-level-ansi256 = 256 colors
+color-level-truecolor = 24-bit color
+error-extra-color = Error parsing style "{ $input }": extra color "{ $word }"
+error-unknown-word = Error parsing style "{ $input }": unknown word: "{ $word }"
+error-unsupported-color = Error parsing style "{ $input }": color "{ $word }" requires { $required }, but the target only supports { $allowed }
+error-input-too-long = Error parsing style: input is { $length } bytes long, more than the maximum of { $max }
+error-too-many-words = Error parsing style "{ $input }": more than the maximum of { $max } words
+error-word-too-long = Error parsing style "{ $input }": word "{ $word }" is longer than the maximum of { $max } bytes
+error-invalid-color = Error parsing color "{ $input }": not a color
+error-invalid-sgr = Error parsing SGR parameters "{ $input }": invalid parameter "{ $word }"
+"#;
+
+fn bundle(locale: &str, ftl: &str) -> Result<FluentBundle<FluentResource>, CatalogError> {
+    let langid: LanguageIdentifier = locale.parse().map_err(|_| CatalogError::InvalidLocale(locale.to_string()))?;
+    let resource = FluentResource::try_new(ftl.to_string())
+        .map_err(|(_, errors)| CatalogError::InvalidResource(format!("{:?}", errors)))?;
+    let mut bundle = FluentBundle::new(vec![langid]);
+    bundle.set_use_isolating(false);
+    bundle.add_resource(resource).map_err(|errors| CatalogError::InvalidResource(format!("{:?}", errors)))?;
+    Ok(bundle)
+}
+
+fn level_id(level: ColorLevel) -> &'static str {
+    match level {
+        ColorLevel::Ansi16 => "color-level-ansi16",
+        ColorLevel::Ansi256 => "color-level-ansi256",
+        ColorLevel::TrueColor => "color-level-truecolor",
+    }
+}
+
+/// A set of translated messages for formatting errors.
+pub struct Catalog {
+    bundle: Option<FluentBundle<FluentResource>>,
+    english: FluentBundle<FluentResource>,
+}
+
+impl Catalog {
+    /// Create a catalog with only the built-in English messages.
+    pub fn english() -> Self {
+        Catalog {
+            bundle: None,
+            english: bundle("en-US", ENGLISH).unwrap(),
+        }
+    }
+
+    /// Create a catalog for `locale` (a BCP 47 language tag, such as
+    /// `de` or `pt-BR`) from the Fluent resource `ftl`, falling back to
+    /// the English messages for any message `ftl` does not define.
+    pub fn new(locale: &str, ftl: &str) -> Result<Self, CatalogError> {
+        Ok(Catalog {
+            bundle: Some(bundle(locale, ftl)?),
+            ..Self::english()
+        })
+    }
+
+    fn format(&self, id: &str, args: Option<&FluentArgs>) -> String {
+        let bundles = self.bundle.iter().chain(std::iter::once(&self.english));
+        for bundle in bundles {
+            if let Some(pattern) = bundle.get_message(id).and_then(|m| m.value()) {
+                let mut errors = Vec::new();
+                return bundle.format_pattern(pattern, args, &mut errors).into_owned();
+            }
+        }
+        id.to_string()
+    }
+
+    /// Format `err` with this catalog's message for it.
+    pub fn format_error(&self, err: &Error) -> String {
+        let mut args = FluentArgs::new();
+        let (input, word) = (Cow::from(err.input()), Cow::from(err.word()));
+        let id = match *err.kind() {
+            ErrorKind::ExtraColor => "error-extra-color",
+            ErrorKind::UnknownWord => "error-unknown-word",
+            ErrorKind::UnsupportedColor { required, allowed } => {
+                args.set("required", self.format(level_id(required), None));
+                args.set("allowed", self.format(level_id(allowed), None));
+                "error-unsupported-color"
+            }
+            ErrorKind::InputTooLong { len, max } => {
+                args.set("length", len);
+                args.set("max", max);
+                "error-input-too-long"
+            }
+            ErrorKind::TooManyWords { max } => {
+                args.set("max", max);
+                "error-too-many-words"
+            }
+            ErrorKind::WordTooLong { max } => {
+                args.set("max", max);
+                "error-word-too-long"
+            }
+            ErrorKind::InvalidColor => "error-invalid-color",
+            ErrorKind::InvalidSgr => "error-invalid-sgr",
+        };
+        args.set("input", input);
+        args.set("word", word);
+        self.format(id, Some(&args))
+    }
+}
+
+impl Default for Catalog {
+    fn default() -> Self {
+        Self::english()
+    }
+}
+
+impl fmt::Debug for Catalog {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        let locale = self.bundle.as_ref().unwrap_or(&self.english).locales[0].to_string();
+        fmt.debug_struct("Catalog").field("locale", &locale).finish()
+    }
+}
+
+/// Type for errors returned by `Catalog::new`.
+#[derive(Debug, PartialEq)]
+pub enum CatalogError {
+    /// The locale is not a valid language tag.
+    InvalidLocale(String),
+    /// The Fluent resource failed to parse, or redefined a message.
+    InvalidResource(String),
+}
+
+impl fmt::Display for CatalogError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::InvalidLocale(locale) => write!(fmt, "Error loading catalog: invalid locale \"{}\"", locale),
+            Self::InvalidResource(errors) => write!(fmt, "Error loading catalog: {}", errors),
+        }
+    }
+}
+
+impl std::error::Error for CatalogError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse, parse_color, parse_sgr, parse_with, ParseOptions};
+
+    fn errors() -> Vec<Error> {
+        let ansi16 = ParseOptions::new().color_level(ColorLevel::Ansi16);
+        let ansi256 = ParseOptions::new().color_level(ColorLevel::Ansi256);
+        vec![
+            parse("red blue green").unwrap_err(),
+            parse("bold blu").unwrap_err(),
+            parse_with("#ff0000", &ansi16).unwrap_err(),
+            parse_with("208", &ansi16).unwrap_err(),
+            parse_with("#ff0000", &ansi256).unwrap_err(),
+            parse_with("bold red", &ParseOptions::new().max_len(4)).unwrap_err(),
+            parse_with("bold red", &ParseOptions::new().max_words(1)).unwrap_err(),
+            parse_with("bold red", &ParseOptions::new().max_word_len(3)).unwrap_err(),
+            parse_color("bold").unwrap_err(),
+            parse_sgr("1;x").unwrap_err(),
+        ]
+    }
+
+    #[test]
+    fn test_english_matches_display() {
+        let catalog = Catalog::english();
+        for err in errors() {
+            assert_eq!(catalog.format_error(&err), err.to_string());
+        }
+    }
+
+    #[test]
+    fn test_translation() {
+        let catalog = Catalog::new("fr", r#"
+color-level-ansi16 = 16 couleurs
+color-level-truecolor = couleurs 24 bits
+error-unsupported-color = « { $word } » nécessite { $required }, mais la cible ne prend en charge que { $allowed }
+"#).unwrap();
+        let errors = errors();
+        assert_eq!(catalog.format_error(&errors[2]),
+                   "« #ff0000 » nécessite couleurs 24 bits, mais la cible ne prend en charge que 16 couleurs");
+        assert_eq!(catalog.format_error(&errors[4]),
+                   "« #ff0000 » nécessite couleurs 24 bits, mais la cible ne prend en charge que 256 colors");
+        assert_eq!(catalog.format_error(&errors[1]), errors[1].to_string());
+        assert_eq!(format!("{:?}", catalog), "Catalog { locale: \"fr\" }");
+    }
+
+    #[test]
+    fn test_catalog_err() {
+        assert_eq!(Catalog::new("not a locale!", "").unwrap_err(), CatalogError::InvalidLocale("not a locale!".to_string()));
+        assert!(matches!(Catalog::new("de", "error-unknown-word = {"), Err(CatalogError::InvalidResource(_))));
+    }
+}
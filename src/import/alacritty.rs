@@ -0,0 +1,124 @@
+use toml::{Table, Value};
+
+use super::{parse_hex, ImportError};
+use crate::Palette;
+
+/// The Alacritty keys for colors 0 through 7, in the `colors.normal` and
+/// `colors.bright` tables.
+const KEYS: [&str; 8] = ["black", "red", "green", "yellow", "blue", "magenta", "cyan", "white"];
+
+/// Read an Alacritty theme: the `colors` table of an `alacritty.toml`
+/// file, or of a theme file it imports.
+///
+/// Colors may be written as `#rrggbb` or `0xrrggbb`.
+///
+/// # Examples
+///
+///     # extern crate colorparse;
+///     # fn main() {
+///     let theme = r##"
+///     [colors.primary]
+///     background = "#1d1f21"
+///     foreground = "#c5c8c6"
+///
+///     [colors.normal]
+///     black = "#1d1f21"
+///     red = "#cc6666"
+///     green = "#b5bd68"
+///     yellow = "#f0c674"
+///     blue = "#81a2be"
+///     magenta = "#b294bb"
+///     cyan = "#8abeb7"
+///     white = "#c5c8c6"
+///
+///     [colors.bright]
+///     black = "#666666"
+///     red = "#d54e53"
+///     green = "#b9ca4a"
+///     yellow = "#e7c547"
+///     blue = "#7aa6da"
+///     magenta = "#c397d8"
+///     cyan = "#70c0b1"
+///     white = "#eaeaea"
+///     "##;
+///     let palette = colorparse::import::alacritty(theme).unwrap();
+///     assert_eq!(palette.ansi[9], (0xd5, 0x4e, 0x53));
+///     # }
+pub fn alacritty(toml: &str) -> Result<Palette, ImportError> {
+    let theme: Table = toml.parse().map_err(|e: toml::de::Error| ImportError::Syntax(e.message().to_string()))?;
+    let table = |key: &str| match theme.get("colors").and_then(|colors| colors.get(key)) {
+        Some(Value::Table(table)) => Ok(table),
+        _ => Err(ImportError::MissingColor(format!("colors.{}", key))),
+    };
+    let color = |table_key: &str, key: &str| {
+        let full_key = format!("colors.{}.{}", table_key, key);
+        match table(table_key)?.get(key) {
+            Some(Value::String(value)) => {
+                let hex = value.strip_prefix("0x").unwrap_or(value);
+                parse_hex(&full_key, hex).map_err(|_| ImportError::InvalidColor(full_key, value.to_string()))
+            }
+            Some(value) => Err(ImportError::InvalidColor(full_key, value.to_string())),
+            None => Err(ImportError::MissingColor(full_key)),
+        }
+    };
+    let mut palette = Palette::default();
+    for (i, key) in KEYS.iter().enumerate() {
+        palette.ansi[i] = color("normal", key)?;
+        palette.ansi[i + 8] = color("bright", key)?;
+    }
+    palette.foreground = color("primary", "foreground")?;
+    palette.background = color("primary", "background")?;
+    Ok(palette)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GRUVBOX: &str = r#"
+        [colors.primary]
+        background = '0x282828'
+        foreground = '0xebdbb2'
+
+        [colors.normal]
+        black   = '0x282828'
+        red     = '0xcc241d'
+        green   = '0x98971a'
+        yellow  = '0xd79921'
+        blue    = '0x458588'
+        magenta = '0xb16286'
+        cyan    = '0x689d6a'
+        white   = '0xa89984'
+
+        [colors.bright]
+        black   = '0x928374'
+        red     = '0xfb4934'
+        green   = '0xb8bb26'
+        yellow  = '0xfabd2f'
+        blue    = '0x83a598'
+        magenta = '0xd3869b'
+        cyan    = '0x8ec07c'
+        white   = '0xebdbb2'
+    "#;
+
+    #[test]
+    fn test_alacritty() {
+        let palette = alacritty(GRUVBOX).unwrap();
+        assert_eq!(palette.ansi[1], (0xcc, 0x24, 0x1d));
+        assert_eq!(palette.ansi[7], (0xa8, 0x99, 0x84));
+        assert_eq!(palette.ansi[8], (0x92, 0x83, 0x74));
+        assert_eq!(palette.foreground, (0xeb, 0xdb, 0xb2));
+        assert_eq!(palette.background, (0x28, 0x28, 0x28));
+    }
+
+    #[test]
+    fn test_alacritty_err() {
+        assert!(matches!(alacritty("[colors"), Err(ImportError::Syntax(_))));
+        assert_eq!(alacritty(&GRUVBOX.replace("[colors.bright]", "[colors.dim]")),
+                   Err(ImportError::MissingColor("colors.bright".to_string())));
+        assert_eq!(alacritty(&GRUVBOX.replace("cyan    = '0x8ec07c'", "")),
+                   Err(ImportError::MissingColor("colors.bright.cyan".to_string())));
+        assert_eq!(alacritty(&GRUVBOX.replace("'0xcc241d'", "'red'")),
+                   Err(ImportError::InvalidColor("colors.normal.red".to_string(), "red".to_string())));
+    }
+}
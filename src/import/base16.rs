@@ -0,0 +1,215 @@
+use ansi_term::Color;
+
+use super::{parse_hex, ImportError};
+use crate::yaml::{self, Entry, Value};
+use crate::Palette;
+
+/// The base color each of terminal colors 0 through 15 takes, as in the
+/// Base16 terminal templates.
+const TERMINAL: [usize; 16] = [0x0, 0x8, 0xb, 0xa, 0xd, 0xe, 0xc, 0x5, 0x3, 0x8, 0xb, 0xa, 0xd, 0xe, 0xc, 0x7];
+
+/// A Base16 color scheme: sixteen colors, `base00` through `base0F`, from
+/// the darkest background shades to the accent colors.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Base16Scheme {
+    /// The name of the scheme.
+    pub scheme: String,
+    /// The author of the scheme.
+    pub author: String,
+    /// Colors `base00` through `base0F`.
+    pub colors: [(u8, u8, u8); 16],
+}
+
+impl Base16Scheme {
+    /// Return the palette a terminal displays with this scheme, mapping
+    /// the base colors onto the terminal colors as the Base16 terminal
+    /// templates do: `base05` on `base00` for the default colors, the
+    /// accents `base08` through `base0E` for red through cyan and their
+    /// bright variants, and `base00`, `base05`, `base03`, and `base07` for
+    /// black, white, and their bright variants.
+    pub fn palette(&self) -> Palette {
+        let mut palette = Palette::default();
+        for (rgb, &base) in palette.ansi.iter_mut().zip(TERMINAL.iter()) {
+            *rgb = self.colors[base];
+        }
+        palette.foreground = self.colors[0x5];
+        palette.background = self.colors[0x0];
+        palette
+    }
+
+    /// Return the RGB value `color` displays as with this scheme's
+    /// `palette`, so a style in Git's syntax, such as `red` or `9`, can be
+    /// mapped onto the scheme.
+    pub fn rgb(&self, color: Color) -> (u8, u8, u8) {
+        self.palette().rgb(color)
+    }
+
+    /// Return the base color named `name`, from `base00` to `base0F`, as
+    /// an RGB color, or `None` if `name` is not a base color.
+    pub fn base(&self, name: &str) -> Option<Color> {
+        let n = name.strip_prefix("base0")?;
+        let n = usize::from_str_radix(n, 16).ok().filter(|_| n.len() == 1)?;
+        let (r, g, b) = self.colors[n];
+        Some(Color::RGB(r, g, b))
+    }
+}
+
+/// Return the string value of `entry`, or an empty string if it has none.
+fn scalar(entry: &Entry) -> Result<&str, ImportError> {
+    match &entry.value {
+        Value::Scalar(value) => Ok(value),
+        Value::Null => Ok(""),
+        Value::Mapping(_) => Err(ImportError::Syntax(format!("line {}: expected a value for \"{}\"", entry.line, entry.key))),
+    }
+}
+
+/// Read a Base16 scheme file.
+///
+/// Scheme files are YAML, with `scheme` and `author` keys and a key for
+/// each of `base00` through `base0F`, such as `base0D: "7cafc2"`, all of
+/// which are required; the `#` before a color is optional. The newer
+/// format, which nests the colors under `palette` and names the scheme
+/// with `name`, reads the same way. Other keys, such as `slug` and
+/// `variant`, are skipped.
+///
+/// Only the subset of YAML that scheme files use is accepted: `key: value`
+/// lines, with mappings such as `palette` nested by indentation, plain,
+/// `'single'`, or `"double"`-quoted scalars, and `#` comments. Flow
+/// collections, sequences, block scalars, anchors, aliases, tags, and
+/// further documents are rejected with `ImportError::Syntax` rather than
+/// misread. Requires the `base16` feature.
+///
+/// # Examples
+///
+///     # extern crate ansi_term;
+///     # extern crate colorparse;
+///     use ansi_term::Color::{Blue, RGB};
+///
+///     # fn main() {
+///     let yaml = "
+///     scheme: \"Default Dark\"
+///     author: \"Chris Kempson (http://chriskempson.com)\"
+///     base00: \"181818\" # background
+///     base01: \"282828\"
+///     base02: \"383838\"
+///     base03: \"585858\"
+///     base04: \"b8b8b8\"
+///     base05: \"d8d8d8\"
+///     base06: \"e8e8e8\"
+///     base07: \"f8f8f8\"
+///     base08: \"ab4642\"
+///     base09: \"dc9656\"
+///     base0A: \"f7ca88\"
+///     base0B: \"a1b56c\"
+///     base0C: \"86c1b9\"
+///     base0D: \"7cafc2\"
+///     base0E: \"ba8baf\"
+///     base0F: \"a16946\"
+///     ";
+///     let scheme = colorparse::import::base16(yaml).unwrap();
+///     assert_eq!(scheme.scheme, "Default Dark");
+///     assert_eq!(scheme.base("base0D"), Some(RGB(0x7c, 0xaf, 0xc2)));
+///     assert_eq!(scheme.rgb(Blue), (0x7c, 0xaf, 0xc2));
+///     assert_eq!(scheme.palette().background, (0x18, 0x18, 0x18));
+///     # }
+pub fn base16(yaml: &str) -> Result<Base16Scheme, ImportError> {
+    let mut scheme = Base16Scheme { scheme: String::new(), author: String::new(), colors: [(0, 0, 0); 16] };
+    let mut found = [false; 16];
+    let entries = yaml::parse(yaml).map_err(ImportError::Syntax)?;
+    let palette = entries.iter().filter(|entry| entry.key == "palette").flat_map(|entry| match &entry.value {
+        Value::Mapping(palette) => palette.as_slice(),
+        _ => &[],
+    });
+    for entry in entries.iter().chain(palette) {
+        let key = entry.key.as_str();
+        match key {
+            "scheme" | "name" => scheme.scheme = scalar(entry)?.to_string(),
+            "author" => scheme.author = scalar(entry)?.to_string(),
+            _ => {
+                let n = match key.strip_prefix("base0").filter(|n| n.len() == 1) {
+                    Some(n) => usize::from_str_radix(n, 16).ok(),
+                    None => None,
+                };
+                if let Some(n) = n {
+                    scheme.colors[n] = parse_hex(key, scalar(entry)?)?;
+                    found[n] = true;
+                }
+            }
+        }
+    }
+    if let Some(n) = found.iter().position(|&found| !found) {
+        return Err(ImportError::MissingColor(format!("base0{:X}", n)));
+    }
+    Ok(scheme)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ansi_term::Color::*;
+
+    const TOMORROW_NIGHT: &str = r##"
+# Tinted theming format
+system: "base16"
+name: 'Tomorrow Night'
+author: Chris Kempson (http://chriskempson.com) # unquoted
+variant: "dark"
+palette:
+  base00: "#1d1f21"
+  base01: "#282a2e"
+  base02: "#373b41"
+  base03: "#969896"
+  base04: "#b4b7b4"
+  base05: "#c5c8c6"
+  base06: "#e0e0e0"
+  base07: "#ffffff"
+  base08: "#cc6666"
+  base09: "#de935f"
+  base0A: "#f0c674"
+  base0B: "#b5bd68"
+  base0C: "#8abeb7"
+  base0D: "#81a2be"
+  base0E: "#b294bb"
+  base0F: "#a3685a"
+"##;
+
+    #[test]
+    fn test_base16() {
+        let scheme = base16(TOMORROW_NIGHT).unwrap();
+        assert_eq!(scheme.scheme, "Tomorrow Night");
+        assert_eq!(scheme.author, "Chris Kempson (http://chriskempson.com)");
+        assert_eq!(scheme.colors[0xf], (0xa3, 0x68, 0x5a));
+        assert_eq!(scheme.base("base0A"), Some(RGB(0xf0, 0xc6, 0x74)));
+        assert_eq!(scheme.base("base0a"), Some(RGB(0xf0, 0xc6, 0x74)));
+        assert_eq!(scheme.base("base10"), None);
+        assert_eq!(scheme.base("base0"), None);
+
+        let palette = scheme.palette();
+        assert_eq!(palette.ansi[0], (0x1d, 0x1f, 0x21));
+        assert_eq!(palette.ansi[1], (0xcc, 0x66, 0x66));
+        assert_eq!(palette.ansi[7], (0xc5, 0xc8, 0xc6));
+        assert_eq!(palette.ansi[8], (0x96, 0x98, 0x96));
+        assert_eq!(palette.ansi[14], (0x8a, 0xbe, 0xb7));
+        assert_eq!(palette.ansi[15], (0xff, 0xff, 0xff));
+        assert_eq!(palette.foreground, (0xc5, 0xc8, 0xc6));
+        assert_eq!(scheme.rgb(Green), (0xb5, 0xbd, 0x68));
+        assert_eq!(scheme.rgb(Fixed(12)), (0x81, 0xa2, 0xbe));
+        assert_eq!(scheme.rgb(Fixed(16)), (0, 0, 0));
+    }
+
+    #[test]
+    fn test_base16_err() {
+        assert_eq!(base16(&TOMORROW_NIGHT.replace("  base0C: \"#8abeb7\"\n", "")),
+                   Err(ImportError::MissingColor("base0C".to_string())));
+        assert_eq!(base16(&TOMORROW_NIGHT.replace("#de935f", "#de935")),
+                   Err(ImportError::InvalidColor("base09".to_string(), "#de935".to_string())));
+        assert_eq!(base16("scheme: x\nbase00"), Err(ImportError::Syntax("line 2: expected \"key: value\"".to_string())));
+        assert_eq!(base16(&TOMORROW_NIGHT.replace("\"#1d1f21\"", "&bg \"#1d1f21\"")),
+                   Err(ImportError::Syntax("line 8: anchors and aliases are not supported".to_string())));
+        assert_eq!(base16(&TOMORROW_NIGHT.replace("author: Chris", "author: |\n  Chris")),
+                   Err(ImportError::Syntax("line 5: block scalars are not supported".to_string())));
+        assert_eq!(base16(&TOMORROW_NIGHT.replace("palette:", "palette: {base00: \"#000000\"}\nx:")),
+                   Err(ImportError::Syntax("line 7: flow collections are not supported".to_string())));
+        assert_eq!(base16("scheme:\n  name: x"), Err(ImportError::Syntax("line 1: expected a value for \"scheme\"".to_string())));
+    }
+}
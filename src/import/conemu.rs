@@ -0,0 +1,128 @@
+use super::ImportError;
+use crate::Palette;
+
+/// The position in ConEmu's color table, which follows the Windows console
+/// order, of each of colors 0 through 15.
+const CONSOLE_ORDER: [usize; 16] = [0, 4, 2, 6, 1, 5, 3, 7, 8, 12, 10, 14, 9, 13, 11, 15];
+
+/// Return the value of the attribute `name` in the XML tag `tag`.
+fn attribute<'a>(tag: &'a str, name: &str) -> Option<&'a str> {
+    let mut rest = tag;
+    while let Some(i) = rest.find(name) {
+        let before = rest[..i].chars().next_back();
+        let after = rest[i + name.len()..].trim_start();
+        rest = &rest[i + name.len()..];
+        if before.is_some_and(|c| !c.is_whitespace()) {
+            continue;
+        }
+        if let Some(value) = after.strip_prefix('=') {
+            let value = value.trim_start();
+            let quote = value.chars().next().filter(|&c| c == '"' || c == '\'')?;
+            let value = &value[1..];
+            return value.find(quote).map(|end| &value[..end]);
+        }
+    }
+    None
+}
+
+/// Read the `Colors` of a ConEmu settings file (`ConEmu.xml`), or of a
+/// color scheme exported from ConEmu's settings.
+///
+/// ConEmu stores its table in the Windows console order, with each entry
+/// as a little-endian `00bbggrr` value; this reorders the table into the
+/// ANSI order and takes the default foreground and background from
+/// `TextColorIdx` and `BackColorIdx`.
+pub fn conemu(xml: &str) -> Result<Palette, ImportError> {
+    let mut table = [None; 16];
+    let mut text_index = 7;
+    let mut back_index = 0;
+    for tag in xml.split('<').skip(1) {
+        let tag = match tag.find('>') {
+            Some(end) if tag.starts_with("value") => &tag[..end],
+            _ => continue,
+        };
+        let (name, data) = match (attribute(tag, "name"), attribute(tag, "data")) {
+            (Some(name), Some(data)) => (name, data),
+            _ => continue,
+        };
+        let invalid = || ImportError::InvalidColor(name.to_string(), data.to_string());
+        if let Some(n) = name.strip_prefix("ColorTable") {
+            let n = match n.parse::<usize>() {
+                Ok(i) if n.len() == 2 && i < 16 => i,
+                _ => continue,
+            };
+            let bgr = u32::from_str_radix(data, 16).map_err(|_| invalid())?;
+            table[n] = Some(((bgr & 0xff) as u8, (bgr >> 8 & 0xff) as u8, (bgr >> 16 & 0xff) as u8));
+        } else if name == "TextColorIdx" || name == "BackColorIdx" {
+            let index = match usize::from_str_radix(data, 16) {
+                // An index of 16 means "automatic", keeping the default.
+                Ok(16) => continue,
+                Ok(index) if index < 16 => index,
+                _ => return Err(invalid()),
+            };
+            if name == "TextColorIdx" {
+                text_index = index;
+            } else {
+                back_index = index;
+            }
+        }
+    }
+
+    let mut palette = Palette::default();
+    for (rgb, &position) in palette.ansi.iter_mut().zip(CONSOLE_ORDER.iter()) {
+        *rgb = table[position].ok_or_else(|| ImportError::MissingColor(format!("ColorTable{:02}", position)))?;
+    }
+    palette.foreground = table[text_index].unwrap();
+    palette.background = table[back_index].unwrap();
+    Ok(palette)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scheme() -> String {
+        let table = [
+            "00000000", "00800000", "00008000", "00808000", "00000080", "00800080", "00008080", "00c0c0c0",
+            "00808080", "00ff0000", "0000ff00", "00ffff00", "000000ff", "00ff00ff", "0000ffff", "00ffffff",
+        ];
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<key name=\"Colors\">\n");
+        for (i, data) in table.iter().enumerate() {
+            xml.push_str(&format!("  <value name=\"ColorTable{:02}\" type=\"dword\" data=\"{}\"/>\n", i, data));
+        }
+        xml.push_str("  <value name=\"TextColorIdx\" type=\"hex\" data=\"10\"/>\n</key>\n");
+        xml
+    }
+
+    #[test]
+    fn test_attribute() {
+        assert_eq!(attribute("value name=\"a\" data='b'/", "name"), Some("a"));
+        assert_eq!(attribute("value name=\"a\" data='b'/", "data"), Some("b"));
+        assert_eq!(attribute("value rename=\"a\" name = \"c\"", "name"), Some("c"));
+        assert_eq!(attribute("value name", "name"), None);
+    }
+
+    #[test]
+    fn test_conemu() {
+        let palette = conemu(&scheme()).unwrap();
+        assert_eq!(palette.ansi[0], (0, 0, 0));
+        assert_eq!(palette.ansi[1], (0x80, 0, 0));
+        assert_eq!(palette.ansi[4], (0, 0, 0x80));
+        assert_eq!(palette.ansi[6], (0, 0x80, 0x80));
+        assert_eq!(palette.ansi[9], (0xff, 0, 0));
+        assert_eq!(palette.ansi[12], (0, 0, 0xff));
+        assert_eq!(palette.foreground, (0xc0, 0xc0, 0xc0));
+        assert_eq!(palette.background, (0, 0, 0));
+
+        let xml = scheme().replace("</key>", "<value name=\"BackColorIdx\" type=\"hex\" data=\"0C\"/></key>");
+        assert_eq!(conemu(&xml).unwrap().background, (0xff, 0, 0));
+    }
+
+    #[test]
+    fn test_conemu_err() {
+        let xml = scheme().replace("ColorTable13", "ColorTableX");
+        assert_eq!(conemu(&xml), Err(ImportError::MissingColor("ColorTable13".to_string())));
+        let xml = scheme().replace("\"00ff00ff\"", "\"red\"");
+        assert_eq!(conemu(&xml), Err(ImportError::InvalidColor("ColorTable13".to_string(), "red".to_string())));
+    }
+}
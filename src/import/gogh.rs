@@ -0,0 +1,71 @@
+use serde_json::Value;
+
+use super::{parse_hex, ImportError};
+use crate::Palette;
+
+/// Read a Gogh color scheme in its JSON format, with the 16 terminal
+/// colors in the keys `color_01` through `color_16`.
+///
+/// # Examples
+///
+///     # extern crate colorparse;
+///     # fn main() {
+///     let scheme = r##"{
+///         "name": "Dracula",
+///         "color_01": "#262626", "color_02": "#E64747", "color_03": "#42E66C", "color_04": "#E4F34A",
+///         "color_05": "#9B6BDF", "color_06": "#E356A7", "color_07": "#75D7EC", "color_08": "#EFA554",
+///         "color_09": "#7A7A7A", "color_10": "#FF5555", "color_11": "#50FA7B", "color_12": "#F1FA8C",
+///         "color_13": "#BD93F9", "color_14": "#FF79C6", "color_15": "#8BE9FD", "color_16": "#F8F8F2",
+///         "background": "#282A36", "foreground": "#F8F8F2", "cursor": "#F8F8F2"
+///     }"##;
+///     let palette = colorparse::import::gogh(scheme).unwrap();
+///     assert_eq!(palette.ansi[1], (0xe6, 0x47, 0x47));
+///     # }
+pub fn gogh(json: &str) -> Result<Palette, ImportError> {
+    let scheme: Value = serde_json::from_str(json).map_err(|e| ImportError::Syntax(e.to_string()))?;
+    let color = |key: &str| match scheme.get(key) {
+        Some(Value::String(value)) => parse_hex(key, value),
+        Some(value) => Err(ImportError::InvalidColor(key.to_string(), value.to_string())),
+        None => Err(ImportError::MissingColor(key.to_string())),
+    };
+    let mut palette = Palette::default();
+    for (i, rgb) in palette.ansi.iter_mut().enumerate() {
+        *rgb = color(&format!("color_{:02}", i + 1))?;
+    }
+    palette.foreground = color("foreground")?;
+    palette.background = color("background")?;
+    Ok(palette)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NORD: &str = r##"{
+        "name": "Nord", "author": "",
+        "color_01": "#353535", "color_02": "#E64569", "color_03": "#89D287", "color_04": "#DAB752",
+        "color_05": "#439ECF", "color_06": "#D961DC", "color_07": "#64AAAF", "color_08": "#B3B3B3",
+        "color_09": "#535353", "color_10": "#E4859A", "color_11": "#A2CCA1", "color_12": "#E1E387",
+        "color_13": "#6FBBE2", "color_14": "#E586E7", "color_15": "#96DCDA", "color_16": "#DEDEDE",
+        "background": "#2E3440", "foreground": "#D8DEE9", "cursor": "#D8DEE9", "hash": "x"
+    }"##;
+
+    #[test]
+    fn test_gogh() {
+        let palette = gogh(NORD).unwrap();
+        assert_eq!(palette.ansi[0], (0x35, 0x35, 0x35));
+        assert_eq!(palette.ansi[9], (0xe4, 0x85, 0x9a));
+        assert_eq!(palette.ansi[15], (0xde, 0xde, 0xde));
+        assert_eq!(palette.foreground, (0xd8, 0xde, 0xe9));
+        assert_eq!(palette.background, (0x2e, 0x34, 0x40));
+    }
+
+    #[test]
+    fn test_gogh_err() {
+        assert!(matches!(gogh("{\"color_01\""), Err(ImportError::Syntax(_))));
+        assert_eq!(gogh(&NORD.replace("\"color_10\"", "\"color10\"")),
+                   Err(ImportError::MissingColor("color_10".to_string())));
+        assert_eq!(gogh(&NORD.replace("\"#DAB752\"", "\"yellow\"")),
+                   Err(ImportError::InvalidColor("color_04".to_string(), "yellow".to_string())));
+    }
+}
@@ -0,0 +1,118 @@
+use toml::{Table, Value};
+
+use super::{parse_hex, ImportError};
+use crate::Palette;
+
+/// Helix's names for terminal colors 0 through 15.
+const NAMES: [&str; 16] = [
+    "black", "red", "green", "yellow", "blue", "magenta", "cyan", "light-gray",
+    "gray", "light-red", "light-green", "light-yellow", "light-blue", "light-magenta", "light-cyan", "white",
+];
+
+/// Read a Helix theme file.
+///
+/// The terminal colors come from the entries of the theme's `palette`
+/// table named after Helix's terminal colors (`red`, `light-red`, `gray`,
+/// and so on), and the default colors from the `ui.text` foreground and
+/// the `ui.background` background. Helix themes need not define all of
+/// these; any left out keep their `Palette::default()` values.
+///
+/// # Examples
+///
+///     # extern crate colorparse;
+///     # fn main() {
+///     let theme = r##"
+///     "ui.background" = { bg = "bg" }
+///     "ui.text" = "fg"
+///
+///     [palette]
+///     bg = "#1e1e2e"
+///     fg = "#cdd6f4"
+///     red = "#f38ba8"
+///     "##;
+///     let palette = colorparse::import::helix(theme).unwrap();
+///     assert_eq!(palette.ansi[1], (0xf3, 0x8b, 0xa8));
+///     assert_eq!(palette.background, (0x1e, 0x1e, 0x2e));
+///     # }
+pub fn helix(toml: &str) -> Result<Palette, ImportError> {
+    let theme: Table = toml.parse().map_err(|e: toml::de::Error| ImportError::Syntax(e.message().to_string()))?;
+    let empty = Table::new();
+    let named = match theme.get("palette") {
+        Some(Value::Table(table)) => table,
+        Some(value) => return Err(ImportError::InvalidColor("palette".to_string(), value.to_string())),
+        None => &empty,
+    };
+    // A color is a `#rrggbb` value or the name of a palette entry; any
+    // other name is one of Helix's terminal colors, left as is.
+    let resolve = |key: &str, value: &Value| match value {
+        Value::String(s) if s.starts_with('#') => parse_hex(key, s).map(Some),
+        Value::String(s) => match named.get(s.as_str()) {
+            Some(Value::String(hex)) if hex.starts_with('#') => parse_hex(key, hex).map(Some),
+            _ => Ok(None),
+        },
+        _ => Err(ImportError::InvalidColor(key.to_string(), value.to_string())),
+    };
+    let mut palette = Palette::default();
+    for (rgb, name) in palette.ansi.iter_mut().zip(NAMES.iter()) {
+        if let Some(value) = named.get(*name) {
+            *rgb = resolve(&format!("palette.{}", name), value)?.unwrap_or(*rgb);
+        }
+    }
+    let style_color = |key: &str, field: &str| match theme.get(key) {
+        Some(Value::Table(style)) => match style.get(field) {
+            Some(value) => resolve(key, value),
+            None => Ok(None),
+        },
+        Some(value) if field == "fg" => resolve(key, value),
+        _ => Ok(None),
+    };
+    if let Some(rgb) = style_color("ui.text", "fg")? {
+        palette.foreground = rgb;
+    }
+    if let Some(rgb) = style_color("ui.background", "bg")? {
+        palette.background = rgb;
+    }
+    Ok(palette)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const THEME: &str = r##"
+        "ui.background" = { bg = "base" }
+        "ui.text" = { fg = "text", modifiers = ["bold"] }
+        "ui.cursor" = { fg = "#ffffff", bg = "red" }
+
+        [palette]
+        base = "#191724"
+        text = "#e0def4"
+        black = "#26233a"
+        red = "#eb6f92"
+        gray = "#6e6a86"
+        light-cyan = "#9ccfd8"
+        white = "white"
+    "##;
+
+    #[test]
+    fn test_helix() {
+        let palette = helix(THEME).unwrap();
+        assert_eq!(palette.ansi[0], (0x26, 0x23, 0x3a));
+        assert_eq!(palette.ansi[1], (0xeb, 0x6f, 0x92));
+        assert_eq!(palette.ansi[2], Palette::VGA.ansi[2]);
+        assert_eq!(palette.ansi[8], (0x6e, 0x6a, 0x86));
+        assert_eq!(palette.ansi[14], (0x9c, 0xcf, 0xd8));
+        assert_eq!(palette.ansi[15], Palette::VGA.ansi[15]);
+        assert_eq!(palette.foreground, (0xe0, 0xde, 0xf4));
+        assert_eq!(palette.background, (0x19, 0x17, 0x24));
+        assert_eq!(helix("").unwrap(), Palette::default());
+    }
+
+    #[test]
+    fn test_helix_err() {
+        assert!(matches!(helix("[palette"), Err(ImportError::Syntax(_))));
+        assert_eq!(helix(&THEME.replace("\"#eb6f92\"", "\"#eb6f9\"")),
+                   Err(ImportError::InvalidColor("palette.red".to_string(), "#eb6f9".to_string())));
+        assert_eq!(helix("\"ui.text\" = 3"), Err(ImportError::InvalidColor("ui.text".to_string(), "3".to_string())));
+    }
+}
@@ -0,0 +1,92 @@
+//! Importers that read terminal and editor theme files into a `Palette`,
+//! and Base16 color schemes into a `Base16Scheme`.
+//!
+//! Each importer is behind a feature named for the file format it reads:
+//! `json`, `toml`, `xml`, or `base16`.
+
+#[cfg(feature = "toml")]
+mod alacritty;
+#[cfg(feature = "base16")]
+mod base16;
+#[cfg(feature = "xml")]
+mod conemu;
+#[cfg(feature = "json")]
+mod gogh;
+#[cfg(feature = "toml")]
+mod helix;
+#[cfg(feature = "json")]
+mod terminal_sexy;
+#[cfg(feature = "json")]
+mod vscode;
+#[cfg(feature = "json")]
+mod windows_terminal;
+
+#[cfg(feature = "toml")]
+pub use self::alacritty::alacritty;
+#[cfg(feature = "base16")]
+pub use self::base16::{base16, Base16Scheme};
+#[cfg(feature = "xml")]
+pub use self::conemu::conemu;
+#[cfg(feature = "json")]
+pub use self::gogh::gogh;
+#[cfg(feature = "toml")]
+pub use self::helix::helix;
+#[cfg(feature = "json")]
+pub use self::terminal_sexy::terminal_sexy;
+#[cfg(feature = "json")]
+pub use self::vscode::vscode;
+#[cfg(feature = "json")]
+pub use self::windows_terminal::windows_terminal;
+
+/// Type for errors returned by the importers.
+#[derive(Debug, PartialEq)]
+pub enum ImportError {
+    /// The file is not valid in its format; carries the underlying
+    /// parser's description of the problem.
+    Syntax(String),
+    /// A required color is missing from the file; carries the key.
+    MissingColor(String),
+    /// A color has a value that is not a color; carries the key and the
+    /// value.
+    InvalidColor(String, String),
+}
+
+impl std::fmt::Display for ImportError {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Syntax(message) => write!(fmt, "Error importing palette: {}", message),
+            Self::MissingColor(key) => write!(fmt, "Error importing palette: missing color \"{}\"", key),
+            Self::InvalidColor(key, value) => {
+                write!(fmt, "Error importing palette: invalid color \"{}\" for \"{}\"", value, key)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ImportError {}
+
+/// Parse a `#rrggbb` color, with or without the `#`.
+#[cfg(any(feature = "base16", feature = "json", feature = "toml"))]
+fn parse_hex(key: &str, value: &str) -> Result<(u8, u8, u8), ImportError> {
+    let invalid = || ImportError::InvalidColor(key.to_string(), value.to_string());
+    let hex = value.strip_prefix('#').unwrap_or(value);
+    if hex.len() != 6 || !hex.is_ascii() {
+        return Err(invalid());
+    }
+    let component = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).map_err(|_| invalid());
+    Ok((component(0)?, component(2)?, component(4)?))
+}
+
+#[cfg(all(test, any(feature = "base16", feature = "json", feature = "toml")))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_hex() {
+        assert_eq!(parse_hex("red", "#C50F1F"), Ok((0xc5, 0x0f, 0x1f)));
+        assert_eq!(parse_hex("red", "c50f1f"), Ok((0xc5, 0x0f, 0x1f)));
+        let invalid = |value: &str| Err(ImportError::InvalidColor("red".to_string(), value.to_string()));
+        assert_eq!(parse_hex("red", "#c50f1"), invalid("#c50f1"));
+        assert_eq!(parse_hex("red", "#c50f1g"), invalid("#c50f1g"));
+    }
+}
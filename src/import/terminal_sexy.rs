@@ -0,0 +1,77 @@
+use serde_json::Value;
+
+use super::{parse_hex, ImportError};
+use crate::Palette;
+
+/// Read a color scheme exported from terminal.sexy in its JSON format,
+/// with the 16 terminal colors in a `color` array.
+///
+/// # Examples
+///
+///     # extern crate colorparse;
+///     # fn main() {
+///     let scheme = r##"{
+///         "name": "Tomorrow Night", "author": "Chris Kempson",
+///         "color": [
+///             "#1d1f21", "#cc6666", "#b5bd68", "#f0c674", "#81a2be", "#b294bb", "#8abeb7", "#c5c8c6",
+///             "#969896", "#cc6666", "#b5bd68", "#f0c674", "#81a2be", "#b294bb", "#8abeb7", "#ffffff"
+///         ],
+///         "foreground": "#c5c8c6", "background": "#1d1f21"
+///     }"##;
+///     let palette = colorparse::import::terminal_sexy(scheme).unwrap();
+///     assert_eq!(palette.ansi[8], (0x96, 0x98, 0x96));
+///     # }
+pub fn terminal_sexy(json: &str) -> Result<Palette, ImportError> {
+    let scheme: Value = serde_json::from_str(json).map_err(|e| ImportError::Syntax(e.to_string()))?;
+    let color = |key: &str, value: Option<&Value>| match value {
+        Some(Value::String(value)) => parse_hex(key, value),
+        Some(value) => Err(ImportError::InvalidColor(key.to_string(), value.to_string())),
+        None => Err(ImportError::MissingColor(key.to_string())),
+    };
+    let colors = match scheme.get("color") {
+        Some(Value::Array(colors)) => colors,
+        Some(value) => return Err(ImportError::InvalidColor("color".to_string(), value.to_string())),
+        None => return Err(ImportError::MissingColor("color".to_string())),
+    };
+    let mut palette = Palette::default();
+    for (i, rgb) in palette.ansi.iter_mut().enumerate() {
+        *rgb = color(&format!("color[{}]", i), colors.get(i))?;
+    }
+    palette.foreground = color("foreground", scheme.get("foreground"))?;
+    palette.background = color("background", scheme.get("background"))?;
+    Ok(palette)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCHEME: &str = r##"{
+        "name": "Ocean", "author": "",
+        "color": [
+            "#2b303b", "#bf616a", "#a3be8c", "#ebcb8b", "#8fa1b3", "#b48ead", "#96b5b4", "#c0c5ce",
+            "#65737e", "#bf616a", "#a3be8c", "#ebcb8b", "#8fa1b3", "#b48ead", "#96b5b4", "#eff1f5"
+        ],
+        "foreground": "#c0c5ce", "background": "#2b303b"
+    }"##;
+
+    #[test]
+    fn test_terminal_sexy() {
+        let palette = terminal_sexy(SCHEME).unwrap();
+        assert_eq!(palette.ansi[1], (0xbf, 0x61, 0x6a));
+        assert_eq!(palette.ansi[15], (0xef, 0xf1, 0xf5));
+        assert_eq!(palette.foreground, (0xc0, 0xc5, 0xce));
+        assert_eq!(palette.background, (0x2b, 0x30, 0x3b));
+    }
+
+    #[test]
+    fn test_terminal_sexy_err() {
+        assert!(matches!(terminal_sexy("["), Err(ImportError::Syntax(_))));
+        assert_eq!(terminal_sexy(&SCHEME.replace(", \"#eff1f5\"", "")),
+                   Err(ImportError::MissingColor("color[15]".to_string())));
+        assert_eq!(terminal_sexy(r#"{"color": "red"}"#),
+                   Err(ImportError::InvalidColor("color".to_string(), "\"red\"".to_string())));
+        assert_eq!(terminal_sexy(&SCHEME.replace("\"foreground\"", "\"fg\"")),
+                   Err(ImportError::MissingColor("foreground".to_string())));
+    }
+}
@@ -0,0 +1,177 @@
+use serde_json::Value;
+
+use super::{parse_hex, ImportError};
+use crate::Palette;
+
+/// The VS Code keys for colors 0 through 15.
+const KEYS: [&str; 16] = [
+    "terminal.ansiBlack", "terminal.ansiRed", "terminal.ansiGreen", "terminal.ansiYellow",
+    "terminal.ansiBlue", "terminal.ansiMagenta", "terminal.ansiCyan", "terminal.ansiWhite",
+    "terminal.ansiBrightBlack", "terminal.ansiBrightRed", "terminal.ansiBrightGreen", "terminal.ansiBrightYellow",
+    "terminal.ansiBrightBlue", "terminal.ansiBrightMagenta", "terminal.ansiBrightCyan", "terminal.ansiBrightWhite",
+];
+
+/// Remove the comments and trailing commas that VS Code allows in its
+/// JSON files ("JSON with comments"), leaving strings intact.
+fn strip_jsonc(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => {
+                out.push(c);
+                while let Some(c) = chars.next() {
+                    out.push(c);
+                    match c {
+                        '\\' => out.extend(chars.next()),
+                        '"' => break,
+                        _ => {}
+                    }
+                }
+            }
+            '/' if chars.peek() == Some(&'/') => {
+                while chars.peek().is_some_and(|&c| c != '\n') {
+                    chars.next();
+                }
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut prev = ' ';
+                for c in chars.by_ref() {
+                    if prev == '*' && c == '/' {
+                        break;
+                    }
+                    prev = c;
+                }
+                out.push(' ');
+            }
+            '}' | ']' => {
+                let trimmed = out.trim_end().len();
+                if out[..trimmed].ends_with(',') {
+                    out.truncate(trimmed - 1);
+                }
+                out.push(c);
+            }
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// Parse a VS Code color, which may be `#rgb`, `#rgba`, `#rrggbb`, or
+/// `#rrggbbaa`; the alpha channel is ignored.
+fn parse_color(key: &str, value: &str) -> Result<(u8, u8, u8), ImportError> {
+    let hex = value.strip_prefix('#').unwrap_or("");
+    match hex.len() {
+        3 | 4 if hex.is_ascii() => {
+            let expanded: String = hex[..3].chars().flat_map(|c| vec![c, c]).collect();
+            parse_hex(key, &expanded).map_err(|_| ImportError::InvalidColor(key.to_string(), value.to_string()))
+        }
+        8 if hex.is_ascii() => {
+            parse_hex(key, &hex[..6]).map_err(|_| ImportError::InvalidColor(key.to_string(), value.to_string()))
+        }
+        _ => parse_hex(key, value),
+    }
+}
+
+/// Read the terminal colors of a VS Code color theme (the JSON file named
+/// by a theme extension's `contributes.themes`).
+///
+/// Takes colors 0 through 15 from the `terminal.ansi*` entries of the
+/// theme's `colors`, and the default foreground and background from
+/// `terminal.foreground` and `terminal.background`, falling back to the
+/// editor's colors. Comments and trailing commas are allowed, as in VS
+/// Code itself.
+///
+/// # Examples
+///
+///     # extern crate colorparse;
+///     # fn main() {
+///     let theme = r##"{
+///         "name": "Example",
+///         "colors": {
+///             // Terminal colors
+///             "terminal.ansiBlack": "#000000", "terminal.ansiRed": "#cd3131",
+///             "terminal.ansiGreen": "#0dbc79", "terminal.ansiYellow": "#e5e510",
+///             "terminal.ansiBlue": "#2472c8", "terminal.ansiMagenta": "#bc3fbc",
+///             "terminal.ansiCyan": "#11a8cd", "terminal.ansiWhite": "#e5e5e5",
+///             "terminal.ansiBrightBlack": "#666666", "terminal.ansiBrightRed": "#f14c4c",
+///             "terminal.ansiBrightGreen": "#23d18b", "terminal.ansiBrightYellow": "#f5f543",
+///             "terminal.ansiBrightBlue": "#3b8eea", "terminal.ansiBrightMagenta": "#d670d6",
+///             "terminal.ansiBrightCyan": "#29b8db", "terminal.ansiBrightWhite": "#e5e5e5",
+///             "editor.foreground": "#d4d4d4", "editor.background": "#1e1e1e",
+///         },
+///     }"##;
+///     let palette = colorparse::import::vscode(theme).unwrap();
+///     assert_eq!(palette.ansi[1], (0xcd, 0x31, 0x31));
+///     assert_eq!(palette.background, (0x1e, 0x1e, 0x1e));
+///     # }
+pub fn vscode(json: &str) -> Result<Palette, ImportError> {
+    let theme: Value = serde_json::from_str(&strip_jsonc(json)).map_err(|e| ImportError::Syntax(e.to_string()))?;
+    let colors = theme.get("colors").ok_or_else(|| ImportError::MissingColor("colors".to_string()))?;
+    let color = |key: &str| match colors.get(key) {
+        Some(Value::String(value)) => parse_color(key, value).map(Some),
+        Some(value) => Err(ImportError::InvalidColor(key.to_string(), value.to_string())),
+        None => Ok(None),
+    };
+    let required = |key: &str| color(key)?.ok_or_else(|| ImportError::MissingColor(key.to_string()));
+    let mut palette = Palette::default();
+    for (rgb, key) in palette.ansi.iter_mut().zip(KEYS.iter()) {
+        *rgb = required(key)?;
+    }
+    palette.foreground = match color("terminal.foreground")? {
+        Some(rgb) => rgb,
+        None => required("editor.foreground")?,
+    };
+    palette.background = match color("terminal.background")? {
+        Some(rgb) => rgb,
+        None => required("editor.background")?,
+    };
+    Ok(palette)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn theme(extra: &str) -> String {
+        let mut colors: Vec<String> = KEYS.iter().enumerate()
+            .map(|(i, key)| format!("\"{}\": \"#{:02x}0000\"", key, i))
+            .collect();
+        colors.push(extra.to_string());
+        format!("{{\"colors\": {{{}}}}}", colors.join(",\n"))
+    }
+
+    #[test]
+    fn test_strip_jsonc() {
+        assert_eq!(strip_jsonc("{\"a\": 1, // one\n\"b\": [2, 3,],}"), "{\"a\": 1, \n\"b\": [2, 3]}");
+        assert_eq!(strip_jsonc("{/* x */\"a\": \"//not /* a comment\"}"), "{ \"a\": \"//not /* a comment\"}");
+        assert_eq!(strip_jsonc("[\"\\\",\", ]"), "[\"\\\",\"]");
+    }
+
+    #[test]
+    fn test_vscode() {
+        let colors = "\"terminal.foreground\": \"#abcd\", \"terminal.background\": \"#10203040\"";
+        let palette = vscode(&theme(colors)).unwrap();
+        assert_eq!(palette.ansi[0], (0, 0, 0));
+        assert_eq!(palette.ansi[15], (0x0f, 0, 0));
+        assert_eq!(palette.foreground, (0xaa, 0xbb, 0xcc));
+        assert_eq!(palette.background, (0x10, 0x20, 0x30));
+
+        let palette = vscode(&theme("\"editor.foreground\": \"#ffffff\", \"editor.background\": \"#000000\",")).unwrap();
+        assert_eq!(palette.foreground, (0xff, 0xff, 0xff));
+        assert_eq!(palette.background, (0, 0, 0));
+    }
+
+    #[test]
+    fn test_vscode_err() {
+        assert!(matches!(vscode("{\"colors\": {"), Err(ImportError::Syntax(_))));
+        assert_eq!(vscode("{}"), Err(ImportError::MissingColor("colors".to_string())));
+        assert_eq!(vscode(&theme("\"editor.foreground\": \"#ffffff\"")),
+                   Err(ImportError::MissingColor("editor.background".to_string())));
+        assert_eq!(vscode(&theme("\"terminal.foreground\": \"white\"")),
+                   Err(ImportError::InvalidColor("terminal.foreground".to_string(), "white".to_string())));
+        let missing = theme("").replace("terminal.ansiCyan", "terminal.cyan");
+        assert_eq!(vscode(&missing), Err(ImportError::MissingColor("terminal.ansiCyan".to_string())));
+    }
+}
@@ -0,0 +1,79 @@
+use serde_json::Value;
+
+use super::{parse_hex, ImportError};
+use crate::Palette;
+
+/// The Windows Terminal keys for colors 0 through 15.
+const KEYS: [&str; 16] = [
+    "black", "red", "green", "yellow", "blue", "purple", "cyan", "white",
+    "brightBlack", "brightRed", "brightGreen", "brightYellow", "brightBlue", "brightPurple", "brightCyan", "brightWhite",
+];
+
+/// Read a Windows Terminal color scheme, the JSON object found in the
+/// `schemes` array of its `settings.json`.
+///
+/// # Examples
+///
+///     # extern crate colorparse;
+///     # fn main() {
+///     let scheme = r##"{
+///         "name": "Campbell",
+///         "foreground": "#CCCCCC", "background": "#0C0C0C",
+///         "black": "#0C0C0C", "red": "#C50F1F", "green": "#13A10E", "yellow": "#C19C00",
+///         "blue": "#0037DA", "purple": "#881798", "cyan": "#3A96DD", "white": "#CCCCCC",
+///         "brightBlack": "#767676", "brightRed": "#E74856", "brightGreen": "#16C60C",
+///         "brightYellow": "#F9F1A5", "brightBlue": "#3B78FF", "brightPurple": "#B4009E",
+///         "brightCyan": "#61D6D6", "brightWhite": "#F2F2F2"
+///     }"##;
+///     let palette = colorparse::import::windows_terminal(scheme).unwrap();
+///     assert_eq!(palette.ansi[1], (0xc5, 0x0f, 0x1f));
+///     # }
+pub fn windows_terminal(json: &str) -> Result<Palette, ImportError> {
+    let scheme: Value = serde_json::from_str(json).map_err(|e| ImportError::Syntax(e.to_string()))?;
+    let color = |key: &str| match scheme.get(key) {
+        Some(Value::String(value)) => parse_hex(key, value),
+        Some(value) => Err(ImportError::InvalidColor(key.to_string(), value.to_string())),
+        None => Err(ImportError::MissingColor(key.to_string())),
+    };
+    let mut palette = Palette::default();
+    for (rgb, key) in palette.ansi.iter_mut().zip(KEYS.iter()) {
+        *rgb = color(key)?;
+    }
+    palette.foreground = color("foreground")?;
+    palette.background = color("background")?;
+    Ok(palette)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ONE_HALF_DARK: &str = r##"{
+        "name": "One Half Dark",
+        "foreground": "#DCDFE4", "background": "#282C34",
+        "cursorColor": "#FFFFFF", "selectionBackground": "#FFFFFF",
+        "black": "#282C34", "red": "#E06C75", "green": "#98C379", "yellow": "#E5C07B",
+        "blue": "#61AFEF", "purple": "#C678DD", "cyan": "#56B6C2", "white": "#DCDFE4",
+        "brightBlack": "#5A6374", "brightRed": "#E06C75", "brightGreen": "#98C379", "brightYellow": "#E5C07B",
+        "brightBlue": "#61AFEF", "brightPurple": "#C678DD", "brightCyan": "#56B6C2", "brightWhite": "#DCDFE4"
+    }"##;
+
+    #[test]
+    fn test_windows_terminal() {
+        let palette = windows_terminal(ONE_HALF_DARK).unwrap();
+        assert_eq!(palette.ansi[0], (0x28, 0x2c, 0x34));
+        assert_eq!(palette.ansi[5], (0xc6, 0x78, 0xdd));
+        assert_eq!(palette.ansi[8], (0x5a, 0x63, 0x74));
+        assert_eq!(palette.foreground, (0xdc, 0xdf, 0xe4));
+        assert_eq!(palette.background, (0x28, 0x2c, 0x34));
+    }
+
+    #[test]
+    fn test_windows_terminal_err() {
+        assert!(matches!(windows_terminal("{"), Err(ImportError::Syntax(_))));
+        let missing = ONE_HALF_DARK.replace("\"brightCyan\"", "\"cyan2\"");
+        assert_eq!(windows_terminal(&missing), Err(ImportError::MissingColor("brightCyan".to_string())));
+        let invalid = ONE_HALF_DARK.replace("\"#E06C75\"", "17");
+        assert_eq!(windows_terminal(&invalid), Err(ImportError::InvalidColor("red".to_string(), "17".to_string())));
+    }
+}
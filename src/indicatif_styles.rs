@@ -0,0 +1,207 @@
+//! Theming `indicatif` progress bars with Git-syntax specs.
+//!
+//! `indicatif` templates style each placeholder with a dotted style string,
+//! as in `{bar:40.cyan/blue}`. This module lets a template name `Theme`
+//! slots in place of those strings, written with a leading `@`, as in
+//! `{bar:40.@bar/@bar.empty}`, and resolves them into the dotted form.
+//!
+//! # Examples
+//!
+//!     # extern crate colorparse;
+//!     # extern crate indicatif;
+//!     use colorparse::indicatif_styles::progress_style;
+//!     use colorparse::Theme;
+//!
+//!     # fn main() {
+//!     let mut theme = Theme::new();
+//!     theme.set("bar", "bold cyan").unwrap();
+//!     theme.set("bar.empty", "blue").unwrap();
+//!     let style = progress_style("{spinner} {bar:40.@bar/@bar.empty} {pos}/{len}", &theme).unwrap();
+//!     let bar = indicatif::ProgressBar::new(100).with_style(style);
+//!     # drop(bar);
+//!     # }
+
+use std::fmt;
+
+use ansi_term::{Color, Style};
+use indicatif::ProgressStyle;
+
+use crate::{TemplateError, Theme};
+
+fn color_name(color: Color) -> String {
+    match color {
+        Color::Black => "black".to_string(),
+        Color::Red => "red".to_string(),
+        Color::Green => "green".to_string(),
+        Color::Yellow => "yellow".to_string(),
+        Color::Blue => "blue".to_string(),
+        Color::Purple => "magenta".to_string(),
+        Color::Cyan => "cyan".to_string(),
+        Color::White => "white".to_string(),
+        Color::Fixed(n) => n.to_string(),
+        Color::RGB(r, g, b) => format!("#{:02x}{:02x}{:02x}", r, g, b),
+    }
+}
+
+/// Convert `style` to the dotted style string used by `indicatif`
+/// templates and `console::Style::from_dotted_str`, such as
+/// `red.on_blue.bold`.
+///
+/// That syntax has no way to express italic, so italic is dropped.
+pub fn to_dotted_str(style: &Style) -> String {
+    let mut parts = Vec::new();
+    if let Some(fg) = style.foreground {
+        parts.push(color_name(fg));
+    }
+    if let Some(bg) = style.background {
+        parts.push(format!("on_{}", color_name(bg)));
+    }
+    let attributes = [
+        (style.is_bold, "bold"),
+        (style.is_dimmed, "dim"),
+        (style.is_underline, "underlined"),
+        (style.is_blink, "blink"),
+        (style.is_reverse, "reverse"),
+        (style.is_hidden, "hidden"),
+        (style.is_strikethrough, "strikethrough"),
+    ];
+    parts.extend(attributes.iter().filter(|a| a.0).map(|a| a.1.to_string()));
+    parts.join(".")
+}
+
+fn resolve_style(template: &str, style: &str, theme: &Theme, out: &mut String) -> Result<(), TemplateError> {
+    for (i, part) in style.split('/').enumerate() {
+        if i > 0 {
+            out.push('/');
+        }
+        if let Some(slot) = part.strip_prefix('@') {
+            match theme.get(slot) {
+                Some(style) => out.push_str(&to_dotted_str(&style)),
+                None => return Err(TemplateError::UnknownSlot(template.to_string(), slot.to_string())),
+            }
+        } else {
+            out.push_str(part);
+        }
+    }
+    Ok(())
+}
+
+/// Rewrite an `indicatif` template, replacing each `@slot` style in a
+/// placeholder with the dotted style string for that slot in `theme`.
+///
+/// Placeholders and styles not using `@` pass through unchanged, as do
+/// the `{{` and `}}` escapes.
+pub fn resolve_template(template: &str, theme: &Theme) -> Result<String, TemplateError> {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(i) = rest.find('{') {
+        out.push_str(&rest[..i]);
+        rest = &rest[i..];
+        if rest.starts_with("{{") {
+            out.push_str("{{");
+            rest = &rest[2..];
+            continue;
+        }
+        let end = match rest.find('}') {
+            Some(end) => end,
+            None => break,
+        };
+        let placeholder = &rest[..end];
+        match placeholder.find(':').and_then(|colon| placeholder[colon..].find('.').map(|dot| colon + dot + 1)) {
+            Some(style_start) => {
+                out.push_str(&placeholder[..style_start]);
+                resolve_style(template, &placeholder[style_start..], theme, &mut out)?;
+            }
+            None => out.push_str(placeholder),
+        }
+        rest = &rest[end..];
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+/// Type for errors returned by `progress_style`.
+#[derive(Debug)]
+pub enum ProgressStyleError {
+    /// The template named a slot not present in the theme.
+    Template(TemplateError),
+    /// `indicatif` rejected the resolved template.
+    Indicatif(indicatif::style::TemplateError),
+}
+
+impl fmt::Display for ProgressStyleError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Template(e) => e.fmt(fmt),
+            Self::Indicatif(e) => e.fmt(fmt),
+        }
+    }
+}
+
+impl std::error::Error for ProgressStyleError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Template(e) => Some(e),
+            Self::Indicatif(e) => Some(e),
+        }
+    }
+}
+
+/// Create a `ProgressStyle` from a template that refers to `theme` slots,
+/// as resolved by `resolve_template`.
+pub fn progress_style(template: &str, theme: &Theme) -> Result<ProgressStyle, ProgressStyleError> {
+    let template = resolve_template(template, theme).map_err(ProgressStyleError::Template)?;
+    ProgressStyle::with_template(&template).map_err(ProgressStyleError::Indicatif)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ansi_term::Color::*;
+
+    #[test]
+    fn test_to_dotted_str() {
+        assert_eq!(to_dotted_str(&Style::new()), "");
+        assert_eq!(to_dotted_str(&Red.on(Blue).bold()), "red.on_blue.bold");
+        assert_eq!(to_dotted_str(&Purple.normal()), "magenta");
+        assert_eq!(to_dotted_str(&Fixed(208).on(RGB(0, 0x80, 0xff))), "208.on_#0080ff");
+        assert_eq!(to_dotted_str(&Style::new().dimmed().underline().blink().reverse().hidden().strikethrough()),
+                   "dim.underlined.blink.reverse.hidden.strikethrough");
+        assert_eq!(to_dotted_str(&Cyan.italic()), "cyan");
+    }
+
+    #[test]
+    fn test_resolve_template() {
+        let mut theme = Theme::new();
+        theme.insert("bar", Cyan.bold());
+        theme.insert("bar.empty", Blue.normal());
+        theme.insert("msg", Fixed(8).normal());
+
+        macro_rules! test {
+            ($template:expr => Ok $out:expr) => {
+                assert_eq!(resolve_template($template, &theme), Ok($out.to_string()));
+            };
+            ($template:expr => $slot:expr) => {
+                assert_eq!(resolve_template($template, &theme),
+                           Err(TemplateError::UnknownSlot($template.to_string(), $slot.to_string())));
+            };
+        }
+
+        test!("" => Ok "");
+        test!("{bar:40.cyan/blue} {pos}" => Ok "{bar:40.cyan/blue} {pos}");
+        test!("{bar:40.@bar/@bar.empty}" => Ok "{bar:40.cyan.bold/blue}");
+        test!("{msg:>10!.@msg} {{@msg}}" => Ok "{msg:>10!.8} {{@msg}}");
+        test!("{prefix:.@bar} {spinner:@bar}" => Ok "{prefix:.cyan.bold} {spinner:@bar}");
+        test!("{bar:.@nope}" => "nope");
+        test!("{bar:.red/@nope}" => "nope");
+    }
+
+    #[test]
+    fn test_progress_style() {
+        let mut theme = Theme::new();
+        theme.insert("bar", Cyan.bold());
+        assert!(progress_style("{bar:40.@bar} {pos}", &theme).is_ok());
+        assert!(matches!(progress_style("{bar:.@nope}", &theme), Err(ProgressStyleError::Template(_))));
+        assert!(matches!(progress_style("{bar:x.@bar}", &theme), Err(ProgressStyleError::Indicatif(_))));
+    }
+}
@@ -0,0 +1,159 @@
+use std::ops::Range;
+
+use ansi_term::Color;
+
+use crate::{attribute_named, color_word, parse_bool, span, words, Attribute, UnderlineStyle};
+
+/// A word of a style string, classified by what it means on its own, as
+/// returned by `lex`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Token<'a> {
+    kind: TokenKind,
+    text: &'a str,
+    span: Range<usize>,
+}
+
+/// The kinds of word `lex` recognizes, as returned by `Token::kind`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[non_exhaustive]
+pub enum TokenKind {
+    /// A color, or `None` for the default (`normal` or `-1`). Whether it
+    /// sets the foreground or the background depends on the colors before
+    /// it.
+    Color(Option<Color>),
+    /// The keyword `default`, for the terminal's default color, which
+    /// replaces the color of a style it is combined with.
+    DefaultColor,
+    /// An attribute being set, as by `bold` or `bold=true`.
+    Attribute(Attribute),
+    /// An attribute being cleared, as by `nobold`, `no-bold`, or
+    /// `bold=false`.
+    NegatedAttribute(Attribute),
+    /// A shape of underline, such as `curly-ul`, which also sets underline.
+    UnderlineStyle(UnderlineStyle),
+    /// The color of the underline, as set by `ul=<color>`, or `None` for
+    /// the default (`ul=normal` or `ul=default`).
+    UnderlineColor(Option<Color>),
+    /// The keyword `reset`.
+    Reset,
+    /// A word that means nothing.
+    Unknown,
+}
+
+impl<'a> Token<'a> {
+    /// Return what the word means.
+    pub fn kind(&self) -> TokenKind {
+        self.kind
+    }
+
+    /// Return the word as written in the input.
+    pub fn text(&self) -> &'a str {
+        self.text
+    }
+
+    /// Return the byte range of the word within the input.
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+}
+
+/// Return what `word`, in lowercase, means on its own.
+pub(crate) fn classify(word: &str) -> TokenKind {
+    let negated = word.strip_prefix("no-").or_else(|| word.strip_prefix("no")).and_then(attribute_named);
+    if word == "reset" {
+        TokenKind::Reset
+    } else if word == "default" {
+        TokenKind::DefaultColor
+    } else if let Some(attribute) = attribute_named(word) {
+        TokenKind::Attribute(attribute)
+    } else if let Some(attribute) = negated {
+        TokenKind::NegatedAttribute(attribute)
+    } else if let Some(&style) = UnderlineStyle::ALL[1..].iter().find(|s| s.name() == word) {
+        TokenKind::UnderlineStyle(style)
+    } else if let Some(pos) = word.find('=') {
+        let (attr, value) = (&word[..pos], &word[pos + 1..]);
+        match (attribute_named(attr), parse_bool(value), color_word(value)) {
+            (Some(attribute), Some(true), _) => TokenKind::Attribute(attribute),
+            (Some(attribute), Some(false), _) => TokenKind::NegatedAttribute(attribute),
+            (Some(Attribute::Underline), None, _) if value == "default" => TokenKind::UnderlineColor(None),
+            (Some(Attribute::Underline), None, Ok(color)) => TokenKind::UnderlineColor(color),
+            _ => TokenKind::Unknown,
+        }
+    } else if let Ok(color) = color_word(word) {
+        TokenKind::Color(color)
+    } else {
+        TokenKind::Unknown
+    }
+}
+
+/// Split a style string into its words, as `parse_spec` does, and classify
+/// each one, for tools such as editors that highlight style strings.
+///
+/// Each word is classified on its own, ignoring the words around it, so
+/// a third color is still a `Color` and an unknown word does not stop the
+/// lexer; `parse_spec` reports both as errors.
+///
+/// # Examples
+///
+///     # extern crate ansi_term;
+///     # extern crate colorparse;
+///     use ansi_term::Color::Red;
+///     use colorparse::{lex, Attribute, TokenKind};
+///
+///     # fn main() {
+///     let tokens: Vec<_> = lex("bold  Red nosparkle noul").collect();
+///     assert_eq!(tokens[0].kind(), TokenKind::Attribute(Attribute::Bold));
+///     assert_eq!(tokens[1].kind(), TokenKind::Color(Some(Red)));
+///     assert_eq!((tokens[1].text(), tokens[1].span()), ("Red", 6..9));
+///     assert_eq!(tokens[2].kind(), TokenKind::Unknown);
+///     assert_eq!(tokens[3].kind(), TokenKind::NegatedAttribute(Attribute::Underline));
+///     # }
+pub fn lex(s: &str) -> impl Iterator<Item = Token<'_>> {
+    words(s).map(move |word| Token { kind: classify(&word.to_lowercase()), text: word, span: span(s, word) })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::TokenKind::*;
+    use ansi_term::Color::*;
+
+    #[test]
+    fn test_lex() {
+        macro_rules! test {
+            ($s:expr => [$($text:expr => $kind:expr),*]) => {
+                let tokens: Vec<_> = lex($s).map(|t| (t.text(), t.kind())).collect();
+                assert_eq!(tokens, vec![$(($text, $kind)),*]);
+            };
+        }
+
+        test!("" => []);
+        test!("  \t " => []);
+        test!("reset -1 normal 208" => ["reset" => Reset, "-1" => Color(None), "normal" => Color(None), "208" => Color(Some(Fixed(208)))]);
+        test!("Default" => ["Default" => DefaultColor]);
+        test!("rgb(1, 2, 3) #F80" => ["rgb(1, 2, 3)" => Color(Some(RGB(1, 2, 3))), "#F80" => Color(Some(RGB(0xff, 0x88, 0)))]);
+        test!("conceal no-dim bold=off ul=on" => [
+            "conceal" => Attribute(super::Attribute::Hidden),
+            "no-dim" => NegatedAttribute(super::Attribute::Dimmed),
+            "bold=off" => NegatedAttribute(super::Attribute::Bold),
+            "ul=on" => Attribute(super::Attribute::Underline)
+        ]);
+        test!("curly-ul ul=red ul=normal UL=Default" => [
+            "curly-ul" => UnderlineStyle(super::UnderlineStyle::Curly),
+            "ul=red" => UnderlineColor(Some(Red)),
+            "ul=normal" => UnderlineColor(None),
+            "UL=Default" => UnderlineColor(None)
+        ]);
+        test!("bold=default ul=defaults" => ["bold=default" => Unknown, "ul=defaults" => Unknown]);
+        test!("bold=red blu nothing" => ["bold=red" => Unknown, "blu" => Unknown, "nothing" => Unknown]);
+    }
+
+    #[test]
+    fn test_lex_spans() {
+        let s = "\u{A0}bold\u{3000}red  blue";
+        for token in lex(s) {
+            assert_eq!(&s[token.span()], token.text());
+        }
+        assert_eq!(lex(s).map(|t| t.span()).collect::<Vec<_>>(), vec![2..6, 9..12, 14..18]);
+    }
+}
@@ -13,33 +13,826 @@
 #![cfg_attr(test, deny(warnings))]
 
 extern crate ansi_term;
+#[cfg(feature = "anstyle")]
+extern crate anstyle;
+#[cfg(feature = "console")]
+extern crate console;
+#[cfg(feature = "crossterm")]
+extern crate crossterm;
+#[cfg(feature = "i18n")]
+extern crate fluent_bundle;
+#[cfg(feature = "i18n")]
+extern crate unic_langid;
+#[cfg(feature = "serde")]
+extern crate serde;
+#[cfg(any(feature = "json", all(test, feature = "serde")))]
+extern crate serde_json;
+#[cfg(all(unix, feature = "osc-query"))]
+extern crate libc;
+#[cfg(feature = "nu-ansi-term")]
+extern crate nu_ansi_term;
+#[cfg(feature = "owo-colors")]
+extern crate owo_colors;
+#[cfg(feature = "indicatif")]
+extern crate indicatif;
+#[cfg(feature = "ratatui")]
+extern crate ratatui;
+#[cfg(feature = "rayon")]
+extern crate rayon;
+#[cfg(feature = "termcolor")]
+extern crate termcolor;
+#[cfg(feature = "toml")]
+extern crate toml;
+#[cfg(feature = "tracing")]
+extern crate tracing_core;
+#[cfg(feature = "tracing")]
+extern crate tracing_subscriber;
+#[cfg(all(test, feature = "tracing"))]
+extern crate tracing;
+extern crate unicode_width;
+use std::ops::Range;
+
 use ansi_term::{Color, Style};
+use crate::function::parse_function;
+use crate::lex::classify;
+use crate::suggest::closest;
+
+#[macro_use]
+mod macros;
+#[cfg(feature = "css-names")]
+mod css_names;
+
+mod ansi;
+#[cfg(feature = "anstyle")]
+pub mod anstyle_styles;
+mod check;
+#[cfg(feature = "console")]
+pub mod console_styles;
+#[cfg(feature = "crossterm")]
+pub mod crossterm_styles;
+mod downgrade;
+pub mod formats;
+#[cfg(feature = "git-corpus")]
+pub mod git_corpus;
+mod function;
+mod global;
+mod html;
+#[cfg(feature = "i18n")]
+pub mod i18n;
+#[cfg(any(feature = "base16", feature = "json", feature = "toml", feature = "xml"))]
+pub mod import;
+#[cfg(feature = "indicatif")]
+pub mod indicatif_styles;
+mod lex;
+#[cfg(feature = "nu-ansi-term")]
+pub mod nu_ansi_term_styles;
+#[cfg(all(unix, feature = "osc-query"))]
+mod osc_query;
+#[cfg(feature = "owo-colors")]
+pub mod owo_colors_styles;
+mod packed;
+mod paint;
+#[cfg(feature = "rayon")]
+mod parallel;
+mod palette;
+mod pulse;
+mod random;
+#[cfg(feature = "ratatui")]
+pub mod ratatui_styles;
+mod render;
+#[cfg(feature = "serde")]
+pub mod serde_style;
+mod sgr;
+mod slot;
+mod spec;
+mod stack;
+mod style_builder;
+mod style_string;
+mod suggest;
+#[cfg(feature = "termcolor")]
+pub mod termcolor_styles;
+#[cfg(feature = "terminfo")]
+mod terminfo;
+#[cfg(feature = "testing")]
+pub mod testing;
+mod theme;
+#[cfg(feature = "tracing")]
+pub mod tracing_styles;
+mod version;
+mod warning;
+mod width;
+#[cfg(any(feature = "base16", feature = "yaml"))]
+mod yaml;
 
-/// Type for errors returned by the parser.
-#[derive(Debug, PartialEq)]
-pub enum Error {
+pub use ansi::{parse_ansi, AnsiSegments};
+#[cfg(feature = "anstyle")]
+pub use anstyle_styles::parse as parse_anstyle;
+pub use check::{Capabilities, Finding};
+pub use downgrade::{downgrade, downgrade_style, equivalent_at};
+pub use global::{colors_enabled, set_override};
+pub use html::{to_css, to_css_with, to_html};
+pub use lex::{lex, Token, TokenKind};
+#[doc(hidden)]
+pub use macros::{__cformat, __is_valid_spec};
+#[cfg(all(unix, feature = "osc-query"))]
+pub use osc_query::query_palette;
+pub use packed::PackedStyle;
+#[cfg(feature = "rayon")]
+pub use parallel::parse_many_par;
+pub use paint::{paint, paint_if, write_styled, write_styled_io, Painted, Painter};
+pub use palette::Palette;
+pub use pulse::{Easing, Pulse};
+pub use random::{random_style, Constraints, StyleRng};
+pub use render::{prefix_sequence, render, suffix_sequence, transition, transition_spec, Transition};
+pub use sgr::parse_sgr;
+pub use slot::{SlotName, SlotNameError};
+pub use spec::{ColorValue, StyleSpec, StyleSpecBuilder, UnderlineStyle};
+pub use stack::StyleStack;
+pub use style_builder::{parse_into, StyleBuilder};
+pub use style_string::StyleString;
+#[cfg(feature = "terminfo")]
+pub use terminfo::{detect_terminfo_level, terminfo_level};
+pub use theme::{render_template, TemplateError, Theme, ThemeError};
+pub use version::{to_config_string, to_config_string_for, GitVersion};
+pub use warning::{Warning, WarningKind};
+pub use width::{strip_ansi, visible_width};
+
+/// Type for errors returned by the parser: what went wrong, as an
+/// `ErrorKind`, along with the input and the word at fault.
+///
+/// # Examples
+///
+///     use colorparse::ErrorKind;
+///
+///     let err = colorparse::parse("bold blu red").unwrap_err();
+///     assert_eq!(err.kind(), &ErrorKind::UnknownWord);
+///     assert_eq!(err.input(), "bold blu red");
+///     assert_eq!(err.word(), "blu");
+///     assert_eq!(err.span(), 5..8);
+#[derive(Clone, Debug, PartialEq)]
+pub struct Error {
+    kind: ErrorKind,
+    input: String,
+    word: String,
+    span: Range<usize>,
+}
+
+/// The kinds of error the parser reports, as returned by `Error::kind`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ErrorKind {
     /// An extra color appeared after the foreground and background colors.
-    ExtraColor(String, String),
+    ExtraColor,
     /// An unknown word appeared.
-    UnknownWord(String, String),
+    UnknownWord,
+    /// A color appeared that needs a higher color level than the one
+    /// permitted by the `ParseOptions`.
+    UnsupportedColor {
+        /// The level the color requires.
+        required: ColorLevel,
+        /// The level the options allow.
+        allowed: ColorLevel,
+    },
+    /// The input is longer than the `ParseOptions` permit. The error
+    /// carries neither the input nor a word, to avoid copying the input.
+    InputTooLong {
+        /// The length of the input in bytes.
+        len: usize,
+        /// The maximum length in bytes.
+        max: usize,
+    },
+    /// The input has more words than the `ParseOptions` permit; the word
+    /// at fault is the first one past the maximum.
+    TooManyWords {
+        /// The maximum number of words.
+        max: usize,
+    },
+    /// A word is longer than the `ParseOptions` permit.
+    WordTooLong {
+        /// The maximum length of a word in bytes.
+        max: usize,
+    },
+    /// The input to `parse_color` is not a color.
+    InvalidColor,
+    /// A parameter given to `parse_sgr` is not a valid SGR parameter, or
+    /// lacks the parameters it needs.
+    InvalidSgr,
 }
 
 impl std::fmt::Display for Error {
     fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
-        match self {
-            Self::ExtraColor(s, word) => {
+        let (s, word) = (&self.input, &self.word);
+        match self.kind {
+            ErrorKind::ExtraColor => {
                 write!(fmt, "Error parsing style \"{}\": extra color \"{}\"", s, word)
             }
-            Self::UnknownWord(s, word) => {
+            ErrorKind::UnknownWord => {
                 write!(fmt, "Error parsing style \"{}\": unknown word: \"{}\"", s, word)
             }
+            ErrorKind::UnsupportedColor { required, allowed } => {
+                write!(fmt, "Error parsing style \"{}\": color \"{}\" requires {}, but the target only supports {}",
+                       s, word, required, allowed)
+            }
+            ErrorKind::InputTooLong { len, max } => {
+                write!(fmt, "Error parsing style: input is {} bytes long, more than the maximum of {}", len, max)
+            }
+            ErrorKind::TooManyWords { max } => {
+                write!(fmt, "Error parsing style \"{}\": more than the maximum of {} words", s, max)
+            }
+            ErrorKind::WordTooLong { max } => {
+                write!(fmt, "Error parsing style \"{}\": word \"{}\" is longer than the maximum of {} bytes",
+                       s, word, max)
+            }
+            ErrorKind::InvalidColor => write!(fmt, "Error parsing color \"{}\": not a color", s),
+            ErrorKind::InvalidSgr => {
+                write!(fmt, "Error parsing SGR parameters \"{}\": invalid parameter \"{}\"", s, word)
+            }
         }
     }
 }
 
+/// A parse error has no underlying cause, so `source` returns `None`.
 impl std::error::Error for Error {}
 
-fn parse_color(word: &str) -> Result<Option<Color>, ()> {
+impl Error {
+    /// Create an error of `kind` for `word`, found at `span` in `input`.
+    pub(crate) fn new(kind: ErrorKind, input: &str, word: &str, span: Range<usize>) -> Self {
+        Error { kind, input: input.to_string(), word: word.to_string(), span }
+    }
+
+    /// Create an error of `kind` for `word`, a slice of `input`.
+    pub(crate) fn at(kind: ErrorKind, input: &str, word: &str) -> Self {
+        Self::new(kind, input, word, span(input, word))
+    }
+
+    /// Return what went wrong.
+    pub fn kind(&self) -> &ErrorKind {
+        &self.kind
+    }
+
+    /// Return the input that failed to parse, or an empty string for
+    /// `ErrorKind::InputTooLong`.
+    pub fn input(&self) -> &str {
+        &self.input
+    }
+
+    /// Return the word at fault, or an empty string for
+    /// `ErrorKind::InputTooLong`.
+    pub fn word(&self) -> &str {
+        &self.word
+    }
+
+    /// Return the byte range within the input of the word at fault, for
+    /// pointing at it in a diagnostic. For `ErrorKind::InputTooLong`, which
+    /// has no such word, this is the part of the input past the maximum
+    /// length.
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+
+    /// Return the keyword or color name the word at fault was most likely
+    /// meant to be, for an unknown word close enough to one.
+    ///
+    /// # Examples
+    ///
+    ///     let err = colorparse::parse("bol blu").unwrap_err();
+    ///     assert_eq!(err.suggestion().as_deref(), Some("bold"));
+    ///     let err = colorparse::parse("bold noitalc").unwrap_err();
+    ///     assert_eq!(err.suggestion().as_deref(), Some("noitalic"));
+    ///     assert_eq!(colorparse::parse("sparkly").unwrap_err().suggestion(), None);
+    pub fn suggestion(&self) -> Option<String> {
+        if self.kind != ErrorKind::UnknownWord {
+            return None;
+        }
+        let keywords = || ATTRIBUTE_NAMES.iter().chain(&UNDERLINE_STYLE_NAMES).chain(&COLOR_NAMES).chain(&["reset"]).cloned();
+        if let Some(keyword) = closest(&self.word, keywords()) {
+            return Some(keyword.to_string());
+        }
+        let lower = self.word.to_lowercase();
+        let prefix = if lower.starts_with("no-") { "no-" } else if lower.starts_with("no") { "no" } else { return None };
+        closest(&lower[prefix.len()..], ATTRIBUTE_NAMES.iter().cloned()).map(|name| format!("{}{}", prefix, name))
+    }
+
+    /// Return the stable code identifying this kind of error, such as
+    /// `CP0001`. Codes are never reused or reassigned across versions.
+    ///
+    /// The codes of errors, and of the warnings `Warning::code` returns,
+    /// are:
+    ///
+    /// | Code   | Name                  | Category     |
+    /// |--------|-----------------------|--------------|
+    /// | CP0001 | `unknown-word`        | `syntax`     |
+    /// | CP0002 | `extra-color`         | `syntax`     |
+    /// | CP0003 | `unsupported-color`   | `capability` |
+    /// | CP0004 | `input-too-long`      | `limit`      |
+    /// | CP0005 | `too-many-words`      | `limit`      |
+    /// | CP0006 | `word-too-long`       | `limit`      |
+    /// | CP0007 | `invalid-color`       | `syntax`     |
+    /// | CP0008 | `invalid-sgr`         | `syntax`     |
+    /// | CP1001 | `cancelled-attribute` | `suspicious` |
+    /// | CP1002 | `normal-after-color`  | `suspicious` |
+    /// | CP1003 | `same-colors`         | `suspicious` |
+    pub fn code(&self) -> &'static str {
+        match self.kind {
+            ErrorKind::UnknownWord => "CP0001",
+            ErrorKind::ExtraColor => "CP0002",
+            ErrorKind::UnsupportedColor { .. } => "CP0003",
+            ErrorKind::InputTooLong { .. } => "CP0004",
+            ErrorKind::TooManyWords { .. } => "CP0005",
+            ErrorKind::WordTooLong { .. } => "CP0006",
+            ErrorKind::InvalidColor => "CP0007",
+            ErrorKind::InvalidSgr => "CP0008",
+        }
+    }
+
+    /// Return the stable short name of this kind of error, such as
+    /// `unknown-word`.
+    pub fn name(&self) -> &'static str {
+        match self.kind {
+            ErrorKind::UnknownWord => "unknown-word",
+            ErrorKind::ExtraColor => "extra-color",
+            ErrorKind::UnsupportedColor { .. } => "unsupported-color",
+            ErrorKind::InputTooLong { .. } => "input-too-long",
+            ErrorKind::TooManyWords { .. } => "too-many-words",
+            ErrorKind::WordTooLong { .. } => "word-too-long",
+            ErrorKind::InvalidColor => "invalid-color",
+            ErrorKind::InvalidSgr => "invalid-sgr",
+        }
+    }
+
+    /// Return a short, single-line description of this error that stays
+    /// the same across versions, for snapshot tests: its code, name, and
+    /// fields, such as `CP0001 unknown-word input="bold blu" word="blu"`.
+    pub fn to_compact_string(&self) -> String {
+        let (code, name, input, word) = (self.code(), self.name(), &self.input, &self.word);
+        match self.kind {
+            ErrorKind::ExtraColor | ErrorKind::UnknownWord | ErrorKind::InvalidSgr => {
+                format!("{} {} input={:?} word={:?}", code, name, input, word)
+            }
+            ErrorKind::UnsupportedColor { required, allowed } => {
+                format!("{} {} input={:?} word={:?} required={:?} allowed={:?}", code, name, input, word, required, allowed)
+            }
+            ErrorKind::InputTooLong { len, max } => format!("{} {} len={} max={}", code, name, len, max),
+            ErrorKind::TooManyWords { max } => format!("{} {} input={:?} max={}", code, name, input, max),
+            ErrorKind::WordTooLong { max } => {
+                format!("{} {} input={:?} word={:?} max={}", code, name, input, word, max)
+            }
+            ErrorKind::InvalidColor => format!("{} {} input={:?}", code, name, input),
+        }
+    }
+
+    /// Return the category this kind of error belongs to.
+    pub fn category(&self) -> Category {
+        match self.kind {
+            ErrorKind::UnknownWord | ErrorKind::ExtraColor | ErrorKind::InvalidColor | ErrorKind::InvalidSgr => {
+                Category::Syntax
+            }
+            ErrorKind::UnsupportedColor { .. } => Category::Capability,
+            ErrorKind::InputTooLong { .. } | ErrorKind::TooManyWords { .. } | ErrorKind::WordTooLong { .. } => {
+                Category::Limit
+            }
+        }
+    }
+}
+
+/// A group of related diagnostics, as returned by `Error::category` and
+/// `Warning::category`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Category {
+    /// The style string does not follow the style grammar.
+    Syntax,
+    /// The style is well-formed but needs more than the target supports.
+    Capability,
+    /// The style string exceeds a limit set in the `ParseOptions`.
+    Limit,
+    /// The style parses, but probably does not do what was meant.
+    Suspicious,
+}
+
+impl std::fmt::Display for Category {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+        fmt.write_str(match self {
+            Category::Syntax => "syntax",
+            Category::Capability => "capability",
+            Category::Limit => "limit",
+            Category::Suspicious => "suspicious",
+        })
+    }
+}
+
+/// The range of colors a terminal can display, from least to most capable.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ColorLevel {
+    /// The 8 basic ANSI colors and their bright variants.
+    Ansi16,
+    /// The 256-color palette (`Color::Fixed`).
+    Ansi256,
+    /// 24-bit RGB colors (`Color::RGB`).
+    TrueColor,
+}
+
+impl ColorLevel {
+    /// Return the color level needed to display `color`.
+    pub fn of(color: Color) -> Self {
+        match color {
+            Color::Fixed(n) if n < 16 => ColorLevel::Ansi16,
+            Color::Fixed(_) => ColorLevel::Ansi256,
+            Color::RGB(..) => ColorLevel::TrueColor,
+            _ => ColorLevel::Ansi16,
+        }
+    }
+}
+
+impl std::fmt::Display for ColorLevel {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+        fmt.write_str(match self {
+            ColorLevel::Ansi16 => "16 colors",
+            ColorLevel::Ansi256 => "256 colors",
+            ColorLevel::TrueColor => "24-bit color",
+        })
+    }
+}
+
+/// Extension methods for `ansi_term::Style`.
+pub trait StyleExt {
+    /// Return the color level a terminal needs to display this style:
+    /// the higher of the levels required by its foreground and background.
+    ///
+    /// # Examples
+    ///
+    ///     use colorparse::{ColorLevel, StyleExt};
+    ///
+    ///     let style = colorparse::parse("bold red 208").unwrap();
+    ///     assert_eq!(style.requires(), ColorLevel::Ansi256);
+    fn requires(&self) -> ColorLevel;
+
+    /// Return a style derived from this one, for a secondary or highlighted
+    /// state of whatever this style marks.
+    ///
+    /// # Examples
+    ///
+    ///     use colorparse::{StyleExt, Variant};
+    ///
+    ///     let base = colorparse::parse("bold cyan").unwrap();
+    ///     assert_eq!(base.variant(Variant::Dimmed), colorparse::parse("dim cyan").unwrap());
+    ///     assert_eq!(base.variant(Variant::Inverted), colorparse::parse("bold normal cyan").unwrap());
+    fn variant(&self, variant: Variant) -> Style;
+
+    /// Return a short, single-line description of this style that stays
+    /// the same across versions, as `StyleSpec::to_compact_string` does.
+    ///
+    /// # Examples
+    ///
+    ///     use colorparse::StyleExt;
+    ///
+    ///     let style = colorparse::parse("ul bold red 8").unwrap();
+    ///     assert_eq!(style.to_compact_string(), "fg=Red bg=Fixed(8) +bold+ul");
+    fn to_compact_string(&self) -> String;
+}
+
+impl StyleExt for Style {
+    fn requires(&self) -> ColorLevel {
+        let fg = self.foreground.map_or(ColorLevel::Ansi16, ColorLevel::of);
+        let bg = self.background.map_or(ColorLevel::Ansi16, ColorLevel::of);
+        std::cmp::max(fg, bg)
+    }
+
+    fn variant(&self, variant: Variant) -> Style {
+        match variant {
+            Variant::Dimmed => Style { is_bold: false, is_dimmed: true, ..*self },
+            Variant::Emphasized => Style { is_bold: true, is_dimmed: false, ..*self },
+            Variant::Inverted => Style { foreground: self.background, background: self.foreground, ..*self },
+        }
+    }
+
+    fn to_compact_string(&self) -> String {
+        StyleSpec::from(*self).to_compact_string()
+    }
+}
+
+/// A systematic change to a style, applied by `StyleExt::variant`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Variant {
+    /// The same style, dim instead of bold.
+    Dimmed,
+    /// The same style, bold instead of dim.
+    Emphasized,
+    /// The same style with its foreground and background colors swapped.
+    Inverted,
+}
+
+/// A text attribute that a `Style` can enable.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Attribute {
+    /// Bold or increased intensity (`bold`).
+    Bold,
+    /// Faint or decreased intensity (`dim`).
+    Dimmed,
+    /// Italic (`italic`).
+    Italic,
+    /// Underline (`ul`).
+    Underline,
+    /// Slow blink (`blink`).
+    Blink,
+    /// Swapped foreground and background colors (`reverse`).
+    Reverse,
+    /// Concealed text (`hidden`, or `conceal`).
+    Hidden,
+    /// Strikethrough (`strike`).
+    Strikethrough,
+    /// A line above the text (`overline`), which an `ansi_term::Style`
+    /// cannot hold, so only a `StyleSpec` enables it.
+    Overline,
+    /// Rapid blink (`rapidblink`), which, like `Overline`, only a
+    /// `StyleSpec` can enable.
+    RapidBlink,
+}
+
+impl Attribute {
+    /// Every attribute, in the order of their SGR codes, except that the
+    /// attributes an `ansi_term::Style` cannot hold come last.
+    pub const ALL: [Attribute; 10] = [
+        Attribute::Bold,
+        Attribute::Dimmed,
+        Attribute::Italic,
+        Attribute::Underline,
+        Attribute::Blink,
+        Attribute::Reverse,
+        Attribute::Hidden,
+        Attribute::Strikethrough,
+        Attribute::Overline,
+        Attribute::RapidBlink,
+    ];
+
+    /// Return true if `style` enables this attribute, which is never the
+    /// case for `Overline` or `RapidBlink`.
+    pub fn is_set(self, style: &Style) -> bool {
+        match self {
+            Attribute::Bold => style.is_bold,
+            Attribute::Dimmed => style.is_dimmed,
+            Attribute::Italic => style.is_italic,
+            Attribute::Underline => style.is_underline,
+            Attribute::Blink => style.is_blink,
+            Attribute::Reverse => style.is_reverse,
+            Attribute::Hidden => style.is_hidden,
+            Attribute::Strikethrough => style.is_strikethrough,
+            Attribute::Overline | Attribute::RapidBlink => false,
+        }
+    }
+
+    /// Return the Git-syntax keyword for this attribute, such as `ul`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Attribute::Bold => "bold",
+            Attribute::Dimmed => "dim",
+            Attribute::Italic => "italic",
+            Attribute::Underline => "ul",
+            Attribute::Blink => "blink",
+            Attribute::Reverse => "reverse",
+            Attribute::Hidden => "hidden",
+            Attribute::Strikethrough => "strike",
+            Attribute::Overline => "overline",
+            Attribute::RapidBlink => "rapidblink",
+        }
+    }
+
+    /// Return `style` with this attribute enabled, or unchanged for
+    /// `Overline` and `RapidBlink`.
+    pub fn apply(self, style: Style) -> Style {
+        match self {
+            Attribute::Bold => style.bold(),
+            Attribute::Dimmed => style.dimmed(),
+            Attribute::Italic => style.italic(),
+            Attribute::Underline => style.underline(),
+            Attribute::Blink => style.blink(),
+            Attribute::Reverse => style.reverse(),
+            Attribute::Hidden => style.hidden(),
+            Attribute::Strikethrough => style.strikethrough(),
+            Attribute::Overline | Attribute::RapidBlink => style,
+        }
+    }
+}
+
+/// Options controlling how `parse_with` interprets a string.
+///
+/// # Examples
+///
+///     use colorparse::{ColorLevel, ParseOptions};
+///
+///     let options = ParseOptions::new().color_level(ColorLevel::Ansi16);
+///     assert!(colorparse::parse_with("bold red", &options).is_ok());
+///     assert!(colorparse::parse_with("#ff0000", &options).is_err());
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseOptions {
+    color_level: ColorLevel,
+    extensions: u8,
+    keywords: Vec<(String, ColorValue)>,
+    ignore_unknown: bool,
+    max_len: Option<usize>,
+    max_words: Option<usize>,
+    max_word_len: Option<usize>,
+}
+
+impl ParseOptions {
+    /// Create the default options, which match the behavior of `parse`.
+    pub fn new() -> Self {
+        ParseOptions {
+            color_level: ColorLevel::TrueColor,
+            extensions: ALL_EXTENSIONS,
+            keywords: Vec::new(),
+            ignore_unknown: false,
+            max_len: None,
+            max_words: None,
+            max_word_len: None,
+        }
+    }
+
+    /// Reject colors that need more than `level` to display, with
+    /// `ErrorKind::UnsupportedColor`. Use `ColorLevel::Ansi256` to disallow
+    /// 24-bit colors.
+    pub fn color_level(mut self, level: ColorLevel) -> Self {
+        self.color_level = level;
+        self
+    }
+
+    /// Accept all of the extensions to Git's syntax that `parse`
+    /// documents, if `enabled`, as by default. Otherwise, accept only what
+    /// Git itself does, treating each extension as an unknown word. The
+    /// setters below switch each extension on or off separately.
+    ///
+    /// # Examples
+    ///
+    ///     use colorparse::{ErrorKind, ParseOptions};
+    ///
+    ///     let git = ParseOptions::new().extensions(false);
+    ///     assert!(colorparse::parse_with("bold brightred #ff8800", &git).is_ok());
+    ///     let err = colorparse::parse_with("bold #f80", &git).unwrap_err();
+    ///     assert_eq!((err.kind(), err.word()), (&ErrorKind::UnknownWord, "#f80"));
+    ///
+    ///     let hex = git.hex_shorthand(true);
+    ///     assert!(colorparse::parse_with("bold #f80", &hex).is_ok());
+    ///     assert!(colorparse::parse_with("rgb(255, 136, 0)", &hex).is_err());
+    pub fn extensions(mut self, enabled: bool) -> Self {
+        self.extensions = if enabled { ALL_EXTENSIONS } else { 0 };
+        self
+    }
+
+    /// Accept three-digit hex colors such as `#f80`, if `enabled`.
+    pub fn hex_shorthand(self, enabled: bool) -> Self {
+        self.extension(HEX_SHORTHAND, enabled)
+    }
+
+    /// Accept the CSS color names that the `css-names` feature adds, such
+    /// as `rebeccapurple`, if `enabled`.
+    pub fn named_colors(self, enabled: bool) -> Self {
+        self.extension(NAMED_COLORS, enabled)
+    }
+
+    /// Accept the color functions `rgb()`, `hsl()`, and the like, if
+    /// `enabled`.
+    pub fn color_functions(self, enabled: bool) -> Self {
+        self.extension(COLOR_FUNCTIONS, enabled)
+    }
+
+    /// Accept attributes with a value, such as `bold=false`, if `enabled`.
+    pub fn attribute_values(self, enabled: bool) -> Self {
+        self.extension(ATTRIBUTE_VALUES, enabled)
+    }
+
+    /// Accept the attributes Git lacks, `hidden`, `overline`, and
+    /// `rapidblink`, and their negations, if `enabled`.
+    pub fn extended_attributes(self, enabled: bool) -> Self {
+        self.extension(EXTENDED_ATTRIBUTES, enabled)
+    }
+
+    /// Accept underline shapes such as `curly-ul` and underline colors
+    /// such as `ul=red`, if `enabled`.
+    pub fn underline_extensions(self, enabled: bool) -> Self {
+        self.extension(UNDERLINE_EXTENSIONS, enabled)
+    }
+
+    fn extension(mut self, flag: u8, enabled: bool) -> Self {
+        if enabled {
+            self.extensions |= flag;
+        } else {
+            self.extensions &= !flag;
+        }
+        self
+    }
+
+    /// Accept `name` as a color word meaning `color`, both on its own and
+    /// as an underline color after `ul=`. Keywords are matched ignoring
+    /// case, take precedence over the built-in color names, and are
+    /// accepted even with `extensions(false)`.
+    ///
+    /// # Examples
+    ///
+    ///     # extern crate ansi_term;
+    ///     # extern crate colorparse;
+    ///     use ansi_term::Color::{Fixed, Red};
+    ///     use colorparse::ParseOptions;
+    ///
+    ///     # fn main() {
+    ///     let options = ParseOptions::new().keyword("accent", Fixed(208)).keyword("danger", Red);
+    ///     assert_eq!(colorparse::parse_with("bold Accent danger", &options), Ok(Fixed(208).on(Red).bold()));
+    ///     assert!(colorparse::parse("accent").is_err());
+    ///     # }
+    pub fn keyword<C: Into<ColorValue>>(mut self, name: &str, color: C) -> Self {
+        let name = name.to_lowercase();
+        let color = color.into();
+        match self.keywords.iter_mut().find(|(n, _)| *n == name) {
+            Some(keyword) => keyword.1 = color,
+            None => self.keywords.push((name, color)),
+        }
+        self
+    }
+
+    /// Return the color of the keyword `word`, in lowercase, if any.
+    fn keyword_color(&self, word: &str) -> Option<Color> {
+        self.keywords.iter().find(|(name, _)| name == word).map(|&(_, color)| color.into())
+    }
+
+    /// Skip unknown words, rather than rejecting them with
+    /// `ErrorKind::UnknownWord`, if `ignore`.
+    ///
+    /// # Examples
+    ///
+    ///     use colorparse::ParseOptions;
+    ///
+    ///     let options = ParseOptions::new().ignore_unknown(true);
+    ///     assert_eq!(colorparse::parse_with("bold sparkly red", &options), colorparse::parse("bold red"));
+    pub fn ignore_unknown(mut self, ignore: bool) -> Self {
+        self.ignore_unknown = ignore;
+        self
+    }
+
+    /// Reject input longer than `max` bytes with `ErrorKind::InputTooLong`,
+    /// before looking at any of it.
+    ///
+    /// By default, input of any length is accepted. When parsing style
+    /// strings from untrusted sources, set this limit along with
+    /// `max_words` and `max_word_len` to bound the work and memory spent
+    /// on each one.
+    ///
+    /// # Examples
+    ///
+    ///     use colorparse::{ErrorKind, ParseOptions};
+    ///
+    ///     let options = ParseOptions::new().max_len(16).max_words(3).max_word_len(8);
+    ///     assert!(colorparse::parse_with("bold red blue", &options).is_ok());
+    ///     let kind = |s| *colorparse::parse_with(s, &options).unwrap_err().kind();
+    ///     assert_eq!(kind("bold red blue ul blink"), ErrorKind::InputTooLong { len: 22, max: 16 });
+    ///     assert_eq!(kind("ul bold red 4"), ErrorKind::TooManyWords { max: 3 });
+    ///     assert_eq!(kind("#ff0000ff"), ErrorKind::WordTooLong { max: 8 });
+    pub fn max_len(mut self, max: usize) -> Self {
+        self.max_len = Some(max);
+        self
+    }
+
+    /// Reject input with more than `max` words with
+    /// `ErrorKind::TooManyWords`.
+    pub fn max_words(mut self, max: usize) -> Self {
+        self.max_words = Some(max);
+        self
+    }
+
+    /// Reject input containing a word longer than `max` bytes with
+    /// `ErrorKind::WordTooLong`.
+    pub fn max_word_len(mut self, max: usize) -> Self {
+        self.max_word_len = Some(max);
+        self
+    }
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The attribute names, which may also be negated with a `no` or `no-`
+/// prefix, or given a value with an `=` suffix.
+pub(crate) const ATTRIBUTE_NAMES: [&str; 11] = [
+    "bold", "dim", "ul", "blink", "reverse", "italic", "strike", "hidden", "conceal", "overline", "rapidblink",
+];
+
+/// The keywords that enable underline with a shape other than a single
+/// straight line.
+pub(crate) const UNDERLINE_STYLE_NAMES: [&str; 4] = ["double-ul", "curly-ul", "dotted-ul", "dashed-ul"];
+
+/// The values accepted after `attr=`.
+pub(crate) const BOOL_NAMES: [&str; 4] = ["true", "false", "on", "off"];
+
+/// The named colors, including the names for the default color.
+pub(crate) const COLOR_NAMES: [&str; 19] = [
+    "normal", "-1", "default", "black", "red", "green", "yellow", "blue", "magenta", "cyan", "white",
+    "brightblack", "brightred", "brightgreen", "brightyellow", "brightblue", "brightmagenta", "brightcyan",
+    "brightwhite",
+];
+
+/// Parse a lowercase color word, returning `None` for the default color.
+pub(crate) fn color_word(word: &str) -> Result<Option<Color>, ()> {
     let color = match word {
         "normal" => None,
         "-1" => None,
@@ -51,14 +844,38 @@ fn parse_color(word: &str) -> Result<Option<Color>, ()> {
         "magenta" => Some(Color::Purple),
         "cyan" => Some(Color::Cyan),
         "white" => Some(Color::White),
+        "brightblack" => Some(Color::Fixed(8)),
+        "brightred" => Some(Color::Fixed(9)),
+        "brightgreen" => Some(Color::Fixed(10)),
+        "brightyellow" => Some(Color::Fixed(11)),
+        "brightblue" => Some(Color::Fixed(12)),
+        "brightmagenta" => Some(Color::Fixed(13)),
+        "brightcyan" => Some(Color::Fixed(14)),
+        "brightwhite" => Some(Color::Fixed(15)),
         _ => {
-            if word.starts_with('#') && word.len() == 7 {
+            if let Some(color) = parse_function(word) {
+                return color.map(Some);
+            }
+            #[cfg(feature = "css-names")]
+            {
+                if let Some(color) = css_names::css_color(word) {
+                    return Ok(Some(color));
+                }
+            }
+            if !word.is_ascii() {
+                return Err(());
+            } else if word.starts_with('#') && word.len() == 7 {
                 if let (Ok(r), Ok(g), Ok(b)) = (u8::from_str_radix(&word[1..3], 16),
                                                 u8::from_str_radix(&word[3..5], 16),
                                                 u8::from_str_radix(&word[5..7], 16)) {
                     return Ok(Some(Color::RGB(r, g, b)))
                 }
-            } else if let Ok(n) = u8::from_str_radix(word, 10) {
+            } else if word.starts_with('#') && word.len() == 4 {
+                let nibble = |i: usize| u8::from_str_radix(&word[i..i + 1], 16).map(|n| n * 0x11);
+                if let (Ok(r), Ok(g), Ok(b)) = (nibble(1), nibble(2), nibble(3)) {
+                    return Ok(Some(Color::RGB(r, g, b)))
+                }
+            } else if let Ok(n) = word.parse::<u8>() {
                 return Ok(Some(Color::Fixed(n)));
             }
             return Err(());
@@ -67,66 +884,406 @@ fn parse_color(word: &str) -> Result<Option<Color>, ()> {
     Ok(color)
 }
 
+/// The flags for each extension to Git's syntax in `ParseOptions`.
+const HEX_SHORTHAND: u8 = 1 << 0;
+const NAMED_COLORS: u8 = 1 << 1;
+const COLOR_FUNCTIONS: u8 = 1 << 2;
+const ATTRIBUTE_VALUES: u8 = 1 << 3;
+const EXTENDED_ATTRIBUTES: u8 = 1 << 4;
+const UNDERLINE_EXTENSIONS: u8 = 1 << 5;
+const ALL_EXTENSIONS: u8 = (1 << 6) - 1;
+
+/// Return the flag of the extension to Git's syntax that `word`, in
+/// lowercase, needs, or 0 if Git accepts it as it is.
+fn extension(word: &str) -> u8 {
+    #[cfg(feature = "css-names")]
+    {
+        if css_names::css_color(word).is_some() {
+            return NAMED_COLORS;
+        }
+    }
+    let name = word.strip_prefix("no-").or_else(|| word.strip_prefix("no")).unwrap_or(word);
+    let extended = |attribute| {
+        matches!(attribute, Some(Attribute::Hidden) | Some(Attribute::Overline) | Some(Attribute::RapidBlink))
+    };
+    if word.contains('(') {
+        COLOR_FUNCTIONS
+    } else if UNDERLINE_STYLE_NAMES.contains(&word) || matches!(classify(word), TokenKind::UnderlineColor(_)) {
+        UNDERLINE_EXTENSIONS
+    } else if word.contains('=') {
+        ATTRIBUTE_VALUES
+    } else if word.starts_with('#') && word.len() == 4 {
+        HEX_SHORTHAND
+    } else if extended(attribute_named(word)) || extended(attribute_named(name)) {
+        EXTENDED_ATTRIBUTES
+    } else {
+        0
+    }
+}
+
+fn parse_bool(value: &str) -> Option<bool> {
+    match value {
+        "true" | "on" => Some(true),
+        "false" | "off" => Some(false),
+        _ => None,
+    }
+}
+
+/// Return true if `c` separates the words of a style spec.
+pub(crate) const fn is_separator(c: char) -> bool {
+    c.is_whitespace() || matches!(c, '\u{200B}' | '\u{2060}' | '\u{FEFF}')
+}
+
+/// Split a style spec into its words, keeping the arguments of a color
+/// function such as `rgb(1, 2, 3)` together with its name.
+pub(crate) fn words(s: &str) -> Words<'_> {
+    Words { rest: s }
+}
+
+/// An iterator over the words of a style spec, returned by `words`.
+pub(crate) struct Words<'a> {
+    rest: &'a str,
+}
+
+impl<'a> Iterator for Words<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<&'a str> {
+        let start = self.rest.find(|c| !is_separator(c))?;
+        let rest = &self.rest[start..];
+        let mut depth = 0usize;
+        let end = rest.char_indices().find(|&(_, c)| {
+            match c {
+                '(' => depth += 1,
+                ')' => depth = depth.saturating_sub(1),
+                c => return depth == 0 && is_separator(c),
+            }
+            false
+        }).map_or(rest.len(), |(i, _)| i);
+        self.rest = &rest[end..];
+        Some(&rest[..end])
+    }
+}
+
+/// Parse a single color word, in any of the forms `parse` accepts for a
+/// color, into an `ansi_term::Color`, or `None` for no color (`normal` or
+/// `-1`) or the terminal's default color (`default`). Surrounding
+/// whitespace is ignored.
+///
+/// # Examples
+///
+///     # extern crate ansi_term;
+///     # extern crate colorparse;
+///     use ansi_term::Color::{Fixed, Red, RGB};
+///     use colorparse::{parse_color, ErrorKind};
+///
+///     # fn main() {
+///     assert_eq!(parse_color("Red"), Ok(Some(Red)));
+///     assert_eq!(parse_color(" 208 "), Ok(Some(Fixed(208))));
+///     assert_eq!(parse_color("rgb(255, 136, 0)"), Ok(Some(RGB(255, 136, 0))));
+///     assert_eq!(parse_color("normal"), Ok(None));
+///     assert_eq!(parse_color("bold").unwrap_err().kind(), &ErrorKind::InvalidColor);
+///     # }
+pub fn parse_color(s: &str) -> Result<Option<Color>, Error> {
+    let word = s.trim_matches(is_separator);
+    if word.eq_ignore_ascii_case("default") {
+        return Ok(None);
+    }
+    color_word(&word.to_lowercase()).map_err(|()| Error::at(ErrorKind::InvalidColor, s, word))
+}
+
+/// Return the byte range of `word`, a slice of `s`, within `s`.
+pub(crate) fn span(s: &str, word: &str) -> Range<usize> {
+    let start = word.as_ptr() as usize - s.as_ptr() as usize;
+    start..start + word.len()
+}
+
 /// Parse a string in Git's color configuration syntax into an
 /// `ansi_term::Style`.
+///
+/// As an extension to Git's syntax, attributes may also be written as
+/// `attr=true`, `attr=false`, `attr=on`, or `attr=off` (for instance,
+/// `bold=false` is equivalent to `nobold`), and 24-bit colors may be
+/// written with three hexadecimal digits in the style of CSS, each digit
+/// doubled to give the full value (`#f80` is `#ff8800`), or as
+/// `rgb(r, g, b)` with decimal components from 0 to 255. They may also be
+/// written as `hsl(h, s%, l%)` or `hsv(h, s%, v%)`, with the hue in
+/// degrees and the saturation, lightness, or value as percentages, which
+/// convert to the nearest 24-bit color (`hsl(220, 80%, 50%)` is
+/// `#1a5de6`).
+///
+/// With the `css-names` feature, colors may also be given by their CSS
+/// names, such as `orange`, `teal`, or `slategray`, for the corresponding
+/// 24-bit colors. The eight names Git already uses (`black` through
+/// `white`) keep their meaning as the basic colors.
+///
+/// Text may be concealed with `hidden`, or its alias `conceal`, which
+/// Git itself does not accept; `nohidden` clears it.
+///
+/// The keyword `reset` discards every color and attribute before it, so
+/// `bold red reset blue` is just `blue`.
+///
+/// The color `default`, added in Git 2.26, stands for the terminal's
+/// default color. An `ansi_term::Style` cannot tell it from an unset color,
+/// so `parse` treats it as `normal`; `parse_spec` keeps it apart.
+///
+/// The bright color names `brightblack` through `brightwhite`, added in
+/// Git 2.26, map to colors 8 through 15 (`Color::Fixed(8)` through
+/// `Color::Fixed(15)`).
+///
+/// Words may be separated by any Unicode whitespace, such as the
+/// non-breaking and full-width spaces often found in text pasted from web
+/// pages, and by the invisible zero-width space, word joiner, and byte
+/// order mark, none of which can appear inside a word.
 pub fn parse(s: &str) -> Result<Style, Error> {
-    let mut style = Style::new();
+    parse_with(s, &ParseOptions::new())
+}
+
+/// Parse a string like `parse`, with behavior controlled by `options`.
+pub fn parse_with(s: &str, options: &ParseOptions) -> Result<Style, Error> {
+    parse_spec_with(s, options).map(Style::from)
+}
+
+/// Parse a string in Git's color configuration syntax into a `StyleSpec`,
+/// which keeps what an `ansi_term::Style` cannot hold.
+///
+/// Besides the syntax `parse` accepts, the spec may set the underline color
+/// with `ul=<color>`, such as `ul=red` or `ul=#ff0000`, which terminals
+/// supporting SGR 58 use for the underline; `ul=normal` or `ul=default`
+/// leaves the default. The keyword `overline` draws a line above the text
+/// (SGR 53), and may be negated like any other attribute; `parse` accepts
+/// it but drops it, since an `ansi_term::Style` has nowhere to keep it.
+/// Likewise, `rapidblink` (SGR 6) blinks faster than `blink` (SGR 5); the
+/// two are kept apart, and a spec may enable both. The shape of the
+/// underline may be chosen with `double-ul`, `curly-ul`, `dotted-ul`, or
+/// `dashed-ul`, each of which enables underline; `noul` clears it whatever
+/// its shape. Attributes negated with `no`, and a `reset`, are recorded in
+/// the spec. A spec keeps only the net effect of an attribute both set and
+/// cleared, the later word winning, so `bold nobold` records bold as
+/// cleared, where Git writes the codes for both.
+///
+/// # Examples
+///
+///     # extern crate ansi_term;
+///     # extern crate colorparse;
+///     use ansi_term::Color::Red;
+///     use colorparse::{transition_spec, StyleSpec};
+///
+///     # fn main() {
+///     let spec = colorparse::parse_spec("ul ul=red").unwrap();
+///     assert_eq!(spec.underline_color(), Some(Red));
+///     assert_eq!(spec.to_string(), "ul ul=red");
+///     assert_eq!(transition_spec(&StyleSpec::new(), &spec).to_string(), "\x1b[4;58;5;1m");
+///     # }
+pub fn parse_spec(s: &str) -> Result<StyleSpec, Error> {
+    parse_spec_with(s, &ParseOptions::new())
+}
+
+/// Parse a string like `parse_spec`, and write it back in a canonical
+/// form, so that two strings normalize the same exactly when they parse
+/// to the same spec.
+///
+/// The canonical form is in lowercase, with `reset` first if the spec
+/// starts with one, then the foreground and background colors, then the
+/// attributes in the order of `Attribute::ALL`, then the cleared
+/// attributes in the same order, and last the underline color. Colors 0
+/// through 7 are written by name, other palette colors by number, and
+/// 24-bit colors as `#rrggbb`; words that cancel out or repeat are
+/// dropped. A spec that sets nothing normalizes
+/// to `normal`.
+///
+/// # Examples
+///
+///     use colorparse::normalize;
+///
+///     assert_eq!(normalize("UL  Bold #F80 blue").unwrap(), "#ff8800 blue bold ul");
+///     assert_eq!(normalize("bold=off dim nodim bold").unwrap(), "bold nodim");
+///     assert_eq!(normalize("rgb(0, 0, 255)").unwrap(), normalize("#0000ff").unwrap());
+///     assert_eq!(normalize("").unwrap(), "normal");
+///     assert!(normalize("bold sparkly").is_err());
+pub fn normalize(s: &str) -> Result<String, Error> {
+    parse_spec(s).map(|spec| spec.normalized())
+}
+
+/// Return the attribute with the keyword `name`, or with the alias
+/// `conceal` for `hidden`.
+fn attribute_named(name: &str) -> Option<Attribute> {
+    let name = if name == "conceal" { "hidden" } else { name };
+    Attribute::ALL.iter().cloned().find(|a| a.name() == name && ATTRIBUTE_NAMES.contains(&name))
+}
+
+/// Parse a string like `parse_spec`, with behavior controlled by `options`.
+pub fn parse_spec_with(s: &str, options: &ParseOptions) -> Result<StyleSpec, Error> {
+    parse_recovering(s, options, skip_unknown(options), &mut Vec::new())
+}
+
+/// Parse a string like `parse_with`, also returning a warning for each
+/// word that parses but is probably a mistake: a word that overrides an
+/// attribute set earlier, as in `bold red nobold`; `normal` after the
+/// foreground color; and a background the same as the foreground.
+///
+/// # Examples
+///
+///     use colorparse::{parse, parse_with_diagnostics, Attribute, ParseOptions, WarningKind};
+///
+///     let (style, warnings) = parse_with_diagnostics("bold red nobold red", &ParseOptions::new()).unwrap();
+///     assert_eq!(style, parse("red red").unwrap());
+///     let kinds: Vec<_> = warnings.iter().map(|w| (w.kind(), w.word())).collect();
+///     assert_eq!(kinds, [(&WarningKind::Cancelled(Attribute::Bold), "nobold"), (&WarningKind::SameColors, "red")]);
+///     assert_eq!(warnings[1].span(), 16..19);
+pub fn parse_with_diagnostics(s: &str, options: &ParseOptions) -> Result<(Style, Vec<Warning>), Error> {
+    let mut warnings = Vec::new();
+    let spec = parse_recovering(s, options, skip_unknown(options), &mut warnings)?;
+    Ok((spec.into(), warnings))
+}
+
+/// Parse a string like `parse`, but never fail: apply every word that
+/// parses, skip the rest, and return an error for each word skipped.
+///
+/// A word is skipped if `parse` would reject it as unknown, or as a color
+/// after the foreground and background are already set.
+///
+/// # Examples
+///
+///     use colorparse::{parse, parse_lossy, ErrorKind};
+///
+///     let (style, errors) = parse_lossy("bold sparkly red blue green");
+///     assert_eq!(style, parse("bold red blue").unwrap());
+///     let skipped: Vec<_> = errors.iter().map(|e| (e.kind(), e.word())).collect();
+///     assert_eq!(skipped, [(&ErrorKind::UnknownWord, "sparkly"), (&ErrorKind::ExtraColor, "green")]);
+pub fn parse_lossy(s: &str) -> (Style, Vec<Error>) {
+    let mut errors = Vec::new();
+    // Without limits on the input, only `recover` can stop the parse.
+    let spec = parse_recovering(s, &ParseOptions::new(), |error| {
+        errors.push(error);
+        Ok(())
+    }, &mut Vec::new());
+    (spec.map(Style::from).unwrap_or_default(), errors)
+}
+
+/// Return the `recover` function for `parse_recovering` that skips
+/// unknown words if `options` say so, and stops at any other error.
+fn skip_unknown(options: &ParseOptions) -> impl Fn(Error) -> Result<(), Error> + '_ {
+    move |error| match error {
+        Error { kind: ErrorKind::UnknownWord, .. } if options.ignore_unknown => Ok(()),
+        error => Err(error),
+    }
+}
+
+/// Parse a string into a spec, passing each error about a single word to
+/// `recover`, which either returns an error to stop parsing, or `Ok` to
+/// skip the word and carry on.
+///
+/// Along the way, push a warning to `warnings` for each suspicious word.
+fn parse_recovering<F>(s: &str, options: &ParseOptions, mut recover: F, warnings: &mut Vec<Warning>)
+                       -> Result<StyleSpec, Error>
+where
+    F: FnMut(Error) -> Result<(), Error>,
+{
+    let mut builder = StyleSpec::builder();
     let mut colors = 0;
-    let mut bold = false;
-    let mut dim = false;
-    let mut ul = false;
-    let mut blink = false;
-    let mut reverse = false;
-    let mut italic = false;
-    let mut strike = false;
-    for word in s.split_whitespace() {
-        match word.to_lowercase().as_ref() {
-            "nobold"|"no-bold" => { bold = false; }
-            "bold" => { bold = true; }
-            "nodim"|"no-dim" => { dim = false; }
-            "dim" => { dim = true; }
-            "noul"|"no-ul" => { ul = false; }
-            "ul" => { ul = true; }
-            "noblink"|"no-blink" => { blink = false; }
-            "blink" => { blink = true; }
-            "noreverse"|"no-reverse" => { reverse = false; }
-            "reverse" => { reverse = true; }
-            "noitalic"|"no-italic" => { italic = false; }
-            "italic" => { italic = true; }
-            "nostrike"|"no-strike" => { strike = false; }
-            "strike" => { strike = true; }
-            w => {
-                if let Ok(color) = parse_color(w) {
-                    if colors == 2 {
-                        return Err(Error::ExtraColor(s.to_string(), word.to_string()));
-                    } else if let Some(color) = color {
-                        if colors == 0 {
-                            style = style.fg(color);
-                        } else if colors == 1 {
-                            style = style.on(color);
+    let mut background = None;
+    if let Some(max) = options.max_len {
+        if s.len() > max {
+            return Err(Error::new(ErrorKind::InputTooLong { len: s.len(), max }, "", "", max..s.len()));
+        }
+    }
+    let check_level = |word: &str, color: Color| {
+        let required = ColorLevel::of(color);
+        if required > options.color_level {
+            return Err(Error::at(ErrorKind::UnsupportedColor { required, allowed: options.color_level }, s, word));
+        }
+        Ok(color)
+    };
+    for (i, word) in words(s).enumerate() {
+        if let Some(max) = options.max_words {
+            if i >= max {
+                return Err(Error::at(ErrorKind::TooManyWords { max }, s, word));
+            }
+        }
+        if let Some(max) = options.max_word_len {
+            if word.len() > max {
+                return Err(Error::at(ErrorKind::WordTooLong { max }, s, word));
+            }
+        }
+        let lower = word.to_lowercase();
+        let unknown = || Error::at(ErrorKind::UnknownWord, s, word);
+        let keyword = match lower.strip_prefix("ul=") {
+            Some(value) => options.keyword_color(value).map(|color| TokenKind::UnderlineColor(Some(color))),
+            None => options.keyword_color(&lower).map(|color| TokenKind::Color(Some(color))),
+        };
+        let needed = extension(&lower);
+        if keyword.is_none() && options.extensions & needed != needed {
+            recover(unknown())?;
+            continue;
+        }
+        let mut set = |builder: StyleSpecBuilder, attribute, on: bool| {
+            if (on && builder.spec.clears(attribute)) || (!on && builder.spec.has(attribute)) {
+                warnings.push(Warning::at(WarningKind::Cancelled(attribute), s, word));
+            }
+            if on { builder.attribute(attribute) } else { builder.no(attribute) }
+        };
+        match keyword.unwrap_or_else(|| classify(&lower)) {
+            TokenKind::Reset => {
+                builder = builder.reset();
+                colors = 0;
+                background = None;
+            }
+            TokenKind::Attribute(attribute) => builder = set(builder, attribute, true),
+            TokenKind::NegatedAttribute(attribute) => builder = set(builder, attribute, false),
+            TokenKind::UnderlineStyle(style) => builder = builder.underline_style(style),
+            TokenKind::UnderlineColor(color) => match color.map(|color| check_level(word, color)) {
+                Some(Err(error)) => recover(error)?,
+                color => builder.spec.underline_color = color.and_then(Result::ok),
+            },
+            kind @ TokenKind::Color(_) | kind @ TokenKind::DefaultColor => {
+                if colors == 2 {
+                    recover(Error::at(ErrorKind::ExtraColor, s, word))?;
+                    continue;
+                }
+                let default = kind == TokenKind::DefaultColor;
+                let color = match kind {
+                    TokenKind::Color(Some(color)) => match check_level(word, color) {
+                        Ok(color) => Some(color),
+                        Err(error) => {
+                            recover(error)?;
+                            continue;
+                        }
+                    },
+                    _ => None,
+                };
+                match colors {
+                    0 => {
+                        builder.spec.foreground = color;
+                        builder.spec.default_foreground = default;
+                    }
+                    _ => {
+                        if color.is_none() && !default && builder.spec.foreground.is_some() {
+                            warnings.push(Warning::at(WarningKind::NormalAfterColor, s, word));
                         }
+                        builder.spec.background = color;
+                        builder.spec.default_background = default;
+                        background = Some(word);
                     }
-                    colors += 1;
-                } else {
-                    return Err(Error::UnknownWord(s.to_string(), word.to_string()));
                 }
+                colors += 1;
             }
+            TokenKind::Unknown => recover(unknown())?,
+        }
+    }
+    if let Some(word) = background {
+        let (fg, bg) = (builder.spec.fg(), builder.spec.bg());
+        if bg.is_some() && fg == bg {
+            warnings.push(Warning::at(WarningKind::SameColors, s, word));
         }
     }
-    if bold { style = style.bold(); }
-    if dim { style = style.dimmed(); }
-    if ul { style = style.underline(); }
-    if blink { style = style.blink(); }
-    if reverse { style = style.reverse(); }
-    if italic { style = style.italic(); }
-    if strike { style = style.strikethrough(); }
-    Ok(style)
+    Ok(builder.build())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use super::Error::*;
+    use super::ErrorKind::*;
     use ansi_term::Color::*;
     use ansi_term::Style;
 
@@ -135,6 +1292,7 @@ mod tests {
         macro_rules! test {
             ($s:expr => $style:expr) => {
                 assert_eq!(parse($s), Ok($style));
+                assert!(__is_valid_spec($s), "{:?}", $s);
             };
         }
 
@@ -161,6 +1319,17 @@ mod tests {
         test!("255 -1" => Fixed(255).normal());
         test!("#000000" => RGB(0,0,0).normal());
         test!("#204060" => RGB(0x20,0x40,0x60).normal());
+        test!("#fff ul" => RGB(0xff,0xff,0xff).underline());
+        test!("#F80 #000" => RGB(0xff,0x88,0).on(RGB(0,0,0)));
+        test!("rgb(255, 128, 0) bold" => RGB(255,128,0).bold());
+        test!("RGB(1,2,3) rgb( 4 ,\t5, 6 )" => RGB(1,2,3).on(RGB(4,5,6)));
+        test!("hsl(220, 80%, 50%) HSV(0, 0%, 100%)" => RGB(26,93,230).on(RGB(255,255,255)));
+        test!("brightred" => Fixed(9).normal());
+        test!("reset" => Style::new());
+        test!("bold red blue reset" => Style::new());
+        test!("bold red blue RESET ul green" => Green.underline());
+        test!("red blue reset green yellow" => Green.on(Yellow));
+        test!("BrightBlack brightwhite" => Fixed(8).on(Fixed(15)));
 
         test!("bold cyan white" => Cyan.on(White).bold());
         test!("bold cyan nobold white" => Cyan.on(White));
@@ -168,15 +1337,83 @@ mod tests {
         test!("bold cyan ul white dim" => Cyan.on(White).bold().underline().dimmed());
         test!("ul cyan white no-ul" => Cyan.on(White));
         test!("italic cyan white" => Cyan.on(White).italic());
+        test!("italic cyan noitalic" => Cyan.normal());
+        test!("noitalic italic" => Style::new().italic());
         test!("strike cyan white" => Cyan.on(White).strikethrough());
+        test!("strike cyan no-strike" => Cyan.normal());
+        test!("nostrike strike" => Style::new().strikethrough());
         test!("blink #050505 white" => RGB(5,5,5).on(White).blink());
+        test!("hidden red" => Red.hidden());
+        test!("CONCEAL nohidden conceal=on" => Style::new().hidden());
+        test!("hidden no-conceal" => Style::new());
+
+        test!("bold=true red" => Red.bold());
+        test!("bold=on red" => Red.bold());
+        test!("bold red bold=false" => Red.normal());
+        test!("ul=off red ul" => Red.underline());
+        test!("BOLD=TRUE Italic=On" => Style::new().bold().italic());
+        test!("strike=on dim=true blink=off" => Style::new().strikethrough().dimmed());
+    }
+
+    #[test]
+    fn test_parse_spec() {
+        let spec = parse_spec("bold nodim red ul=#ff0000").unwrap();
+        assert_eq!(spec, StyleSpec::builder().bold().no(Attribute::Dimmed).fg(Red).underline_color(RGB(255, 0, 0)).build());
+        assert_eq!(parse_spec("ul=blue ul=normal"), Ok(StyleSpec::new()));
+        assert_eq!(parse_spec("ul=blue ul=default"), Ok(StyleSpec::new()));
+        assert_eq!(parse_spec("overline red"), Ok(StyleSpec::builder().overline().fg(Red).build()));
+        assert_eq!(parse_spec("overline no-overline").unwrap().to_string(), "nooverline");
+        assert_eq!(parse_spec("overline=on").unwrap().to_compact_string(), "+overline");
+        assert_eq!(parse("bold overline"), Ok(Style::new().bold()));
+        let blink = parse_spec("rapidblink blink").unwrap();
+        assert!(blink.has(Attribute::RapidBlink) && blink.has(Attribute::Blink));
+        assert_eq!(blink.to_string(), "blink rapidblink");
+        assert_eq!(parse_spec("blink rapidblink norapidblink").unwrap().to_string(), "blink norapidblink");
+        assert_eq!(parse("rapidblink"), Ok(Style::new()));
+        let default = parse_spec("default default no-reverse bold").unwrap();
+        assert_eq!(default, StyleSpec::builder().default_fg().default_bg().no(Attribute::Reverse).bold().build());
+        assert_eq!(default.to_string(), "bold noreverse default default");
+        assert_eq!(parse_spec("normal DEFAULT").unwrap().to_string(), "normal default");
+        assert_eq!(parse("red default"), Ok(Red.normal()));
+        assert!(parse("red default blue").is_err());
+        assert_eq!(parse_color("Default"), Ok(None));
+        assert_eq!(parse_spec("Curly-UL ul=red").unwrap().to_string(), "curly-ul ul=red");
+        assert_eq!(parse_spec("double-ul").unwrap().underline_style(), UnderlineStyle::Double);
+        assert_eq!(parse_spec("dotted-ul ul").unwrap().underline_style(), UnderlineStyle::Single);
+        assert_eq!(parse_spec("dashed-ul noul"), Ok(StyleSpec::builder().no(Attribute::Underline).build()));
+        assert_eq!(parse("dashed-ul red"), Ok(Red.underline()));
+        assert_eq!(parse_spec("nocurly-ul"), Err(Error::new(UnknownWord, "nocurly-ul", "nocurly-ul", 0..10)));
+        assert_eq!(parse_spec("ul=208 UL=On").unwrap().to_string(), "ul ul=208");
+        assert_eq!(parse_spec("ul=off").unwrap().to_string(), "noul");
+        assert_eq!(parse("ul ul=red"), Ok(Style::new().underline()));
+        assert_eq!(parse_spec("bold=red"), Err(Error::new(UnknownWord, "bold=red", "bold=red", 0..8)));
+        assert_eq!(parse_spec("ul=blu"), Err(Error::new(UnknownWord, "ul=blu", "ul=blu", 0..6)));
+        assert_eq!(parse_spec("red blue ul=green").unwrap().underline_color(), Some(Green));
+        assert_eq!(parse_spec_with("ul=#ff0000", &ParseOptions::new().color_level(ColorLevel::Ansi256)),
+                   Err(Error::new(UnsupportedColor { required: ColorLevel::TrueColor, allowed: ColorLevel::Ansi256 },
+                                  "ul=#ff0000", "ul=#ff0000", 0..10)));
+        for s in &["bold red", "italic nobold 208 #123456", "ul ul=red", "reset dim", "normal blue ul=#abcdef"] {
+            assert_eq!(parse_spec(s).unwrap().to_string(), *s);
+        }
+    }
+
+    #[test]
+    fn test_negation() {
+        for name in &ATTRIBUTE_NAMES {
+            for prefix in &["no", "no-", "NO-"] {
+                let s = format!("red {} {}{}", name, prefix, name);
+                assert_eq!(parse(&s), Ok(Red.normal()), "{:?}", s);
+            }
+        }
     }
 
     #[test]
     fn test_parse_style_err() {
         macro_rules! test {
-            ($s:expr => $err:ident $word:expr) => {
-                assert_eq!(parse($s), Err($err($s.to_string(), $word.to_string())));
+            ($s:expr => $kind:ident $word:expr) => {
+                let start = $s.find($word).unwrap();
+                assert_eq!(parse($s), Err(Error::new($kind, $s, $word, start..start + $word.len())));
+                assert!(!__is_valid_spec($s), "{:?}", $s);
             };
         }
 
@@ -199,6 +1436,8 @@ mod tests {
         test!("123-1" => UnknownWord "123-1");
         test!("blue1" => UnknownWord "blue1");
         test!("blue-1" => UnknownWord "blue-1");
+        test!("bright" => UnknownWord "bright");
+        test!("bright-red" => UnknownWord "bright-red");
         test!("no" => UnknownWord "no");
         test!("nou" => UnknownWord "nou");
         test!("noblue" => UnknownWord "noblue");
@@ -208,10 +1447,273 @@ mod tests {
         test!("no-green" => UnknownWord "no-green");
         test!("no-#123456" => UnknownWord "no-#123456");
         test!("#" => UnknownWord "#");
+        test!("#12" => UnknownWord "#12");
+        test!("#1234" => UnknownWord "#1234");
+        test!("#fgf" => UnknownWord "#fgf");
+        test!("rgb(1, 2)" => UnknownWord "rgb(1, 2)");
+        test!("rgb(1, 2, 256)" => UnknownWord "rgb(1, 2, 256)");
+        test!("rgb(1, 2, 3, 4)" => UnknownWord "rgb(1, 2, 3, 4)");
+        test!("rgb(1, 2, 3" => UnknownWord "rgb(1, 2, 3");
+        test!("rgb(1 2 3)" => UnknownWord "rgb(1 2 3)");
+        test!("hsl(0, 200%, 50%)" => UnknownWord "hsl(0, 200%, 50%)");
+        test!("#\u{e9}1" => UnknownWord "#\u{e9}1");
+        test!("#a\u{e9}aaa" => UnknownWord "#a\u{e9}aaa");
         test!("#12345" => UnknownWord "#12345");
         test!("#1234567" => UnknownWord "#1234567");
         test!("#bcdefg" => UnknownWord "#bcdefg");
         test!("#blue" => UnknownWord "#blue");
         test!("blue#123456" => UnknownWord "blue#123456");
+        test!("bold=" => UnknownWord "bold=");
+        test!("bold=yes" => UnknownWord "bold=yes");
+        test!("=true" => UnknownWord "=true");
+        test!("red=true" => UnknownWord "red=true");
+        test!("bold=true=false" => UnknownWord "bold=true=false");
+        test!("nobold=true" => UnknownWord "nobold=true");
+        test!("noreset" => UnknownWord "noreset");
+        test!("reset=true" => UnknownWord "reset=true");
+        test!("red blue green reset" => ExtraColor "green");
+    }
+
+    #[test]
+    fn test_requires() {
+        use ColorLevel::*;
+        assert_eq!(Style::new().requires(), Ansi16);
+        assert_eq!(Style::new().bold().requires(), Ansi16);
+        assert_eq!(Red.on(Fixed(15)).requires(), Ansi16);
+        assert_eq!(Fixed(16).normal().requires(), Ansi256);
+        assert_eq!(Style::new().on(Fixed(255)).requires(), Ansi256);
+        assert_eq!(Fixed(200).on(RGB(1, 2, 3)).requires(), TrueColor);
+        assert_eq!(Style::new().on(RGB(1, 2, 3)).requires(), TrueColor);
+    }
+
+    #[test]
+    fn test_parse_color_level() {
+        use ColorLevel::*;
+        macro_rules! test {
+            ($s:expr, $level:expr => Ok $style:expr) => {
+                assert_eq!(parse_with($s, &ParseOptions::new().color_level($level)), Ok($style));
+            };
+            ($s:expr, $level:expr => $word:expr, $required:expr) => {
+                assert_eq!(parse_with($s, &ParseOptions::new().color_level($level)),
+                           Err(Error::at(UnsupportedColor { required: $required, allowed: $level }, $s,
+                                         &$s[$s.find($word).unwrap()..][..$word.len()])));
+            };
+        }
+
+        test!("bold red blue", Ansi16 => Ok Red.on(Blue).bold());
+        test!("normal 15", Ansi16 => Ok Style::new().on(Fixed(15)));
+        test!("16", Ansi16 => "16", Ansi256);
+        test!("red #ff0000", Ansi16 => "#ff0000", TrueColor);
+        test!("255 0", Ansi256 => Ok Fixed(255).on(Fixed(0)));
+        test!("#ff0000", Ansi256 => "#ff0000", TrueColor);
+        test!("#ff0000 255", TrueColor => Ok RGB(255, 0, 0).on(Fixed(255)));
+
+        assert_eq!(parse_with("red blue #ff0000", &ParseOptions::new().color_level(Ansi16)),
+                   Err(Error::new(ExtraColor, "red blue #ff0000", "#ff0000", 9..16)));
+        assert_eq!(Error::new(UnsupportedColor { required: TrueColor, allowed: Ansi16 }, "#ff0000", "#ff0000", 0..7).to_string(),
+                   "Error parsing style \"#ff0000\": color \"#ff0000\" requires 24-bit color, but the target only supports 16 colors");
+    }
+
+    #[test]
+    fn test_variant() {
+        let base = Red.on(Fixed(236)).underline();
+        assert_eq!(base.variant(Variant::Dimmed), Red.on(Fixed(236)).underline().dimmed());
+        assert_eq!(base.bold().variant(Variant::Dimmed), base.dimmed());
+        assert_eq!(base.dimmed().variant(Variant::Emphasized), base.bold());
+        assert_eq!(base.variant(Variant::Inverted), Fixed(236).on(Red).underline());
+        assert_eq!(Style::new().variant(Variant::Inverted), Style::new());
+        assert_eq!(base.variant(Variant::Inverted).variant(Variant::Inverted), base);
+    }
+
+    #[test]
+    fn test_error_code() {
+        let errors = [
+            (parse("bold blu").unwrap_err(), "CP0001", "unknown-word", Category::Syntax),
+            (parse("red blue green").unwrap_err(), "CP0002", "extra-color", Category::Syntax),
+            (parse_with("208", &ParseOptions::new().color_level(ColorLevel::Ansi16)).unwrap_err(),
+             "CP0003", "unsupported-color", Category::Capability),
+        ];
+        for (err, code, name, category) in errors.iter() {
+            assert_eq!(err.code(), *code);
+            assert_eq!(err.name(), *name);
+            assert_eq!(err.category(), *category);
+        }
+        assert_eq!(Category::Capability.to_string(), "capability");
+
+        let (_, warnings) = parse_with_diagnostics("bold nobold red normal", &ParseOptions::new()).unwrap();
+        let codes: Vec<_> = warnings.iter().map(|w| (w.code(), w.name(), w.category())).collect();
+        assert_eq!(codes, [
+            ("CP1001", "cancelled-attribute", Category::Suspicious),
+            ("CP1002", "normal-after-color", Category::Suspicious),
+        ]);
+        let (_, warnings) = parse_with_diagnostics("1 red", &ParseOptions::new()).unwrap();
+        assert_eq!((warnings[0].code(), warnings[0].name()), ("CP1003", "same-colors"));
+        assert_eq!(Category::Suspicious.to_string(), "suspicious");
+    }
+
+    #[test]
+    fn test_parse_options() {
+        let git = ParseOptions::new().extensions(false);
+        for s in &["bold nodim no-ul red blue", "reset brightred 208", "-1 #ff0000", "normal", "noitalic strike"] {
+            assert_eq!(parse_with(s, &git), parse(s), "{:?}", s);
+        }
+        for &(s, word) in &[("#fff", "#fff"), ("bold=true", "bold=true"), ("rgb(1, 2, 3)", "rgb(1, 2, 3)"),
+                            ("red hidden", "hidden"), ("NoConceal", "NoConceal"), ("curly-ul", "curly-ul"),
+                            ("ul=red", "ul=red"), ("overline", "overline"), ("no-rapidblink", "no-rapidblink")] {
+            let start = s.find(word).unwrap();
+            assert_eq!(parse_with(s, &git), Err(Error::new(UnknownWord, s, word, start..start + word.len())));
+            assert!(parse(s).is_ok(), "{:?}", s);
+        }
+
+        for (s, only) in [
+            ("#fff", git.clone().hex_shorthand(true)),
+            ("rgb(1, 2, 3)", git.clone().color_functions(true)),
+            ("bold=true", git.clone().attribute_values(true)),
+            ("no-rapidblink", git.clone().extended_attributes(true)),
+            ("curly-ul ul=red", git.clone().underline_extensions(true)),
+        ] {
+            assert_eq!(parse_with(s, &only), parse(s), "{:?}", s);
+            assert!(parse_with("#fff rgb(1, 2, 3) bold=true no-rapidblink curly-ul ul=red", &only).is_err(), "{:?}", s);
+            assert_eq!(parse_with(s, &only.extensions(true)), parse(s));
+        }
+        #[cfg(feature = "css-names")]
+        {
+            assert!(parse_with("rebeccapurple", &git).is_err());
+            assert_eq!(parse_with("rebeccapurple", &git.clone().named_colors(true)), parse("rebeccapurple"));
+            assert!(parse_with("rebeccapurple", &ParseOptions::new().named_colors(false)).is_err());
+        }
+
+        let keywords = git.clone().keyword("Accent", Fixed(208)).keyword("red", Blue).keyword("accent", Fixed(209));
+        assert_eq!(parse_with("bold ACCENT red", &keywords), Ok(Fixed(209).on(Blue).bold()));
+        assert_eq!(parse_with("ul=accent", &keywords), parse("ul=209"));
+        assert_eq!(parse_with("accent", &ParseOptions::new()), Err(Error::new(UnknownWord, "accent", "accent", 0..6)));
+
+        let lenient = ParseOptions::new().ignore_unknown(true);
+        assert_eq!(parse_with("bold blu red bold=maybe ul=blu", &lenient), Ok(Red.bold()));
+        assert_eq!(parse_with("red green blue", &lenient), Err(Error::new(ExtraColor, "red green blue", "blue", 10..14)));
+        assert_eq!(parse_with("#fff red hidden", &git.clone().ignore_unknown(true)), Ok(Red.normal()));
+    }
+
+    #[test]
+    fn test_normalize() {
+        macro_rules! test {
+            ($s:expr => $normal:expr) => {
+                assert_eq!(normalize($s), Ok($normal.to_string()), "{:?}", $s);
+                assert_eq!(normalize($normal), Ok($normal.to_string()));
+            };
+        }
+
+        test!("" => "normal");
+        test!("normal" => "normal");
+        test!("-1 Black" => "normal black");
+        test!("strike bold ul dim italic" => "bold dim italic ul strike");
+        test!("blink red blink" => "red blink");
+        test!("BrightRed 0" => "9 black");
+        test!("ul=1 curly-ul" => "curly-ul ul=red");
+        test!("nodim bold red reset" => "reset");
+        test!("bold reset noitalic blue" => "reset blue noitalic");
+        test!("conceal default" => "default hidden");
+        assert_eq!(normalize("red ul"), normalize("ul red"));
+        assert_eq!(normalize("red blue green"), Err(parse_spec("red blue green").unwrap_err()));
+    }
+
+    #[test]
+    fn test_parse_color() {
+        assert_eq!(parse_color("BrightBlue"), Ok(Some(Fixed(12))));
+        assert_eq!(parse_color("#F80\u{A0}"), Ok(Some(RGB(0xff, 0x88, 0))));
+        assert_eq!(parse_color("-1"), Ok(None));
+        assert_eq!(parse_color(" blu"), Err(Error::new(InvalidColor, " blu", "blu", 1..4)));
+        assert_eq!(parse_color("red blue"), Err(Error::new(InvalidColor, "red blue", "red blue", 0..8)));
+        let err = parse_color("").unwrap_err();
+        assert_eq!(err.to_string(), "Error parsing color \"\": not a color");
+        assert_eq!(err.to_compact_string(), "CP0007 invalid-color input=\"\"");
+    }
+
+    #[test]
+    fn test_suggestion() {
+        let suggestion = |s| parse(s).unwrap_err().suggestion();
+        assert_eq!(suggestion("blu"), Some("blue".to_string()));
+        assert_eq!(suggestion("BOLD Ytellow"), Some("yellow".to_string()));
+        assert_eq!(suggestion("brightrde"), Some("brightred".to_string()));
+        assert_eq!(suggestion("rest"), Some("reset".to_string()));
+        assert_eq!(suggestion("curly-lu"), Some("curly-ul".to_string()));
+        assert_eq!(suggestion("no-strik"), Some("no-strike".to_string()));
+        assert_eq!(suggestion("nobodl"), Some("nobold".to_string()));
+        assert_eq!(suggestion("#ggg"), None);
+        assert_eq!(parse("red blue green").unwrap_err().suggestion(), None);
+    }
+
+    #[test]
+    fn test_parse_with_diagnostics() {
+        let warnings = |s| {
+            let (_, warnings) = parse_with_diagnostics(s, &ParseOptions::new()).unwrap();
+            warnings.iter().map(|w| (*w.kind(), w.word().to_string())).collect::<Vec<_>>()
+        };
+        assert_eq!(warnings("bold red blue ul nodim"), []);
+        assert_eq!(warnings("normal red"), []);
+        assert_eq!(warnings("bold reset nobold"), []);
+        assert_eq!(warnings("ul no-ul"), [(WarningKind::Cancelled(Attribute::Underline), "no-ul".to_string())]);
+        assert_eq!(warnings("noitalic italic=on"), [(WarningKind::Cancelled(Attribute::Italic), "italic=on".to_string())]);
+        assert_eq!(warnings("red normal"), [(WarningKind::NormalAfterColor, "normal".to_string())]);
+        assert_eq!(warnings("red default"), []);
+        assert_eq!(warnings("1 Red"), [(WarningKind::SameColors, "Red".to_string())]);
+        assert_eq!(warnings("red red reset red"), []);
+        assert!(parse_with_diagnostics("bold blu", &ParseOptions::new()).is_err());
+        let (_, warnings) = parse_with_diagnostics("blink noblink", &ParseOptions::new()).unwrap();
+        assert_eq!(warnings[0].to_string(),
+                   "Warning for style \"blink noblink\": \"noblink\" overrides the earlier setting of blink");
+    }
+
+    #[test]
+    fn test_parse_lossy() {
+        assert_eq!(parse_lossy(""), (Style::new(), vec![]));
+        assert_eq!(parse_lossy("bold red"), (Red.bold(), vec![]));
+        let s = "blu bold red ul=blu blue bold=maybe green";
+        assert_eq!(parse_lossy(s), (Red.on(Blue).bold(), vec![
+            Error::new(UnknownWord, s, "blu", 0..3),
+            Error::new(UnknownWord, s, "ul=blu", 13..19),
+            Error::new(UnknownWord, s, "bold=maybe", 25..35),
+            Error::new(ExtraColor, s, "green", 36..41),
+        ]));
+        assert_eq!(parse_lossy("red blue reset green"), (Green.normal(), vec![]));
+    }
+
+    #[test]
+    fn test_limits() {
+        let options = ParseOptions::new().max_len(10);
+        assert_eq!(parse_with("bold red", &options), Ok(Red.bold()));
+        assert_eq!(parse_with("bold red ul", &options),
+                   Err(Error::new(InputTooLong { len: 11, max: 10 }, "", "", 10..11)));
+        let options = ParseOptions::new().max_words(2);
+        assert_eq!(parse_with("  bold  red  ", &options), Ok(Red.bold()));
+        assert_eq!(parse_with("bold red blue", &options),
+                   Err(Error::new(TooManyWords { max: 2 }, "bold red blue", "blue", 9..13)));
+        let options = ParseOptions::new().max_word_len(4);
+        assert_eq!(parse_with("bold red", &options), Ok(Red.bold()));
+        assert_eq!(parse_with("bold green", &options),
+                   Err(Error::new(WordTooLong { max: 4 }, "bold green", "green", 5..10)));
+        let err = parse_with("bold red ul", &ParseOptions::new().max_len(10)).unwrap_err();
+        assert_eq!(err.category(), Category::Limit);
+        assert_eq!(err.span(), 10..11);
+        assert_eq!(err.to_compact_string(), "CP0004 input-too-long len=11 max=10");
+    }
+
+    #[test]
+    fn test_unicode_whitespace() {
+        let expected = Ok(Red.on(Blue).bold());
+        assert_eq!(parse("bold\u{A0}red\u{3000}blue"), expected);
+        assert_eq!(parse("\u{FEFF}bold\u{200B}red\u{2060}blue\u{2003}"), expected);
+        assert_eq!(parse("bold\u{200D}red"), Err(Error::new(UnknownWord, "bold\u{200D}red", "bold\u{200D}red", 0..10)));
+    }
+
+    #[test]
+    fn test_to_compact_string() {
+        assert_eq!(parse("").unwrap().to_compact_string(), "plain");
+        assert_eq!(parse("bold blu").unwrap_err().to_compact_string(),
+                   "CP0001 unknown-word input=\"bold blu\" word=\"blu\"");
+        assert_eq!(parse_with("\"#ff0000\"", &ParseOptions::new()).unwrap_err().to_compact_string(),
+                   "CP0001 unknown-word input=\"\\\"#ff0000\\\"\" word=\"\\\"#ff0000\\\"\"");
+        assert_eq!(parse_with("#ff0000", &ParseOptions::new().color_level(ColorLevel::Ansi256)).unwrap_err().to_compact_string(),
+                   "CP0003 unsupported-color input=\"#ff0000\" word=\"#ff0000\" required=TrueColor allowed=Ansi256");
     }
 }
@@ -0,0 +1,368 @@
+use std::fmt;
+
+use crate::{colors_enabled, is_separator, parse, ATTRIBUTE_NAMES, BOOL_NAMES, COLOR_NAMES, UNDERLINE_STYLE_NAMES};
+
+/// Format arguments into a `String` styled with a spec in Git syntax.
+///
+/// When the spec is a string literal, it is validated at compile time.
+/// Otherwise it is parsed when the macro runs, and the text is left
+/// unstyled if the spec is invalid. The text is also left unstyled when
+/// `colors_enabled()` returns false.
+///
+/// # Examples
+///
+///     # #[macro_use] extern crate colorparse;
+///     # fn main() {
+///     colorparse::set_override(Some(true));
+///     let s = cformat!("bold red", "error: {}", 42);
+///     assert_eq!(s, "\x1b[1;31merror: 42\x1b[0m");
+///     # }
+///
+/// An invalid literal spec fails to compile:
+///
+/// ```compile_fail
+/// # #[macro_use] extern crate colorparse;
+/// # fn main() {
+/// let s = cformat!("bold blu", "error: {}", 42);
+/// # }
+/// ```
+#[macro_export]
+macro_rules! cformat {
+    ($spec:literal, $($arg:tt)+) => {{
+        const _: () = assert!($crate::__is_valid_spec($spec), concat!("invalid color spec: ", $spec));
+        $crate::__cformat($spec, format_args!($($arg)+))
+    }};
+    ($spec:expr, $($arg:tt)+) => {
+        $crate::__cformat(&$spec, format_args!($($arg)+))
+    };
+}
+
+/// Print a line to standard output, styled with a spec in Git syntax, as
+/// formatted by `cformat!`.
+///
+/// # Examples
+///
+///     # #[macro_use] extern crate colorparse;
+///     # fn main() {
+///     # let e = "file not found";
+///     paintln!("bold red", "error: {}", e);
+///     # }
+#[macro_export]
+macro_rules! paintln {
+    ($spec:expr, $($arg:tt)+) => {
+        println!("{}", $crate::cformat!($spec, $($arg)+))
+    };
+}
+
+#[doc(hidden)]
+pub fn __cformat(spec: &str, args: fmt::Arguments) -> String {
+    match parse(spec) {
+        Ok(style) if colors_enabled() => style.paint(args.to_string()).to_string(),
+        _ => args.to_string(),
+    }
+}
+
+/// Return the character starting at `s[i]`, which must start one in a
+/// valid UTF-8 string, and its length in bytes.
+const fn char_at(s: &[u8], i: usize) -> (char, usize) {
+    let len = if s[i] < 0x80 { 1 } else if s[i] < 0xe0 { 2 } else if s[i] < 0xf0 { 3 } else { 4 };
+    let mut c = if len == 1 { s[i] as u32 } else { (s[i] & (0x7f >> len)) as u32 };
+    let mut k = 1;
+    while k < len {
+        c = (c << 6) | (s[i + k] & 0x3f) as u32;
+        k += 1;
+    }
+    match char::from_u32(c) {
+        Some(c) => (c, len),
+        None => ('\0', len),
+    }
+}
+
+/// Return the length in bytes of the separator, as `parse` splits words
+/// at, starting at `s[i]`, or 0 if there is none.
+const fn separator_at(s: &[u8], i: usize) -> usize {
+    let (c, len) = char_at(s, i);
+    if is_separator(c) { len } else { 0 }
+}
+
+/// Return the length in bytes of the separator ending just before
+/// `s[end]`, or 0 if there is none.
+const fn separator_before(s: &[u8], end: usize) -> usize {
+    let mut i = end - 1;
+    while s[i] & 0xc0 == 0x80 {
+        i -= 1;
+    }
+    if separator_at(s, i) > 0 { end - i } else { 0 }
+}
+
+/// Return true if `s[start..end]` case-insensitively equals `word`.
+const fn word_eq(s: &[u8], start: usize, end: usize, word: &str) -> bool {
+    let word = word.as_bytes();
+    if end - start != word.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < word.len() {
+        if s[start + i].to_ascii_lowercase() != word[i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+const fn is_one_of(s: &[u8], start: usize, end: usize, words: &[&str]) -> bool {
+    let mut i = 0;
+    while i < words.len() {
+        if word_eq(s, start, end, words[i]) {
+            return true;
+        }
+        i += 1;
+    }
+    false
+}
+
+const fn is_attribute(s: &[u8], start: usize, end: usize) -> bool {
+    if is_one_of(s, start, end, &ATTRIBUTE_NAMES) || is_one_of(s, start, end, &UNDERLINE_STYLE_NAMES) {
+        return true;
+    }
+    if end - start > 2 && word_eq(s, start, start + 2, "no") {
+        let name = if s[start + 2] == b'-' { start + 3 } else { start + 2 };
+        return is_one_of(s, name, end, &ATTRIBUTE_NAMES);
+    }
+    let mut i = start;
+    while i < end {
+        if s[i] == b'=' {
+            return is_one_of(s, start, i, &ATTRIBUTE_NAMES)
+                && (is_one_of(s, i + 1, end, &BOOL_NAMES) || (word_eq(s, start, i, "ul") && i + 1 < end && is_color(s, i + 1, end)));
+        }
+        i += 1;
+    }
+    false
+}
+
+/// Return true if `s[start..end]`, surrounded by optional separators, is the
+/// argument at `position` of a color function: an integer from 0 to 255
+/// for `rgb`, and otherwise a hue in degrees followed by percentages.
+const fn is_argument(s: &[u8], start: usize, end: usize, rgb: bool, position: usize) -> bool {
+    let (mut start, mut end) = (start, end);
+    while start < end && separator_at(s, start) > 0 {
+        start += separator_at(s, start);
+    }
+    while end > start && separator_before(s, end) > 0 {
+        end -= separator_before(s, end);
+    }
+    if !rgb && position == 0 && end - start > 3 && word_eq(s, end - 3, end, "deg") {
+        end -= 3;
+    } else if !rgb && position > 0 && end > start && s[end - 1] == b'%' {
+        end -= 1;
+    }
+    if start < end && s[start] == b'+' {
+        start += 1;
+    }
+    let mut value = 0u32;
+    let mut digits = 0;
+    let mut fraction = false;
+    let mut nonzero_fraction = false;
+    let mut i = start;
+    while i < end {
+        if s[i] == b'.' && !rgb && !fraction {
+            fraction = true;
+        } else if !s[i].is_ascii_digit() {
+            return false;
+        } else if fraction {
+            nonzero_fraction |= s[i] != b'0';
+            digits += 1;
+        } else {
+            value = value * 10 + (s[i] - b'0') as u32;
+            if value > 1000 {
+                value = 1000;
+            }
+            digits += 1;
+        }
+        i += 1;
+    }
+    if digits == 0 {
+        return false;
+    }
+    if rgb {
+        value <= 255
+    } else if position == 0 {
+        true
+    } else {
+        value < 100 || (value == 100 && !nonzero_fraction)
+    }
+}
+
+/// Return true if `s[start..end]` is a color function, such as
+/// `rgb(1, 2, 3)` or `hsl(220, 80%, 50%)`.
+const fn is_function(s: &[u8], start: usize, end: usize) -> bool {
+    if end - start < 5 || s[end - 1] != b')' {
+        return false;
+    }
+    let rgb = word_eq(s, start, start + 4, "rgb(");
+    if !rgb && !word_eq(s, start, start + 4, "hsl(") && !word_eq(s, start, start + 4, "hsv(") {
+        return false;
+    }
+    let mut arguments = 0;
+    let mut argument_start = start + 4;
+    let mut i = start + 4;
+    while i < end {
+        if s[i] == b',' || i == end - 1 {
+            if arguments == 3 || !is_argument(s, argument_start, i, rgb, arguments) {
+                return false;
+            }
+            arguments += 1;
+            argument_start = i + 1;
+        }
+        i += 1;
+    }
+    arguments == 3
+}
+
+const fn is_color(s: &[u8], start: usize, end: usize) -> bool {
+    if is_one_of(s, start, end, &COLOR_NAMES) || is_function(s, start, end) {
+        return true;
+    }
+    #[cfg(feature = "css-names")]
+    {
+        let mut i = 0;
+        while i < crate::css_names::CSS_NAMES.len() {
+            if word_eq(s, start, end, crate::css_names::CSS_NAMES[i].0) {
+                return true;
+            }
+            i += 1;
+        }
+    }
+    if s[start] == b'#' {
+        if end - start != 7 && end - start != 4 {
+            return false;
+        }
+        let mut i = start + 1;
+        while i < end {
+            if !s[i].is_ascii_hexdigit() {
+                return false;
+            }
+            i += 1;
+        }
+        return true;
+    }
+    let mut i = if s[start] == b'+' { start + 1 } else { start };
+    if i == end {
+        return false;
+    }
+    let mut value = 0u32;
+    while i < end {
+        if !s[i].is_ascii_digit() {
+            return false;
+        }
+        value = value * 10 + (s[i] - b'0') as u32;
+        if value > 255 {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+/// Return true if `parse` would accept `spec`, for validating literal
+/// specs at compile time. Words are split at the same separators as in
+/// `parse`, but any other non-ASCII character is conservatively rejected.
+#[doc(hidden)]
+pub const fn __is_valid_spec(spec: &str) -> bool {
+    let s = spec.as_bytes();
+    let mut colors = 0;
+    let mut i = 0;
+    while i < s.len() {
+        if separator_at(s, i) > 0 {
+            i += separator_at(s, i);
+            continue;
+        }
+        let start = i;
+        let mut depth = 0;
+        while i < s.len() && (depth > 0 || separator_at(s, i) == 0) {
+            if s[i] == b'(' {
+                depth += 1;
+            } else if s[i] == b')' && depth > 0 {
+                depth -= 1;
+            } else if !s[i].is_ascii() && separator_at(s, i) == 0 {
+                return false;
+            }
+            i += char_at(s, i).1;
+        }
+        if word_eq(s, start, i, "reset") {
+            colors = 0;
+            continue;
+        }
+        if is_attribute(s, start, i) {
+            continue;
+        }
+        if !is_color(s, start, i) || colors == 2 {
+            return false;
+        }
+        colors += 1;
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::global::lock_override;
+    use crate::set_override;
+
+    #[test]
+    fn test_is_valid_spec() {
+        for s in &["", "  ", "normal", "-1 normal", "red blue", "RED\tBlue", "255 #abcdef", "+7", "007",
+                   "bold cyan nobold white", "no-ul NoBlink", "bold=true ITALIC=Off", "strike",
+                   "brightred BrightBlue", "red blue reset green", "#fff #F80",
+                   "rgb(1, 2, 3) RGB(255,0,0)", "rgb(+1, 2, 3)", "hsl(220, 80%, 50%)", "hsv(720deg,100,0.5%)",
+                   "HSL(12.5, 100.0%, 0)", "ul ul=red", "UL=#abc ul=rgb(1, 2, 3) ul=normal",
+                   "curly-ul ul=red", "Double-UL dotted-ul dashed-ul", "overline no-overline", "hidden noconceal", "rapidblink no-rapidblink",
+                   "red\u{a0}blue", "bold\u{200b}red\u{3000}\u{feff}", "rgb(1,\u{a0}2, 3\u{2060})"] {
+            assert!(__is_valid_spec(s), "{:?}", s);
+            assert!(parse(s).is_ok(), "{:?}", s);
+        }
+        for s in &["red blue green", "256", "-2", "+", "no", "no-", "noblue", "#12345", "#bcdefg", "bold=",
+                   "bold=yes", "=true", "nobold=true", "blue1", "b\u{e4}ld", "red\u{a0}blu\u{e9}", "noreset", "#ff", "#ffff",
+                   "rgb(1, 2)", "rgb(1, 2, 256)", "rgb(1,2,3,4)", "rgb(1, 2, 3", "rgb(,1,2)", "rgb(1 2 3)",
+                   "rgb(1.5, 2, 3)", "hsl(0, 100.5%, 50%)", "hsl(0, 101%, 0)", "hsl(0deg, 50deg, 0)", "hsl(0, 5%%, 0)",
+                   "hsl(1e2, 5, 5)", "hsl(0..1, 5, 5)", "hsl(., 5, 5)", "hsv(0, 5, 5, 5)", "hsl(-1, 5, 5)",
+                   "ul=", "ul=blu", "bold=red", "noul=red",
+                   "curly", "curly-", "nocurly-ul", "curly-ul=true"] {
+            assert!(!__is_valid_spec(s), "{:?}", s);
+        }
+    }
+
+    #[cfg(feature = "git-corpus")]
+    #[test]
+    fn test_is_valid_spec_git_corpus() {
+        for case in crate::git_corpus::cases() {
+            assert_eq!(__is_valid_spec(case.input), parse(case.input).is_ok(), "{:?}", case.input);
+        }
+    }
+
+    #[cfg(feature = "css-names")]
+    #[test]
+    fn test_is_valid_spec_css_names() {
+        for s in &["orange", "bold Teal slategray"] {
+            assert!(__is_valid_spec(s), "{:?}", s);
+            assert!(parse(s).is_ok(), "{:?}", s);
+        }
+        assert!(!__is_valid_spec("orange teal red"));
+    }
+
+    #[test]
+    fn test_cformat() {
+        let _lock = lock_override();
+        set_override(Some(true));
+        assert_eq!(cformat!("bold red", "error: {}", 42), "\x1b[1;31merror: 42\x1b[0m");
+        let spec = String::from("ul");
+        assert_eq!(cformat!(spec, "{}", "x"), "\x1b[4mx\x1b[0m");
+        let invalid = "bold blu";
+        assert_eq!(cformat!(invalid, "{}", "x"), "x");
+        set_override(Some(false));
+        assert_eq!(cformat!("bold red", "error: {}", 42), "error: 42");
+        set_override(None);
+    }
+}
@@ -0,0 +1,119 @@
+//! Parsing Git-syntax specs into `nu_ansi_term` styles.
+//!
+//! `nu_ansi_term` is a maintained fork of `ansi_term`, with the same shape
+//! of `Style`. This module converts a `StyleSpec` into a
+//! `nu_ansi_term::Style`, which, unlike an `ansi_term::Style`, can hold
+//! Git's `default` color and `reset`.
+//!
+//! # Examples
+//!
+//!     # extern crate colorparse;
+//!     # extern crate nu_ansi_term;
+//!     use nu_ansi_term::Color::{Blue, Red};
+//!
+//!     # fn main() {
+//!     let style = colorparse::nu_ansi_term_styles::parse("bold red blue").unwrap();
+//!     assert_eq!(style, Red.on(Blue).bold());
+//!     println!("{}", style.paint("Bold red on blue"));
+//!     # }
+
+use ansi_term::Color;
+use nu_ansi_term::{Color as NuColor, Style as NuStyle};
+
+use crate::style_builder::build;
+use crate::{parse_spec, Attribute, ColorValue, Error, StyleBuilder, StyleSpec};
+
+/// Convert `color` to the `nu_ansi_term::Color` that gives the same
+/// escape sequence.
+pub fn to_nu_color(color: Color) -> NuColor {
+    match color {
+        Color::Black => NuColor::Black,
+        Color::Red => NuColor::Red,
+        Color::Green => NuColor::Green,
+        Color::Yellow => NuColor::Yellow,
+        Color::Blue => NuColor::Blue,
+        Color::Purple => NuColor::Purple,
+        Color::Cyan => NuColor::Cyan,
+        Color::White => NuColor::White,
+        Color::Fixed(n) => NuColor::Fixed(n),
+        Color::RGB(r, g, b) => NuColor::Rgb(r, g, b),
+    }
+}
+
+/// Parse a string in Git's color configuration syntax, as `parse_spec`
+/// does, into a `nu_ansi_term::Style`.
+pub fn parse(s: &str) -> Result<NuStyle, Error> {
+    parse_spec(s).map(NuStyle::from)
+}
+
+/// Builds a `nu_ansi_term::Style`, with a `default` color as
+/// `Color::Default` and a reset as `prefix_with_reset`.
+impl StyleBuilder for NuStyle {
+    fn set_fg(&mut self, color: ColorValue) {
+        self.foreground = Some(to_nu_color(color.into()));
+    }
+
+    fn set_bg(&mut self, color: ColorValue) {
+        self.background = Some(to_nu_color(color.into()));
+    }
+
+    fn set_attr(&mut self, attribute: Attribute) {
+        match attribute {
+            Attribute::Bold => self.is_bold = true,
+            Attribute::Dimmed => self.is_dimmed = true,
+            Attribute::Italic => self.is_italic = true,
+            Attribute::Underline => self.is_underline = true,
+            Attribute::Blink => self.is_blink = true,
+            Attribute::Reverse => self.is_reverse = true,
+            Attribute::Hidden => self.is_hidden = true,
+            Attribute::Strikethrough => self.is_strikethrough = true,
+            Attribute::Overline | Attribute::RapidBlink => {}
+        }
+    }
+
+    fn set_default_fg(&mut self) {
+        self.foreground = Some(NuColor::Default);
+    }
+
+    fn set_default_bg(&mut self) {
+        self.background = Some(NuColor::Default);
+    }
+
+    fn reset(&mut self) {
+        self.prefix_with_reset = true;
+    }
+}
+
+/// Converts to a `nu_ansi_term::Style`, as `StyleBuilder` builds it,
+/// dropping the underline color and shape, overline, rapid blink, and
+/// cleared attributes.
+impl From<StyleSpec> for NuStyle {
+    fn from(spec: StyleSpec) -> Self {
+        build(&spec)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(parse("ul dim 208 #102030"), Ok(NuColor::Fixed(208).on(NuColor::Rgb(16, 32, 48)).underline().dimmed()));
+        assert_eq!(parse("conceal strike blink reverse italic magenta"),
+                   Ok(NuColor::Purple.italic().blink().reverse().hidden().strikethrough()));
+        assert_eq!(parse("default brightred"), Ok(NuColor::Default.on(NuColor::Fixed(9))));
+        assert_eq!(parse("reset cyan"), Ok(NuStyle { prefix_with_reset: true, ..NuColor::Cyan.normal() }));
+        assert_eq!(parse("nobold overline curly-ul ul=red"), Ok(NuStyle::new().underline()));
+        assert_eq!(parse(""), Ok(NuStyle::new()));
+        assert!(parse("bold sparkly").is_err());
+    }
+
+    #[test]
+    fn test_escape_sequences() {
+        for s in ["bold red blue", "dim ul 9 208", "italic #ff8000 normal", "reverse strike white black"].iter() {
+            let style = crate::parse(s).unwrap();
+            assert_eq!(parse(s).unwrap().prefix().to_string(), style.prefix().to_string(), "{}", s);
+        }
+    }
+}
@@ -0,0 +1,167 @@
+use std::fs::OpenOptions;
+use std::io::{self, Read, Write};
+use std::os::unix::io::AsRawFd;
+use std::time::{Duration, Instant};
+
+use crate::Palette;
+
+/// Parse one color from an OSC response, such as `rgb:ffff/8080/0000`,
+/// scaling each component of one to four hex digits to eight bits.
+fn parse_rgb(s: &str) -> Option<(u8, u8, u8)> {
+    let mut components = s.strip_prefix("rgb:")?.split('/').map(|c| {
+        if c.is_empty() || c.len() > 4 {
+            return None;
+        }
+        let max = (1u32 << (4 * c.len())) - 1;
+        u32::from_str_radix(c, 16).ok().map(|v| (v * 255 / max) as u8)
+    });
+    let rgb = (components.next()??, components.next()??, components.next()??);
+    match components.next() {
+        None => Some(rgb),
+        Some(_) => None,
+    }
+}
+
+/// Apply the OSC 4, 10, and 11 responses in `response` to `palette`,
+/// returning how many colors the responses set.
+fn apply_responses(response: &[u8], palette: &mut Palette) -> usize {
+    let response = String::from_utf8_lossy(response);
+    let mut count = 0;
+    for reply in response.split("\x1b]").skip(1) {
+        let reply = reply.split(['\x07', '\x1b']).next().unwrap_or("");
+        let mut fields = reply.split(';');
+        let (slot, color) = match (fields.next(), fields.next(), fields.next()) {
+            (Some("4"), Some(n), Some(color)) => match n.parse::<usize>() {
+                Ok(n) if n < 16 => (&mut palette.ansi[n], color),
+                _ => continue,
+            },
+            (Some("10"), Some(color), None) => (&mut palette.foreground, color),
+            (Some("11"), Some(color), None) => (&mut palette.background, color),
+            _ => continue,
+        };
+        if let Some(rgb) = parse_rgb(color) {
+            *slot = rgb;
+            count += 1;
+        }
+    }
+    count
+}
+
+/// Return true if `response` contains the terminal's reply to the primary
+/// device attributes request, which terminals send after answering the
+/// preceding OSC queries.
+fn has_device_attributes(response: &[u8]) -> bool {
+    response.windows(3).position(|w| w == b"\x1b[?").is_some_and(|i| response[i..].contains(&b'c'))
+}
+
+/// Restores the terminal's previous mode when dropped.
+struct RawMode {
+    fd: libc::c_int,
+    original: libc::termios,
+}
+
+impl RawMode {
+    fn enable(fd: libc::c_int) -> io::Result<Self> {
+        let mut termios = unsafe { std::mem::zeroed::<libc::termios>() };
+        if unsafe { libc::tcgetattr(fd, &mut termios) } != 0 {
+            return Err(io::Error::last_os_error());
+        }
+        let original = termios;
+        termios.c_lflag &= !(libc::ICANON | libc::ECHO);
+        termios.c_cc[libc::VMIN] = 1;
+        termios.c_cc[libc::VTIME] = 0;
+        if unsafe { libc::tcsetattr(fd, libc::TCSANOW, &termios) } != 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(RawMode { fd, original })
+    }
+}
+
+impl Drop for RawMode {
+    fn drop(&mut self) {
+        unsafe { libc::tcsetattr(self.fd, libc::TCSANOW, &self.original) };
+    }
+}
+
+/// Ask the controlling terminal for its actual palette and default colors,
+/// using OSC 4, 10, and 11, waiting at most `timeout` for the answers.
+///
+/// Colors the terminal does not report keep their values from
+/// `Palette::default()`. Returns an error if there is no controlling
+/// terminal, or if it reports no colors at all before the timeout.
+pub fn query_palette(timeout: Duration) -> io::Result<Palette> {
+    let mut tty = OpenOptions::new().read(true).write(true).open("/dev/tty")?;
+    let fd = tty.as_raw_fd();
+    let _raw = RawMode::enable(fd)?;
+
+    let mut query = String::new();
+    for n in 0..16 {
+        query.push_str(&format!("\x1b]4;{};?\x07", n));
+    }
+    query.push_str("\x1b]10;?\x07\x1b]11;?\x07\x1b[c");
+    tty.write_all(query.as_bytes())?;
+    tty.flush()?;
+
+    let deadline = Instant::now() + timeout;
+    let mut response = Vec::new();
+    while !has_device_attributes(&response) {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining == Duration::from_millis(0) {
+            break;
+        }
+        let mut pollfd = libc::pollfd { fd, events: libc::POLLIN, revents: 0 };
+        let millis = remaining.as_millis().clamp(1, libc::c_int::MAX as u128) as libc::c_int;
+        match unsafe { libc::poll(&mut pollfd, 1, millis) } {
+            -1 => return Err(io::Error::last_os_error()),
+            0 => break,
+            _ => {}
+        }
+        let mut buf = [0; 256];
+        let n = tty.read(&mut buf)?;
+        response.extend_from_slice(&buf[..n]);
+    }
+
+    let mut palette = Palette::default();
+    match apply_responses(&response, &mut palette) {
+        0 => Err(io::Error::new(io::ErrorKind::TimedOut, "terminal did not report its palette")),
+        _ => Ok(palette),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_rgb() {
+        assert_eq!(parse_rgb("rgb:ffff/8080/0000"), Some((255, 128, 0)));
+        assert_eq!(parse_rgb("rgb:ff/80/00"), Some((255, 128, 0)));
+        assert_eq!(parse_rgb("rgb:f/8/0"), Some((255, 136, 0)));
+        assert_eq!(parse_rgb("rgb:fff/000/fff"), Some((255, 0, 255)));
+        assert_eq!(parse_rgb("rgb:ff/80"), None);
+        assert_eq!(parse_rgb("rgb:ff/80/00/00"), None);
+        assert_eq!(parse_rgb("rgb:fffff/0/0"), None);
+        assert_eq!(parse_rgb("rgb:gg/0/0"), None);
+        assert_eq!(parse_rgb("#ff8000"), None);
+    }
+
+    #[test]
+    fn test_apply_responses() {
+        let response = b"\x1b]4;1;rgb:cdcd/0000/0000\x07\x1b]4;15;rgb:ff/ff/ff\x1b\\\
+                         \x1b]10;rgb:e5e5/e5e5/e5e5\x07\x1b]11;rgb:0000/0000/0000\x07\x1b[?62;22c";
+        let mut palette = Palette::default();
+        assert_eq!(apply_responses(response, &mut palette), 4);
+        assert_eq!(palette.ansi[1], (0xcd, 0, 0));
+        assert_eq!(palette.ansi[15], (255, 255, 255));
+        assert_eq!(palette.ansi[2], Palette::VGA.ansi[2]);
+        assert_eq!(palette.foreground, (0xe5, 0xe5, 0xe5));
+        assert_eq!(palette.background, (0, 0, 0));
+        assert!(has_device_attributes(response));
+        assert!(!has_device_attributes(b"\x1b]4;1;rgb:cdcd/0000/0000\x07"));
+
+        let mut palette = Palette::default();
+        let response = b"\x1b]4;16;rgb:0/0/0\x07\x1b]4;x;rgb:0/0/0\x07\x1b]12;rgb:0/0/0\x07\x1b]10;?\x07";
+        assert_eq!(apply_responses(response, &mut palette), 0);
+        assert_eq!(palette, Palette::default());
+    }
+}
@@ -0,0 +1,134 @@
+//! Parsing Git-syntax specs into `owo-colors` styles.
+//!
+//! This module converts a `StyleSpec` into an `owo_colors::Style`, chosen
+//! at runtime, so that programs standardized on `owo-colors` can take
+//! their styles from Git-syntax config.
+//!
+//! # Examples
+//!
+//!     # extern crate colorparse;
+//!     # extern crate owo_colors;
+//!     use owo_colors::{OwoColorize, Style};
+//!
+//!     # fn main() {
+//!     let style = colorparse::owo_colors_styles::parse("bold red blue").unwrap();
+//!     assert_eq!(style, Style::new().red().on_blue().bold());
+//!     println!("{}", "Bold red on blue".style(style));
+//!     # }
+
+use ansi_term::Color;
+use owo_colors::{AnsiColors, DynColors, Effect, Style as OwoStyle, XtermColors};
+
+use crate::style_builder::build;
+use crate::{parse_spec, Attribute, ColorValue, Error, StyleBuilder, StyleSpec};
+
+/// The `owo_colors::AnsiColors` for each of colors 0 through 15.
+const ANSI: [AnsiColors; 16] = [
+    AnsiColors::Black, AnsiColors::Red, AnsiColors::Green, AnsiColors::Yellow,
+    AnsiColors::Blue, AnsiColors::Magenta, AnsiColors::Cyan, AnsiColors::White,
+    AnsiColors::BrightBlack, AnsiColors::BrightRed, AnsiColors::BrightGreen, AnsiColors::BrightYellow,
+    AnsiColors::BrightBlue, AnsiColors::BrightMagenta, AnsiColors::BrightCyan, AnsiColors::BrightWhite,
+];
+
+/// Convert `color` to the `owo_colors::DynColors` that gives the escape
+/// sequence Git writes for it, with colors 0–15 as `AnsiColors`.
+pub fn to_owo_color(color: Color) -> DynColors {
+    match color {
+        Color::Black => DynColors::Ansi(AnsiColors::Black),
+        Color::Red => DynColors::Ansi(AnsiColors::Red),
+        Color::Green => DynColors::Ansi(AnsiColors::Green),
+        Color::Yellow => DynColors::Ansi(AnsiColors::Yellow),
+        Color::Blue => DynColors::Ansi(AnsiColors::Blue),
+        Color::Purple => DynColors::Ansi(AnsiColors::Magenta),
+        Color::Cyan => DynColors::Ansi(AnsiColors::Cyan),
+        Color::White => DynColors::Ansi(AnsiColors::White),
+        Color::Fixed(n) if n < 16 => DynColors::Ansi(ANSI[n as usize]),
+        Color::Fixed(n) => DynColors::Xterm(XtermColors::from(n)),
+        Color::RGB(r, g, b) => DynColors::Rgb(r, g, b),
+    }
+}
+
+/// Parse a string in Git's color configuration syntax, as `parse_spec`
+/// does, into an `owo_colors::Style`.
+pub fn parse(s: &str) -> Result<OwoStyle, Error> {
+    parse_spec(s).map(OwoStyle::from)
+}
+
+/// Builds an `owo_colors::Style`, with a `default` color as
+/// `AnsiColors::Default` and rapid blink as `Effect::BlinkFast`.
+impl StyleBuilder for OwoStyle {
+    fn set_fg(&mut self, color: ColorValue) {
+        *self = self.color(to_owo_color(color.into()));
+    }
+
+    fn set_bg(&mut self, color: ColorValue) {
+        *self = self.on_color(to_owo_color(color.into()));
+    }
+
+    fn set_attr(&mut self, attribute: Attribute) {
+        let effect = match attribute {
+            Attribute::Bold => Effect::Bold,
+            Attribute::Dimmed => Effect::Dimmed,
+            Attribute::Italic => Effect::Italic,
+            Attribute::Underline => Effect::Underline,
+            Attribute::Blink => Effect::Blink,
+            Attribute::RapidBlink => Effect::BlinkFast,
+            Attribute::Reverse => Effect::Reversed,
+            Attribute::Hidden => Effect::Hidden,
+            Attribute::Strikethrough => Effect::Strikethrough,
+            Attribute::Overline => return,
+        };
+        *self = self.effect(effect);
+    }
+
+    fn set_default_fg(&mut self) {
+        *self = self.color(AnsiColors::Default);
+    }
+
+    fn set_default_bg(&mut self) {
+        *self = self.on_color(AnsiColors::Default);
+    }
+}
+
+/// Converts to an `owo_colors::Style`, as `StyleBuilder` builds it,
+/// dropping the underline color and shape, overline, cleared attributes,
+/// and reset.
+impl From<StyleSpec> for OwoStyle {
+    fn from(spec: StyleSpec) -> Self {
+        build(&spec)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(parse("ul dim 208 #102030"),
+                   Ok(OwoStyle::new().color(XtermColors::from(208)).on_truecolor(16, 32, 48).underline().dimmed()));
+        assert_eq!(parse("conceal strike rapidblink reverse italic magenta 15"),
+                   Ok(OwoStyle::new().magenta().on_bright_white().italic().blink_fast().reversed().hidden().strikethrough()));
+        assert_eq!(parse("default brightred"), Ok(OwoStyle::new().default_color().on_bright_red()));
+        assert_eq!(parse("reset nobold overline curly-ul ul=red"), Ok(OwoStyle::new().underline()));
+        assert_eq!(parse(""), Ok(OwoStyle::new()));
+        assert!(parse("bold sparkly").is_err());
+    }
+
+    #[test]
+    fn test_escape_sequences() {
+        struct Prefix(OwoStyle);
+
+        impl std::fmt::Display for Prefix {
+            fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                self.0.fmt_prefix(f)
+            }
+        }
+
+        for s in ["bold red blue", "dim ul 9 208", "italic #ff8000 normal", "reverse strike white black", "default 15"].iter() {
+            let prefix = Prefix(parse(s).unwrap()).to_string();
+            let params: Vec<&str> = prefix.split("\x1b[").filter(|p| !p.is_empty()).map(|p| p.trim_end_matches('m')).collect();
+            assert_eq!(crate::parse_sgr(&params.join(";")), crate::parse_spec(s), "{}", s);
+        }
+    }
+}
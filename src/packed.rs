@@ -0,0 +1,161 @@
+use ansi_term::{Color, Style};
+
+use crate::{downgrade_style, Attribute, ColorLevel};
+
+/// The low bits hold one bit for each attribute, at its discriminant,
+/// and the colors follow them.
+const ATTRIBUTE_BITS: u32 = Attribute::ALL.len() as u32;
+const ATTRIBUTE_MASK: u32 = (1 << ATTRIBUTE_BITS) - 1;
+const COLOR_BITS: u32 = 9;
+const COLOR_MASK: u32 = (1 << COLOR_BITS) - 1;
+const FG_SHIFT: u32 = ATTRIBUTE_BITS;
+const BG_SHIFT: u32 = FG_SHIFT + COLOR_BITS;
+const USED_BITS: u32 = BG_SHIFT + COLOR_BITS;
+
+const _: () = assert!(ATTRIBUTE_MASK & COLOR_MASK << FG_SHIFT == 0 && COLOR_MASK << FG_SHIFT & COLOR_MASK << BG_SHIFT == 0);
+const _: () = assert!(USED_BITS <= 32);
+
+/// Encode a color in 9 bits: 0 for none, 1 through 8 for the named colors,
+/// and 9 through 264 for `Color::Fixed`, keeping the two apart so that
+/// unpacking returns the same variant.
+fn pack_color(color: Option<Color>) -> Option<u32> {
+    Some(match color {
+        None => 0,
+        Some(Color::Black) => 1,
+        Some(Color::Red) => 2,
+        Some(Color::Green) => 3,
+        Some(Color::Yellow) => 4,
+        Some(Color::Blue) => 5,
+        Some(Color::Purple) => 6,
+        Some(Color::Cyan) => 7,
+        Some(Color::White) => 8,
+        Some(Color::Fixed(n)) => 9 + u32::from(n),
+        Some(Color::RGB(..)) => return None,
+    })
+}
+
+fn unpack_color(bits: u32) -> Option<Color> {
+    Some(match bits {
+        0 => return None,
+        1 => Color::Black,
+        2 => Color::Red,
+        3 => Color::Green,
+        4 => Color::Yellow,
+        5 => Color::Blue,
+        6 => Color::Purple,
+        7 => Color::Cyan,
+        8 => Color::White,
+        n => Color::Fixed((n - 9) as u8),
+    })
+}
+
+/// A style packed into 32 bits, for storing a style per cell of a large
+/// screen buffer.
+///
+/// A packed style holds the attributes and any colors other than
+/// `Color::RGB`; `PackedStyle::pack` returns `None` for styles with RGB
+/// colors, and `PackedStyle::pack_lossy` downgrades them to the 256-color
+/// palette first. Unpacking returns exactly the style that was packed.
+///
+/// # Examples
+///
+///     # extern crate ansi_term;
+///     # extern crate colorparse;
+///     use ansi_term::Style;
+///     use colorparse::{parse, PackedStyle};
+///
+///     # fn main() {
+///     let style = parse("bold red 208").unwrap();
+///     let packed = PackedStyle::pack(style).unwrap();
+///     assert_eq!(std::mem::size_of_val(&packed), 4);
+///     assert_eq!(packed.unpack(), style);
+///     assert_eq!(PackedStyle::pack(parse("#ff0000").unwrap()), None);
+///     assert_eq!(PackedStyle::pack_lossy(parse("#ff0000").unwrap()).unpack(), parse("196").unwrap());
+///     assert_eq!(Style::from(PackedStyle::default()), Style::new());
+///     # }
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct PackedStyle(u32);
+
+impl PackedStyle {
+    /// Pack `style`, or return `None` if it uses an RGB color.
+    pub fn pack(style: Style) -> Option<Self> {
+        let attributes = Attribute::ALL.iter()
+            .filter(|a| a.is_set(&style))
+            .fold(0, |bits, &a| bits | 1 << a as u32);
+        let fg = pack_color(style.foreground)?;
+        let bg = pack_color(style.background)?;
+        Some(PackedStyle(attributes | fg << FG_SHIFT | bg << BG_SHIFT))
+    }
+
+    /// Pack `style`, first downgrading any RGB colors to the nearest colors
+    /// of the 256-color palette.
+    pub fn pack_lossy(style: Style) -> Self {
+        Self::pack(downgrade_style(style, ColorLevel::Ansi256)).unwrap()
+    }
+
+    /// Return the style this packs.
+    pub fn unpack(self) -> Style {
+        let mut style = Attribute::ALL.iter()
+            .filter(|&&a| self.0 & 1 << a as u32 != 0)
+            .fold(Style::new(), |style, a| a.apply(style));
+        style.foreground = unpack_color(self.0 >> FG_SHIFT & COLOR_MASK);
+        style.background = unpack_color(self.0 >> BG_SHIFT & COLOR_MASK);
+        style
+    }
+
+    /// Return the packed representation, whose upper 4 bits are always 0.
+    pub fn to_bits(self) -> u32 {
+        self.0
+    }
+
+    /// Return the packed style with the representation `bits`, as returned
+    /// by `to_bits`, or `None` if `bits` is not a valid representation.
+    pub fn from_bits(bits: u32) -> Option<Self> {
+        let valid = |color: u32| color <= 264;
+        if bits >> USED_BITS != 0
+            || !valid(bits >> FG_SHIFT & COLOR_MASK)
+            || !valid(bits >> BG_SHIFT & COLOR_MASK) {
+            return None;
+        }
+        Some(PackedStyle(bits))
+    }
+}
+
+impl From<PackedStyle> for Style {
+    fn from(packed: PackedStyle) -> Style {
+        packed.unpack()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ansi_term::Color::*;
+
+    #[test]
+    fn test_round_trip() {
+        let styles = [
+            Style::new(),
+            Red.normal(),
+            Fixed(1).normal(),
+            Fixed(0).on(Fixed(255)),
+            White.on(Black).bold().dimmed().italic().underline().blink().reverse().hidden().strikethrough(),
+            Style::new().on(Purple).strikethrough(),
+        ];
+        for &style in &styles {
+            let packed = PackedStyle::pack(style).unwrap();
+            assert_eq!(packed.unpack(), style);
+            assert_eq!(PackedStyle::from_bits(packed.to_bits()), Some(packed));
+        }
+        assert_ne!(PackedStyle::pack(Red.normal()), PackedStyle::pack(Fixed(1).normal()));
+    }
+
+    #[test]
+    fn test_unpackable() {
+        assert_eq!(PackedStyle::pack(Red.on(RGB(1, 2, 3))), None);
+        assert_eq!(PackedStyle::pack_lossy(RGB(0xff, 0, 0).bold()).unpack(), Fixed(196).bold());
+        assert_eq!(PackedStyle::from_bits(1 << 28), None);
+        assert_eq!(PackedStyle::from_bits(265 << FG_SHIFT), None);
+        assert_eq!(PackedStyle::from_bits(264 << BG_SHIFT).map(PackedStyle::unpack), Some(Style::new().on(Fixed(255))));
+    }
+}
@@ -0,0 +1,200 @@
+use std::fmt;
+use std::io::{self, IsTerminal};
+
+use ansi_term::Style;
+
+use crate::global::enabled_if;
+use crate::render::write_style;
+
+/// A value to display in a style, as returned by `paint`.
+///
+/// Its `Display` implementation writes the style's escape sequence, the
+/// value, and a reset directly into the formatter, without building an
+/// intermediate `String` as `Style::paint` does. Formatting flags such as
+/// width and precision apply to the value. A plain style writes the value
+/// alone, as does a disabled painter; see `set_override`.
+#[derive(Debug)]
+pub struct Painted<'a, T: ?Sized + 'a> {
+    style: Style,
+    value: &'a T,
+    condition: bool,
+}
+
+impl<'a, T: ?Sized> Painted<'a, T> {
+    /// Return the style the value is displayed in.
+    pub fn style(&self) -> Style {
+        self.style
+    }
+
+    /// Return the value being displayed.
+    pub fn value(&self) -> &'a T {
+        self.value
+    }
+}
+
+impl<'a, T: ?Sized> Clone for Painted<'a, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, T: ?Sized> Copy for Painted<'a, T> {}
+
+impl<'a, T: fmt::Display + ?Sized> fmt::Display for Painted<'a, T> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        if self.style == Style::new() || !enabled_if(self.condition) {
+            return self.value.fmt(fmt);
+        }
+        write_style(fmt, &self.style)?;
+        self.value.fmt(fmt)?;
+        fmt.write_str("\x1b[0m")
+    }
+}
+
+/// Display `value` in `style`, without allocating, unless `set_override`
+/// or `NO_COLOR` disables styling.
+///
+/// # Examples
+///
+///     # colorparse::set_override(Some(true));
+///     let style = colorparse::parse("bold red").unwrap();
+///     let s = format!("error: {:>4}", colorparse::paint(style, &42));
+///     assert_eq!(s, "error: \x1b[1;31m  42\x1b[0m");
+pub fn paint<T: fmt::Display + ?Sized>(style: Style, value: &T) -> Painted<'_, T> {
+    paint_if(true, style, value)
+}
+
+/// Display `value` in `style` if `condition` holds, as `paint` does; a
+/// value passed to `set_override` takes precedence over `condition`.
+///
+/// # Examples
+///
+///     use std::io::IsTerminal;
+///
+///     let style = colorparse::parse("yellow").unwrap();
+///     println!("{}", colorparse::paint_if(std::io::stdout().is_terminal(), style, "warning"));
+pub fn paint_if<T: fmt::Display + ?Sized>(condition: bool, style: Style, value: &T) -> Painted<'_, T> {
+    Painted { style, value, condition }
+}
+
+/// Paints values when its output stream supports styling.
+///
+/// A value passed to `set_override` takes precedence over the painter's
+/// own decision, so a `--color` option applies to every painter.
+///
+/// # Examples
+///
+///     use colorparse::Painter;
+///
+///     let painter = Painter::stderr();
+///     let style = colorparse::parse("bold red").unwrap();
+///     eprintln!("{}: something failed", painter.paint(style, "error"));
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Painter {
+    enabled: bool,
+}
+
+impl Painter {
+    /// Create a painter that styles values if `enabled` is true.
+    pub fn new(enabled: bool) -> Self {
+        Painter { enabled }
+    }
+
+    /// Create a painter that styles values if standard output is a
+    /// terminal.
+    pub fn stdout() -> Self {
+        Self::new(io::stdout().is_terminal())
+    }
+
+    /// Create a painter that styles values if standard error is a
+    /// terminal.
+    pub fn stderr() -> Self {
+        Self::new(io::stderr().is_terminal())
+    }
+
+    /// Return true if values painted now would be styled.
+    pub fn is_enabled(&self) -> bool {
+        enabled_if(self.enabled)
+    }
+
+    /// Display `value` in `style` if this painter is enabled.
+    pub fn paint<'a, T: fmt::Display + ?Sized>(&self, style: Style, value: &'a T) -> Painted<'a, T> {
+        paint_if(self.enabled, style, value)
+    }
+}
+
+/// Write formatted arguments to `out` in `style`, as `paint` displays them.
+///
+/// # Examples
+///
+///     use std::fmt::Write;
+///
+///     # colorparse::set_override(Some(true));
+///     let style = colorparse::parse("green").unwrap();
+///     let mut s = String::from("status: ");
+///     colorparse::write_styled(&mut s, &style, format_args!("{} ok", 3)).unwrap();
+///     assert_eq!(s, "status: \x1b[32m3 ok\x1b[0m");
+pub fn write_styled<W: fmt::Write + ?Sized>(out: &mut W, style: &Style, args: fmt::Arguments) -> fmt::Result {
+    write!(out, "{}", paint(*style, &args))
+}
+
+/// Write formatted arguments to the byte stream `out` in `style`, as
+/// `paint` displays them.
+pub fn write_styled_io<W: io::Write + ?Sized>(out: &mut W, style: &Style, args: fmt::Arguments) -> io::Result<()> {
+    write!(out, "{}", paint(*style, &args))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ansi_term::Color::*;
+    use crate::global::lock_override;
+    use crate::set_override;
+
+    #[test]
+    fn test_paint() {
+        let _lock = lock_override();
+        set_override(Some(true));
+        assert_eq!(paint(Style::new(), "plain").to_string(), "plain");
+        assert_eq!(paint(Red.normal(), "red").to_string(), Red.paint("red").to_string());
+        assert_eq!(paint(Red.on(Blue).underline(), "x").to_string(), "\x1b[4;31;44mx\x1b[0m");
+        assert_eq!(paint(Fixed(208).bold(), &1.5).to_string(), "\x1b[1;38;5;208m1.5\x1b[0m");
+        assert_eq!(format!("[{:<4.2}]", paint(Green.normal(), "abcdef")), "[\x1b[32mab  \x1b[0m]");
+
+        let painted = paint(Red.bold(), "value");
+        assert_eq!(painted.style(), Red.bold());
+        assert_eq!(painted.value(), "value");
+        set_override(None);
+    }
+
+    #[test]
+    fn test_override() {
+        let _lock = lock_override();
+        let style = Red.bold();
+        set_override(Some(false));
+        assert_eq!(paint(style, "x").to_string(), "x");
+        assert_eq!(Painter::new(true).paint(style, "x").to_string(), "x");
+        assert!(!Painter::new(true).is_enabled());
+        set_override(Some(true));
+        assert_eq!(paint_if(false, style, "x").to_string(), "\x1b[1;31mx\x1b[0m");
+        assert_eq!(Painter::new(false).paint(style, "x").to_string(), "\x1b[1;31mx\x1b[0m");
+        set_override(None);
+        assert_eq!(paint_if(false, style, "x").to_string(), "x");
+        assert_eq!(Painter::new(false).paint(style, "x").to_string(), "x");
+    }
+
+    #[test]
+    fn test_write_styled() {
+        let _lock = lock_override();
+        set_override(Some(true));
+        let mut s = String::new();
+        write_styled(&mut s, &Blue.bold(), format_args!("{}-{}", 1, 2)).unwrap();
+        write_styled(&mut s, &Style::new(), format_args!("!")).unwrap();
+        assert_eq!(s, "\x1b[1;34m1-2\x1b[0m!");
+
+        let mut bytes = Vec::new();
+        write_styled_io(&mut bytes, &Cyan.italic(), format_args!("{:03}", 7)).unwrap();
+        assert_eq!(bytes, b"\x1b[3;36m007\x1b[0m");
+        set_override(None);
+    }
+}
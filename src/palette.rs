@@ -0,0 +1,129 @@
+use ansi_term::{Color, Style};
+
+use crate::downgrade::{downgrade_with, to_rgb};
+use crate::ColorLevel;
+
+/// The RGB values a terminal displays for its 16 basic colors and for its
+/// default foreground and background.
+///
+/// Terminals let users configure these colors, so the crate assumes the
+/// VGA palette (`Palette::default()`) unless given a real one, such as one
+/// read from a terminal theme file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Palette {
+    /// Colors 0 through 15: black, red, green, yellow, blue, magenta,
+    /// cyan, and white, followed by their bright variants.
+    pub ansi: [(u8, u8, u8); 16],
+    /// The default foreground color.
+    pub foreground: (u8, u8, u8),
+    /// The default background color.
+    pub background: (u8, u8, u8),
+}
+
+impl Palette {
+    /// The VGA text-mode palette.
+    pub const VGA: Palette = Palette {
+        ansi: [
+            (0, 0, 0),
+            (170, 0, 0),
+            (0, 170, 0),
+            (170, 85, 0),
+            (0, 0, 170),
+            (170, 0, 170),
+            (0, 170, 170),
+            (170, 170, 170),
+            (85, 85, 85),
+            (255, 85, 85),
+            (85, 255, 85),
+            (255, 255, 85),
+            (85, 85, 255),
+            (255, 85, 255),
+            (85, 255, 255),
+            (255, 255, 255),
+        ],
+        foreground: (170, 170, 170),
+        background: (0, 0, 0),
+    };
+
+    /// Return the RGB value this palette displays for `color`, using the
+    /// xterm palette for colors 16 through 255.
+    pub fn rgb(&self, color: Color) -> (u8, u8, u8) {
+        to_rgb(color, &self.ansi)
+    }
+
+    /// Convert `color` to the nearest color displayable at `level`, as
+    /// `downgrade` does, but matching against this palette's basic colors.
+    pub fn downgrade(&self, color: Color, level: ColorLevel) -> Color {
+        downgrade_with(color, level, self)
+    }
+
+    /// Convert the colors of `style` to the nearest colors displayable at
+    /// `level`, matching against this palette's basic colors.
+    pub fn downgrade_style(&self, style: Style, level: ColorLevel) -> Style {
+        Style {
+            foreground: style.foreground.map(|c| self.downgrade(c, level)),
+            background: style.background.map(|c| self.downgrade(c, level)),
+            ..style
+        }
+    }
+
+    fn relative_luminance(&self, color: Color) -> f64 {
+        let (r, g, b) = self.rgb(color);
+        let channel = |c: u8| {
+            let c = f64::from(c) / 255.0;
+            if c <= 0.03928 { c / 12.92 } else { ((c + 0.055) / 1.055).powf(2.4) }
+        };
+        0.2126 * channel(r) + 0.7152 * channel(g) + 0.0722 * channel(b)
+    }
+
+    /// Return the WCAG contrast ratio between two colors as displayed with
+    /// this palette, from 1 (identical luminance) to 21 (black against
+    /// white).
+    pub fn contrast_ratio(&self, a: Color, b: Color) -> f64 {
+        let (a, b) = (self.relative_luminance(a), self.relative_luminance(b));
+        let (light, dark) = if a > b { (a, b) } else { (b, a) };
+        (light + 0.05) / (dark + 0.05)
+    }
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Palette::VGA
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ansi_term::Color::*;
+
+    #[test]
+    fn test_rgb() {
+        let mut palette = Palette::default();
+        palette.ansi[1] = (0xcd, 0, 0);
+        assert_eq!(palette.rgb(Red), (0xcd, 0, 0));
+        assert_eq!(palette.rgb(Fixed(1)), (0xcd, 0, 0));
+        assert_eq!(palette.rgb(Fixed(9)), (255, 85, 85));
+        assert_eq!(palette.rgb(Fixed(196)), (255, 0, 0));
+        assert_eq!(palette.rgb(Fixed(232)), (8, 8, 8));
+        assert_eq!(palette.rgb(RGB(1, 2, 3)), (1, 2, 3));
+    }
+
+    #[test]
+    fn test_downgrade() {
+        let mut palette = Palette::default();
+        assert_eq!(palette.downgrade(RGB(0xff, 0, 0), ColorLevel::Ansi16), Red);
+        palette.ansi[9] = (0xff, 0, 0);
+        assert_eq!(palette.downgrade(RGB(0xff, 0, 0), ColorLevel::Ansi16), Fixed(9));
+        assert_eq!(palette.downgrade_style(RGB(0xff, 0, 0).on(Fixed(196)).bold(), ColorLevel::Ansi16),
+                   Fixed(9).on(Fixed(9)).bold());
+    }
+
+    #[test]
+    fn test_contrast_ratio() {
+        let palette = Palette::default();
+        assert!((palette.contrast_ratio(Black, Fixed(15)) - 21.0).abs() < 1e-9);
+        assert!((palette.contrast_ratio(RGB(1, 2, 3), RGB(1, 2, 3)) - 1.0).abs() < 1e-9);
+        assert_eq!(palette.contrast_ratio(Red, Blue), palette.contrast_ratio(Blue, Red));
+    }
+}
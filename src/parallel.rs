@@ -0,0 +1,38 @@
+use ansi_term::Style;
+use rayon::prelude::*;
+
+use crate::{parse_with, Error, ParseOptions};
+
+/// Parse many style strings across the threads of the rayon thread pool,
+/// returning the result for each string in the same order as `specs`.
+///
+/// A string that fails to parse yields its own error in its own position,
+/// without affecting the others.
+///
+/// # Examples
+///
+///     use colorparse::{parse, parse_many_par, ParseOptions};
+///
+///     let specs = ["bold red", "blu", "ul 208"];
+///     let results = parse_many_par(&specs, &ParseOptions::new());
+///     assert_eq!(results[0], parse("bold red"));
+///     assert!(results[1].is_err());
+///     assert_eq!(results[2], parse("ul 208"));
+pub fn parse_many_par<S: AsRef<str> + Sync>(specs: &[S], options: &ParseOptions) -> Vec<Result<Style, Error>> {
+    specs.par_iter().map(|spec| parse_with(spec.as_ref(), options)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_many_par() {
+        let specs: Vec<String> = (0..1000)
+            .map(|n| if n % 7 == 0 { format!("bad{}", n) } else { format!("bold {}", n % 256) })
+            .collect();
+        let options = ParseOptions::new();
+        let sequential: Vec<_> = specs.iter().map(|spec| parse_with(spec, &options)).collect();
+        assert_eq!(parse_many_par(&specs, &options), sequential);
+    }
+}
@@ -0,0 +1,165 @@
+use ansi_term::{Color, Style};
+
+use crate::{ColorLevel, Palette};
+
+/// How a `Pulse` moves between its endpoints over time.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Easing {
+    /// At a constant rate.
+    Linear,
+    /// Slowly at first, then faster.
+    EaseIn,
+    /// Quickly at first, then slower.
+    EaseOut,
+    /// Slowly at both ends, and faster in between.
+    EaseInOut,
+}
+
+impl Easing {
+    fn apply(self, t: f64) -> f64 {
+        match self {
+            Easing::Linear => t,
+            Easing::EaseIn => t * t,
+            Easing::EaseOut => t * (2.0 - t),
+            Easing::EaseInOut => t * t * (3.0 - 2.0 * t),
+        }
+    }
+}
+
+/// A cyclic animation between two styles, such as a color pulse for a
+/// spinner or an attention effect.
+///
+/// Each cycle moves from the first style to the second and back. Colors
+/// are blended in RGB, using the palette for the basic colors, and then
+/// downgraded to the color level; attributes, and colors set on only one
+/// side, switch halfway.
+///
+/// # Examples
+///
+///     use colorparse::{ColorLevel, Easing, Pulse};
+///
+///     let from = colorparse::parse("#000000").unwrap();
+///     let to = colorparse::parse("bold #ff0000").unwrap();
+///     let frames = Pulse::new(from, to).frames(4).easing(Easing::Linear).cycle();
+///     assert_eq!(frames, [
+///         colorparse::parse("#000000").unwrap(),
+///         colorparse::parse("bold #800000").unwrap(),
+///         colorparse::parse("bold #ff0000").unwrap(),
+///         colorparse::parse("bold #800000").unwrap(),
+///     ]);
+///
+///     let ansi = Pulse::new(from, to).color_level(ColorLevel::Ansi256).cycle();
+///     assert!(ansi.iter().all(|style| colorparse::StyleExt::requires(style) <= ColorLevel::Ansi256));
+#[derive(Clone, Debug, PartialEq)]
+pub struct Pulse {
+    from: Style,
+    to: Style,
+    frames: usize,
+    easing: Easing,
+    level: ColorLevel,
+    palette: Palette,
+}
+
+impl Pulse {
+    /// Create a pulse between `from` and `to`, with 16 frames per cycle,
+    /// `Easing::EaseInOut`, 24-bit color, and the default palette.
+    pub fn new(from: Style, to: Style) -> Self {
+        Pulse {
+            from,
+            to,
+            frames: 16,
+            easing: Easing::EaseInOut,
+            level: ColorLevel::TrueColor,
+            palette: Palette::default(),
+        }
+    }
+
+    /// Use `frames` frames per cycle, at least 1.
+    pub fn frames(mut self, frames: usize) -> Self {
+        self.frames = frames.max(1);
+        self
+    }
+
+    /// Move between the endpoints according to `easing`.
+    pub fn easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+
+    /// Downgrade each frame to colors displayable at `level`.
+    pub fn color_level(mut self, level: ColorLevel) -> Self {
+        self.level = level;
+        self
+    }
+
+    /// Blend the basic colors as displayed with `palette`.
+    pub fn palette(mut self, palette: Palette) -> Self {
+        self.palette = palette;
+        self
+    }
+
+    fn blend(&self, a: Option<Color>, b: Option<Color>, t: f64) -> Option<Color> {
+        match (a, b) {
+            _ if t <= 0.0 => a,
+            _ if t >= 1.0 => b,
+            (Some(a), Some(b)) if a != b => {
+                let ((r1, g1, b1), (r2, g2, b2)) = (self.palette.rgb(a), self.palette.rgb(b));
+                let mix = |x: u8, y: u8| (f64::from(x) + (f64::from(y) - f64::from(x)) * t).round() as u8;
+                Some(Color::RGB(mix(r1, r2), mix(g1, g2), mix(b1, b2)))
+            }
+            _ => if t < 0.5 { a } else { b },
+        }
+    }
+
+    /// Return frame `i` of the cycle; frames repeat every cycle.
+    pub fn frame(&self, i: usize) -> Style {
+        let phase = 2.0 * (i % self.frames) as f64 / self.frames as f64;
+        let t = self.easing.apply(if phase <= 1.0 { phase } else { 2.0 - phase });
+        let style = Style {
+            foreground: self.blend(self.from.foreground, self.to.foreground, t),
+            background: self.blend(self.from.background, self.to.background, t),
+            ..if t < 0.5 { self.from } else { self.to }
+        };
+        self.palette.downgrade_style(style, self.level)
+    }
+
+    /// Return the frames of one cycle.
+    pub fn cycle(&self) -> Vec<Style> {
+        (0..self.frames).map(|i| self.frame(i)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ansi_term::Color::*;
+
+    #[test]
+    fn test_easing() {
+        for &easing in [Easing::Linear, Easing::EaseIn, Easing::EaseOut, Easing::EaseInOut].iter() {
+            assert_eq!(easing.apply(0.0), 0.0);
+            assert_eq!(easing.apply(1.0), 1.0);
+        }
+        assert_eq!(Easing::EaseIn.apply(0.5), 0.25);
+        assert_eq!(Easing::EaseOut.apply(0.5), 0.75);
+        assert_eq!(Easing::EaseInOut.apply(0.5), 0.5);
+    }
+
+    #[test]
+    fn test_pulse() {
+        let pulse = Pulse::new(Black.normal(), Red.on(Blue).underline()).frames(4).easing(Easing::Linear);
+        assert_eq!(pulse.cycle(), [
+            Black.normal(),
+            RGB(85, 0, 0).on(Blue).underline(),
+            Red.on(Blue).underline(),
+            RGB(85, 0, 0).on(Blue).underline(),
+        ]);
+        assert_eq!(pulse.frame(5), pulse.frame(1));
+        assert_eq!(Pulse::new(Red.normal(), Red.bold()).frames(2).cycle(), [Red.normal(), Red.bold()]);
+        assert_eq!(Pulse::new(Red.normal(), Blue.normal()).frames(0).cycle(), [Red.normal()]);
+        let ansi16 = Pulse::new(Black.normal(), RGB(255, 255, 255).normal()).frames(4).color_level(ColorLevel::Ansi16);
+        assert_eq!(ansi16.cycle(), [Black.normal(), White.normal(), Fixed(15).normal(), White.normal()]);
+        assert_eq!(Pulse::new(Black.normal(), White.normal()).palette(Palette { ansi: [(0, 0, 0); 16], ..Palette::VGA })
+                       .frames(4).easing(Easing::Linear).frame(1), RGB(0, 0, 0).normal());
+    }
+}
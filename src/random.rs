@@ -0,0 +1,209 @@
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+
+use ansi_term::{Color, Style};
+
+use crate::downgrade::from_index;
+use crate::{Attribute, ColorLevel, Palette};
+
+/// How many candidate styles `random_style` tries before settling for the
+/// highest-contrast one.
+const ATTEMPTS: usize = 1000;
+
+/// A small, seedable pseudo-random number generator for `random_style`.
+///
+/// The same seed always produces the same sequence of styles, which keeps
+/// test fixtures and demos reproducible.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StyleRng {
+    state: u64,
+}
+
+impl StyleRng {
+    /// Create a generator from `seed`.
+    pub fn new(seed: u64) -> Self {
+        StyleRng { state: seed }
+    }
+
+    /// Create a generator with a seed that differs on every call.
+    pub fn from_entropy() -> Self {
+        Self::new(RandomState::new().build_hasher().finish())
+    }
+
+    /// Return the next value, using SplitMix64.
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    pub(crate) fn next_u8(&mut self) -> u8 {
+        (self.next_u64() >> 56) as u8
+    }
+
+    /// Return a value below `n`, which must not be zero.
+    #[cfg(feature = "testing")]
+    pub(crate) fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+}
+
+/// Constraints on the styles produced by `random_style`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Constraints {
+    attributes: Vec<Attribute>,
+    min_contrast: f64,
+    level: ColorLevel,
+    background: bool,
+}
+
+impl Constraints {
+    /// Create the default constraints: any attribute, any 24-bit
+    /// foreground and background, and no minimum contrast.
+    pub fn new() -> Self {
+        Constraints {
+            attributes: Attribute::ALL.to_vec(),
+            min_contrast: 1.0,
+            level: ColorLevel::TrueColor,
+            background: true,
+        }
+    }
+
+    /// Only enable attributes from `attributes`.
+    pub fn attributes(mut self, attributes: &[Attribute]) -> Self {
+        self.attributes = attributes.to_vec();
+        self
+    }
+
+    /// Require a WCAG contrast ratio of at least `ratio` (from 1 to 21)
+    /// between the foreground and background.
+    pub fn min_contrast(mut self, ratio: f64) -> Self {
+        self.min_contrast = ratio;
+        self
+    }
+
+    /// Only use colors displayable at `level`.
+    pub fn color_level(mut self, level: ColorLevel) -> Self {
+        self.level = level;
+        self
+    }
+
+    /// Whether to set a background color; if not, the minimum contrast
+    /// does not apply.
+    pub fn background(mut self, background: bool) -> Self {
+        self.background = background;
+        self
+    }
+}
+
+impl Default for Constraints {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub(crate) fn random_color(level: ColorLevel, rng: &mut StyleRng) -> Color {
+    match level {
+        ColorLevel::Ansi16 => from_index(rng.next_u8() % 16),
+        ColorLevel::Ansi256 => match rng.next_u8() {
+            n if n < 16 => from_index(n),
+            n => Color::Fixed(n),
+        },
+        ColorLevel::TrueColor => Color::RGB(rng.next_u8(), rng.next_u8(), rng.next_u8()),
+    }
+}
+
+/// Generate a random style satisfying `constraints`, drawing randomness
+/// from `rng`.
+///
+/// Each allowed attribute is enabled with a probability of one in four. If
+/// no candidate meets the minimum contrast after many attempts, returns the
+/// highest-contrast candidate found.
+///
+/// # Examples
+///
+///     use colorparse::{random_style, ColorLevel, Constraints, StyleExt, StyleRng};
+///
+///     let constraints = Constraints::new()
+///         .attributes(&[])
+///         .min_contrast(4.5)
+///         .color_level(ColorLevel::Ansi256);
+///     let style = random_style(&constraints, &mut StyleRng::new(42));
+///     assert!(style.requires() <= ColorLevel::Ansi256);
+///     assert_eq!(style, random_style(&constraints, &mut StyleRng::new(42)));
+pub fn random_style(constraints: &Constraints, rng: &mut StyleRng) -> Style {
+    let palette = Palette::default();
+    let mut best: Option<(f64, Style)> = None;
+    for _ in 0..ATTEMPTS {
+        let fg = random_color(constraints.level, rng);
+        let mut style = fg.normal();
+        let mut contrast = f64::INFINITY;
+        if constraints.background {
+            let bg = random_color(constraints.level, rng);
+            style = style.on(bg);
+            contrast = palette.contrast_ratio(fg, bg);
+        }
+        for &attribute in &constraints.attributes {
+            if rng.next_u8() < 64 {
+                style = attribute.apply(style);
+            }
+        }
+        if contrast >= constraints.min_contrast {
+            return style;
+        }
+        if best.is_none_or(|(c, _)| contrast > c) {
+            best = Some((contrast, style));
+        }
+    }
+    best.unwrap().1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::StyleExt;
+
+    #[test]
+    fn test_seeded() {
+        let constraints = Constraints::new();
+        let mut a = StyleRng::new(1);
+        let mut b = StyleRng::new(1);
+        for _ in 0..10 {
+            assert_eq!(random_style(&constraints, &mut a), random_style(&constraints, &mut b));
+        }
+        assert_ne!(random_style(&constraints, &mut StyleRng::new(1)),
+                   random_style(&constraints, &mut StyleRng::new(2)));
+    }
+
+    #[test]
+    fn test_constraints() {
+        let mut rng = StyleRng::new(7);
+        let constraints = Constraints::new()
+            .attributes(&[Attribute::Bold, Attribute::Underline])
+            .min_contrast(7.0)
+            .color_level(ColorLevel::Ansi16);
+        for _ in 0..100 {
+            let style = random_style(&constraints, &mut rng);
+            assert_eq!(style.requires(), ColorLevel::Ansi16);
+            let contrast = Palette::default().contrast_ratio(style.foreground.unwrap(), style.background.unwrap());
+            assert!(contrast >= 7.0);
+            for &attribute in &Attribute::ALL[..] {
+                if attribute != Attribute::Bold && attribute != Attribute::Underline {
+                    assert!(!attribute.is_set(&style));
+                }
+            }
+        }
+
+        let constraints = Constraints::new().attributes(&[]).background(false).min_contrast(100.0);
+        let style = random_style(&constraints, &mut rng);
+        assert_eq!(style.background, None);
+        assert_eq!(style, Style { foreground: style.foreground, ..Style::new() });
+
+        let constraints = Constraints::new().min_contrast(100.0);
+        let style = random_style(&constraints, &mut rng);
+        let contrast = Palette::default().contrast_ratio(style.foreground.unwrap(), style.background.unwrap());
+        assert!(contrast > 10.0);
+    }
+}
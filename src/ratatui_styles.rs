@@ -0,0 +1,144 @@
+//! Parsing Git-syntax specs into `ratatui` styles.
+//!
+//! TUI apps built on `ratatui` often let users theme their widgets. This
+//! module converts a `StyleSpec` into a `ratatui::style::Style`, which,
+//! like a spec, can both add and remove modifiers, so a Git-syntax spec
+//! such as `nobold` switches off bold in the style it is patched onto.
+//!
+//! # Examples
+//!
+//!     # extern crate colorparse;
+//!     # extern crate ratatui;
+//!     use ratatui::style::{Color, Modifier, Style};
+//!
+//!     # fn main() {
+//!     let style = colorparse::ratatui_styles::parse("bold red blue").unwrap();
+//!     assert_eq!(style, Style::new().fg(Color::Red).bg(Color::Blue).add_modifier(Modifier::BOLD));
+//!
+//!     let patched = style.patch(colorparse::ratatui_styles::parse("nobold ul").unwrap());
+//!     assert_eq!(patched, Style::new().fg(Color::Red).bg(Color::Blue)
+//!                                     .add_modifier(Modifier::UNDERLINED).remove_modifier(Modifier::BOLD));
+//!     # }
+
+use ansi_term::Color;
+use ratatui::style::{Color as RatatuiColor, Modifier, Style as RatatuiStyle};
+
+use crate::style_builder::build;
+use crate::{parse_spec, Attribute, ColorValue, Error, StyleBuilder, StyleSpec};
+
+/// The `ratatui::style::Color` for each of colors 0 through 15.
+const ANSI: [RatatuiColor; 16] = [
+    RatatuiColor::Black, RatatuiColor::Red, RatatuiColor::Green, RatatuiColor::Yellow,
+    RatatuiColor::Blue, RatatuiColor::Magenta, RatatuiColor::Cyan, RatatuiColor::Gray,
+    RatatuiColor::DarkGray, RatatuiColor::LightRed, RatatuiColor::LightGreen, RatatuiColor::LightYellow,
+    RatatuiColor::LightBlue, RatatuiColor::LightMagenta, RatatuiColor::LightCyan, RatatuiColor::White,
+];
+
+/// Convert `color` to the `ratatui::style::Color` that gives the same
+/// color, with colors 0–15 as the named colors, so that `white` and `7`
+/// become `Gray` and `15` becomes `White`.
+pub fn to_ratatui_color(color: Color) -> RatatuiColor {
+    match color {
+        Color::Black => RatatuiColor::Black,
+        Color::Red => RatatuiColor::Red,
+        Color::Green => RatatuiColor::Green,
+        Color::Yellow => RatatuiColor::Yellow,
+        Color::Blue => RatatuiColor::Blue,
+        Color::Purple => RatatuiColor::Magenta,
+        Color::Cyan => RatatuiColor::Cyan,
+        Color::White => RatatuiColor::Gray,
+        Color::Fixed(n) if n < 16 => ANSI[n as usize],
+        Color::Fixed(n) => RatatuiColor::Indexed(n),
+        Color::RGB(r, g, b) => RatatuiColor::Rgb(r, g, b),
+    }
+}
+
+/// Return the `ratatui::style::Modifier` for `attribute`, or `None` for
+/// overline, which `ratatui` cannot show.
+fn modifier(attribute: Attribute) -> Option<Modifier> {
+    Some(match attribute {
+        Attribute::Bold => Modifier::BOLD,
+        Attribute::Dimmed => Modifier::DIM,
+        Attribute::Italic => Modifier::ITALIC,
+        Attribute::Underline => Modifier::UNDERLINED,
+        Attribute::Blink => Modifier::SLOW_BLINK,
+        Attribute::RapidBlink => Modifier::RAPID_BLINK,
+        Attribute::Reverse => Modifier::REVERSED,
+        Attribute::Hidden => Modifier::HIDDEN,
+        Attribute::Strikethrough => Modifier::CROSSED_OUT,
+        Attribute::Overline => return None,
+    })
+}
+
+/// Parse a string in Git's color configuration syntax, as `parse_spec`
+/// does, into a `ratatui::style::Style`.
+pub fn parse(s: &str) -> Result<RatatuiStyle, Error> {
+    parse_spec(s).map(RatatuiStyle::from)
+}
+
+/// Builds a `ratatui::style::Style`, with cleared attributes as removed
+/// modifiers, a `default` color as `Color::Reset`, and a reset as
+/// `Style::reset`.
+impl StyleBuilder for RatatuiStyle {
+    fn set_fg(&mut self, color: ColorValue) {
+        self.fg = Some(to_ratatui_color(color.into()));
+    }
+
+    fn set_bg(&mut self, color: ColorValue) {
+        self.bg = Some(to_ratatui_color(color.into()));
+    }
+
+    fn set_attr(&mut self, attribute: Attribute) {
+        if let Some(modifier) = modifier(attribute) {
+            *self = self.add_modifier(modifier);
+        }
+    }
+
+    fn clear_attr(&mut self, attribute: Attribute) {
+        if let Some(modifier) = modifier(attribute) {
+            *self = self.remove_modifier(modifier);
+        }
+    }
+
+    fn set_default_fg(&mut self) {
+        self.fg = Some(RatatuiColor::Reset);
+    }
+
+    fn set_default_bg(&mut self) {
+        self.bg = Some(RatatuiColor::Reset);
+    }
+
+    fn reset(&mut self) {
+        *self = RatatuiStyle::reset();
+    }
+}
+
+/// Converts to a `ratatui::style::Style`, as `StyleBuilder` builds it,
+/// dropping the underline color and shape and overline.
+impl From<StyleSpec> for RatatuiStyle {
+    fn from(spec: StyleSpec) -> Self {
+        build(&spec)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let style = RatatuiStyle::new;
+        assert_eq!(parse("ul dim 208 #102030"),
+                   Ok(style().fg(RatatuiColor::Indexed(208)).bg(RatatuiColor::Rgb(16, 32, 48)).add_modifier(Modifier::UNDERLINED | Modifier::DIM)));
+        assert_eq!(parse("conceal strike blink rapidblink reverse italic magenta 15"),
+                   Ok(style().fg(RatatuiColor::Magenta).bg(RatatuiColor::White).add_modifier(
+                       Modifier::ITALIC | Modifier::SLOW_BLINK | Modifier::RAPID_BLINK | Modifier::REVERSED
+                       | Modifier::HIDDEN | Modifier::CROSSED_OUT)));
+        assert_eq!(parse("default brightblack nobold noul"),
+                   Ok(style().fg(RatatuiColor::Reset).bg(RatatuiColor::DarkGray).remove_modifier(Modifier::BOLD | Modifier::UNDERLINED)));
+        assert_eq!(parse("reset cyan"), Ok(RatatuiStyle::reset().fg(RatatuiColor::Cyan)));
+        assert_eq!(parse("overline curly-ul ul=red white"), Ok(style().fg(RatatuiColor::Gray).add_modifier(Modifier::UNDERLINED)));
+        assert_eq!(parse(""), Ok(style()));
+        assert!(parse("bold sparkly").is_err());
+    }
+}
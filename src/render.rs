@@ -0,0 +1,515 @@
+use std::fmt::Write;
+
+use ansi_term::{Color, Style};
+
+use crate::downgrade::index;
+use crate::{Attribute, StyleSpec, UnderlineStyle};
+
+/// A toggleable attribute, the SGR parameter that enables the attribute,
+/// and the SGR parameter that disables it.
+type Toggle = (Attribute, u8, u8);
+
+/// Bold and dim share SGR 22 to disable, as blink and rapid blink share
+/// SGR 25, so disabling one of a pair disables the other as well.
+pub(crate) const ATTRIBUTES: [Toggle; 10] = [
+    (Attribute::Bold, 1, 22),
+    (Attribute::Dimmed, 2, 22),
+    (Attribute::Italic, 3, 23),
+    (Attribute::Underline, 4, 24),
+    (Attribute::Blink, 5, 25),
+    (Attribute::RapidBlink, 6, 25),
+    (Attribute::Reverse, 7, 27),
+    (Attribute::Hidden, 8, 28),
+    (Attribute::Strikethrough, 9, 29),
+    (Attribute::Overline, 53, 55),
+];
+
+fn color_params<P: Extend<u8>>(color: Color, base: u8, params: &mut P) {
+    match color {
+        Color::Black => params.extend(Some(base)),
+        Color::Red => params.extend(Some(base + 1)),
+        Color::Green => params.extend(Some(base + 2)),
+        Color::Yellow => params.extend(Some(base + 3)),
+        Color::Blue => params.extend(Some(base + 4)),
+        Color::Purple => params.extend(Some(base + 5)),
+        Color::Cyan => params.extend(Some(base + 6)),
+        Color::White => params.extend(Some(base + 7)),
+        Color::Fixed(n) => params.extend([base + 8, 5, n].iter().cloned()),
+        Color::RGB(r, g, b) => params.extend([base + 8, 2, r, g, b].iter().cloned()),
+    }
+}
+
+/// Append the parameters that set the underline color, which has no
+/// short codes for the basic colors.
+fn underline_color_params<P: Extend<u8>>(color: Color, params: &mut P) {
+    match (index(color), color) {
+        (Some(n), _) => params.extend([58, 5, n].iter().cloned()),
+        (None, Color::RGB(r, g, b)) => params.extend([58, 2, r, g, b].iter().cloned()),
+        (None, _) => unreachable!(),
+    }
+}
+
+/// Append the parameters that apply `spec` on top of the plain style.
+pub(crate) fn spec_params<P: Extend<u8>>(spec: &StyleSpec, params: &mut P) {
+    for &(attribute, on, _) in &ATTRIBUTES {
+        if spec.has(attribute) { params.extend(Some(on)); }
+    }
+    if let Some(fg) = spec.foreground() { color_params(fg, 30, params); }
+    if let Some(bg) = spec.background() { color_params(bg, 40, params); }
+    if let Some(ul) = spec.underline_color() { underline_color_params(ul, params); }
+}
+
+/// Append the parameters that apply `style` on top of the plain style.
+pub(crate) fn style_params<P: Extend<u8>>(style: &Style, params: &mut P) {
+    spec_params(&StyleSpec::from(*style), params)
+}
+
+/// A fixed-capacity buffer of SGR parameters, large enough for the
+/// parameters of any single style.
+struct ParamBuf {
+    params: [u8; 25],
+    len: usize,
+}
+
+impl Extend<u8> for ParamBuf {
+    fn extend<I: IntoIterator<Item = u8>>(&mut self, iter: I) {
+        for param in iter {
+            self.params[self.len] = param;
+            self.len += 1;
+        }
+    }
+}
+
+/// Return the parameters that switch `from` into `to` by changing only
+/// what differs between them.
+fn incremental_params(from: &StyleSpec, to: &StyleSpec) -> Vec<u8> {
+    let mut params = Vec::new();
+    let disabled: Vec<u8> = ATTRIBUTES.iter()
+        .filter(|&&(attribute, _, _)| from.has(attribute) && !to.has(attribute))
+        .map(|&(_, _, off)| off)
+        .collect();
+    for &(attribute, on, off) in &ATTRIBUTES {
+        // Only attribute parameters precede this point, and none of them
+        // enables one attribute while disabling another, so this finds
+        // whether a shared `off` was already sent.
+        if disabled.contains(&off) && !params.contains(&off) {
+            params.push(off);
+        }
+        let single_again = attribute == Attribute::Underline
+            && from.underline_style() != UnderlineStyle::Single
+            && to.underline_style() == UnderlineStyle::Single;
+        if to.has(attribute) && (!from.has(attribute) || disabled.contains(&off) || single_again) {
+            params.push(on);
+        }
+    }
+    if from.foreground() != to.foreground() {
+        match to.foreground() {
+            Some(fg) => color_params(fg, 30, &mut params),
+            None => params.push(39),
+        }
+    }
+    if from.background() != to.background() {
+        match to.background() {
+            Some(bg) => color_params(bg, 40, &mut params),
+            None => params.push(49),
+        }
+    }
+    if from.underline_color() != to.underline_color() {
+        match to.underline_color() {
+            Some(ul) => underline_color_params(ul, &mut params),
+            None => params.push(59),
+        }
+    }
+    params
+}
+
+/// Return the spec with the same appearance as `spec` when applied on top
+/// of the plain style, without its cleared attributes or reset.
+pub(crate) fn appearance(spec: &StyleSpec) -> StyleSpec {
+    let mut builder = StyleSpec::builder();
+    builder.spec.foreground = spec.foreground();
+    builder.spec.background = spec.background();
+    builder.spec.underline_color = spec.underline_color();
+    let mut builder = spec.attributes().fold(builder, |builder, a| builder.attribute(a));
+    builder.spec.underline_style = spec.underline_style();
+    builder.build()
+}
+
+fn encoded_len(params: &[u8]) -> usize {
+    params.iter().map(|p| p.to_string().len() + 1).sum()
+}
+
+fn transition_params(from: &StyleSpec, to: &StyleSpec) -> Vec<u8> {
+    let (from, to) = (&appearance(from), &appearance(to));
+    if from == to {
+        return Vec::new();
+    }
+    let incremental = incremental_params(from, to);
+    let mut reset = vec![0];
+    spec_params(to, &mut reset);
+    if encoded_len(&reset) < encoded_len(&incremental) {
+        reset
+    } else {
+        incremental
+    }
+}
+
+/// Return the underline shape to select after `params` switch the terminal
+/// from `from` into `to`, if `to` has a shape other than a single line that
+/// the terminal does not already show.
+fn underline_style_change(from: &StyleSpec, to: &StyleSpec, params: &[u8]) -> Option<UnderlineStyle> {
+    let style = to.underline_style();
+    let shown = from.has(Attribute::Underline) && from.underline_style() == style && params.first() != Some(&0);
+    if to.has(Attribute::Underline) && style != UnderlineStyle::Single && !shown {
+        Some(style)
+    } else {
+        None
+    }
+}
+
+/// Append the escape sequence for `params` to `out`, or nothing if
+/// `params` is empty.
+pub(crate) fn write_sgr<W: Write>(out: &mut W, params: &[u8]) -> std::fmt::Result {
+    if params.is_empty() {
+        return Ok(());
+    }
+    out.write_str("\x1b[")?;
+    for (i, param) in params.iter().enumerate() {
+        if i > 0 {
+            out.write_char(';')?;
+        }
+        write!(out, "{}", param)?;
+    }
+    out.write_char('m')
+}
+
+/// Append the escape sequence that applies `style` on top of the plain
+/// style to `out`, or nothing if `style` is plain, without allocating.
+pub(crate) fn write_style<W: Write>(out: &mut W, style: &Style) -> std::fmt::Result {
+    let mut buf = ParamBuf { params: [0; 25], len: 0 };
+    style_params(style, &mut buf);
+    write_sgr(out, &buf.params[..buf.len])
+}
+
+/// Append the color parameters of Git's escape sequences, which write
+/// colors 0–7 with the short codes of the named colors, and colors 8–15,
+/// the bright colors, with the aixterm codes 90–97 and 100–107.
+fn git_color_params<P: Extend<u8>>(color: Color, base: u8, params: &mut P) {
+    match color {
+        Color::Fixed(n) if n < 8 => params.extend(Some(base + n)),
+        Color::Fixed(n) if n < 16 => params.extend(Some(base + 60 + (n - 8))),
+        _ => color_params(color, base, params),
+    }
+}
+
+/// Return the escape sequence that starts text in the style of `spec`, as
+/// Git writes it, or an empty string for an empty spec, for writing
+/// straight to a terminal without `ansi_term`.
+///
+/// The sequence has the attributes the spec sets, then those it clears,
+/// then the foreground and background, with SGR 39 and 49 for a default
+/// color, and the underline color. Colors 0–15 use the short codes, as
+/// in `ESC [ 91 m` for `brightred`. A reset comes first as an empty
+/// parameter, as in `ESC [ ; 1 m` for `reset bold`. An underline shape
+/// follows in a sequence of its own, as in `transition_spec`.
+///
+/// # Examples
+///
+///     use colorparse::{parse_spec, prefix_sequence, suffix_sequence};
+///
+///     let spec = parse_spec("bold nodim default #ff0000 overline").unwrap();
+///     assert_eq!(prefix_sequence(&spec), "\x1b[1;53;22;39;48;2;255;0;0m");
+///     assert_eq!(suffix_sequence(&spec), "\x1b[0m");
+///     assert_eq!(prefix_sequence(&parse_spec("reset").unwrap()), "\x1b[m");
+pub fn prefix_sequence(spec: &StyleSpec) -> String {
+    let mut params = Vec::new();
+    for &(attribute, on, _) in &ATTRIBUTES {
+        if spec.has(attribute) { params.push(on); }
+    }
+    for &(attribute, _, off) in &ATTRIBUTES {
+        if spec.clears(attribute) && !params.contains(&off) { params.push(off); }
+    }
+    match spec.foreground() {
+        Some(fg) => git_color_params(fg, 30, &mut params),
+        None if spec.is_default_fg() => params.push(39),
+        None => {}
+    }
+    match spec.background() {
+        Some(bg) => git_color_params(bg, 40, &mut params),
+        None if spec.is_default_bg() => params.push(49),
+        None => {}
+    }
+    if let Some(ul) = spec.underline_color() { underline_color_params(ul, &mut params); }
+    let mut out = String::new();
+    if spec.is_reset() {
+        out.push_str("\x1b[");
+        for param in &params {
+            write!(out, ";{}", param).unwrap();
+        }
+        out.push('m');
+    } else {
+        write_sgr(&mut out, &params).unwrap();
+    }
+    if spec.has(Attribute::Underline) && spec.underline_style() != UnderlineStyle::Single {
+        write!(out, "\x1b[4:{}m", spec.underline_style().sgr_subparameter()).unwrap();
+    }
+    out
+}
+
+/// Return the escape sequence that ends text started with `prefix_sequence`,
+/// resetting the terminal, or an empty string if the prefix is empty.
+pub fn suffix_sequence(spec: &StyleSpec) -> String {
+    if prefix_sequence(spec).is_empty() {
+        String::new()
+    } else {
+        "\x1b[0m".to_string()
+    }
+}
+
+/// The SGR parameters that switch the terminal from one style to another,
+/// as computed by `transition`.
+///
+/// Its `Display` implementation writes the complete escape sequence, or
+/// nothing if no change is needed. An underline shape, which needs an SGR
+/// subparameter, follows in a sequence of its own, such as `ESC [ 4:3 m`
+/// for a curly underline, so that terminals which ignore it still draw a
+/// plain underline.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Transition {
+    params: Vec<u8>,
+    underline_style: Option<UnderlineStyle>,
+}
+
+impl Transition {
+    /// Return the SGR parameters, in the order they should be sent, not
+    /// including the underline shape.
+    pub fn params(&self) -> &[u8] {
+        &self.params
+    }
+
+    /// Return the underline shape to select after the parameters, if the
+    /// shape changes to one other than `UnderlineStyle::Single`.
+    pub fn underline_style(&self) -> Option<UnderlineStyle> {
+        self.underline_style
+    }
+
+    /// Return true if the two styles look the same, so no escape sequence
+    /// is needed.
+    pub fn is_empty(&self) -> bool {
+        self.params.is_empty() && self.underline_style.is_none()
+    }
+}
+
+impl std::fmt::Display for Transition {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write_sgr(fmt, &self.params)?;
+        match self.underline_style {
+            Some(style) => write!(fmt, "\x1b[4:{}m", style.sgr_subparameter()),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Compute the fewest SGR parameters that switch the terminal from style
+/// `from` into style `to`: either the incremental changes between them,
+/// or a reset followed by `to` in full, whichever encodes shorter.
+///
+/// # Examples
+///
+///     use colorparse::{parse, transition};
+///
+///     let from = parse("bold red blue").unwrap();
+///     let to = parse("bold green blue ul").unwrap();
+///     let t = transition(&from, &to);
+///     assert_eq!(t.params(), &[4, 32]);
+///     assert_eq!(t.to_string(), "\x1b[4;32m");
+pub fn transition(from: &Style, to: &Style) -> Transition {
+    transition_spec(&StyleSpec::from(*from), &StyleSpec::from(*to))
+}
+
+/// Compute the fewest SGR parameters that switch the terminal from spec
+/// `from` into spec `to`, as `transition` does for styles, including the
+/// underline color (SGR 58 to set it, and 59 to restore the default) and
+/// the underline shape (SGR `4:n`).
+///
+/// Each spec is taken as the complete appearance of the text, so the
+/// attributes it clears and any reset make no difference.
+///
+/// # Examples
+///
+///     # extern crate ansi_term;
+///     # extern crate colorparse;
+///     use ansi_term::Color::{Blue, Red};
+///     use colorparse::{transition_spec, StyleSpec};
+///
+///     # fn main() {
+///     let plain = StyleSpec::new();
+///     let curly = StyleSpec::builder().underline().underline_color(Red).build();
+///     assert_eq!(transition_spec(&plain, &curly).to_string(), "\x1b[4;58;5;1m");
+///     let blue = StyleSpec::builder().underline().underline_color(Blue).build();
+///     assert_eq!(transition_spec(&curly, &blue).params(), &[58, 5, 4]);
+///     let plain_ul = StyleSpec::builder().underline().build();
+///     assert_eq!(transition_spec(&blue, &plain_ul).params(), &[59]);
+///     # }
+pub fn transition_spec(from: &StyleSpec, to: &StyleSpec) -> Transition {
+    let params = transition_params(from, to);
+    let underline_style = underline_style_change(&appearance(from), &appearance(to), &params);
+    Transition { params, underline_style }
+}
+
+/// Render a sequence of styled text segments, emitting only the escape
+/// sequences needed to move from each segment's style to the next, and a
+/// final reset if the last style is not plain.
+///
+/// # Examples
+///
+///     use colorparse::{parse, render};
+///
+///     let bold_red = parse("bold red").unwrap();
+///     let bold_blue = parse("bold blue").unwrap();
+///     assert_eq!(render(vec![(bold_red, "a"), (bold_blue, "b")]),
+///                "\x1b[1;31ma\x1b[34mb\x1b[0m");
+pub fn render<'a, I>(segments: I) -> String
+where
+    I: IntoIterator<Item = (Style, &'a str)>,
+{
+    let mut out = String::new();
+    let mut current = Style::new();
+    for (style, text) in segments {
+        if text.is_empty() {
+            continue;
+        }
+        write!(out, "{}{}", transition(&current, &style), text).unwrap();
+        current = style;
+    }
+    write!(out, "{}", transition(&current, &Style::new())).unwrap();
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ansi_term::Color::*;
+
+    #[test]
+    fn test_transition_params() {
+        macro_rules! test {
+            ($from:expr => $to:expr, $params:expr) => {
+                let expected: &[u8] = &$params;
+                assert_eq!(transition_params(&StyleSpec::from($from), &StyleSpec::from($to)), expected);
+            };
+        }
+
+        let plain = Style::new();
+        test!(plain => plain, []);
+        test!(plain => Red.bold(), [1, 31]);
+        test!(Red.bold() => plain, [0]);
+        test!(Red.bold() => Blue.bold(), [34]);
+        test!(Red.bold() => Red.dimmed(), [22, 2]);
+        test!(Red.bold().dimmed() => Red.dimmed(), [22, 2]);
+        test!(Red.underline() => Red.italic(), [3, 24]);
+        test!(Red.on(Blue) => Red.normal(), [49]);
+        test!(Red.on(Blue) => Style::new().on(Blue), [39]);
+        test!(Fixed(100).on(RGB(1, 2, 3)) => Fixed(200).on(RGB(1, 2, 3)), [38, 5, 200]);
+        test!(Red.bold().underline().italic().blink() => Green.normal(), [0, 32]);
+        test!(plain => RGB(10, 20, 30).on(Fixed(8)).reverse().hidden().strikethrough(),
+              [7, 8, 9, 38, 2, 10, 20, 30, 48, 5, 8]);
+    }
+
+    #[test]
+    fn test_transition_spec() {
+        let plain = StyleSpec::new();
+        let red_ul = StyleSpec::builder().underline().underline_color(Red).build();
+        let rgb_ul = StyleSpec::builder().fg(Green).underline().underline_color(RGB(1, 2, 3)).build();
+        assert_eq!(transition_spec(&plain, &red_ul).params(), &[4, 58, 5, 1]);
+        assert_eq!(transition_spec(&red_ul, &rgb_ul).params(), &[32, 58, 2, 1, 2, 3]);
+        assert_eq!(transition_spec(&rgb_ul, &plain).params(), &[0]);
+        assert_eq!(transition_spec(&red_ul, &StyleSpec::builder().underline().build()).params(), &[59]);
+        let cleared = StyleSpec::builder().no(crate::Attribute::Bold).reset().underline().underline_color(Red).build();
+        assert!(transition_spec(&red_ul, &cleared).is_empty());
+    }
+
+    #[test]
+    fn test_prefix_sequence() {
+        macro_rules! test {
+            ($s:expr => $sgr:expr) => {
+                assert_eq!(prefix_sequence(&crate::parse_spec($s).unwrap()), $sgr, "{}", $s);
+            };
+        }
+
+        test!("" => "");
+        test!("normal" => "");
+        test!("reset bold 1" => "\x1b[;1;31m");
+        test!("nobold nodim noblink norapidblink" => "\x1b[22;25m");
+        test!("7 9" => "\x1b[37;101m");
+        test!("brightblack 15 ul=9" => "\x1b[90;107;58;5;9m");
+        test!("bold default 0" => "\x1b[1;39;40m");
+        test!("default default" => "\x1b[39;49m");
+        test!("ul=208 overline" => "\x1b[53;58;5;208m");
+        test!("curly-ul #010203" => "\x1b[4;38;2;1;2;3m\x1b[4:3m");
+        assert_eq!(suffix_sequence(&crate::parse_spec("nobold").unwrap()), "\x1b[0m");
+        assert_eq!(suffix_sequence(&StyleSpec::new()), "");
+    }
+
+    #[test]
+    fn test_rapid_blink() {
+        let rapid = StyleSpec::builder().rapid_blink().build();
+        let both = StyleSpec::builder().blink().rapid_blink().bold().build();
+        assert_eq!(transition_spec(&StyleSpec::new(), &rapid).params(), &[6]);
+        assert_eq!(transition_spec(&both, &rapid).params(), &[0, 6]);
+        assert_eq!(transition_spec(&both, &StyleSpec::builder().bold().rapid_blink().build()).params(),
+                   &[25, 6]);
+        assert_eq!(transition_spec(&rapid, &StyleSpec::builder().blink().build()).params(), &[0, 5]);
+        assert_eq!(transition_spec(&rapid, &both).params(), &[1, 5]);
+    }
+
+    #[test]
+    fn test_overline() {
+        let overline = StyleSpec::builder().overline().fg(Red).build();
+        assert_eq!(transition_spec(&StyleSpec::new(), &overline).params(), &[53, 31]);
+        assert_eq!(transition_spec(&overline, &StyleSpec::builder().fg(Red).build()).params(), &[55]);
+    }
+
+    #[test]
+    fn test_transition_underline_style() {
+        let plain = StyleSpec::new();
+        let ul = StyleSpec::builder().underline().build();
+        let curly = StyleSpec::builder().underline_style(UnderlineStyle::Curly).build();
+        let dotted = StyleSpec::builder().fg(Red).underline_style(UnderlineStyle::Dotted).build();
+        assert_eq!(transition_spec(&plain, &curly).to_string(), "\x1b[4m\x1b[4:3m");
+        assert_eq!(transition_spec(&ul, &curly).to_string(), "\x1b[4:3m");
+        assert_eq!(transition_spec(&curly, &ul).to_string(), "\x1b[4m");
+        assert_eq!(transition_spec(&curly, &dotted).to_string(), "\x1b[31m\x1b[4:4m");
+        assert_eq!(transition_spec(&curly, &plain).to_string(), "\x1b[0m");
+        assert!(transition_spec(&curly, &curly).is_empty());
+        let t = transition_spec(&StyleSpec::builder().bold().italic().blink().underline_style(UnderlineStyle::Double).build(),
+                                &StyleSpec::builder().underline_style(UnderlineStyle::Double).build());
+        assert_eq!(t.params(), &[0, 4]);
+        assert_eq!(t.underline_style(), Some(UnderlineStyle::Double));
+    }
+
+    #[test]
+    fn test_transition() {
+        let t = transition(&Red.normal(), &Red.normal());
+        assert!(t.is_empty());
+        assert_eq!(t.to_string(), "");
+
+        let t = transition(&Red.bold(), &Fixed(100).on(RGB(1, 2, 3)).bold());
+        assert!(!t.is_empty());
+        assert_eq!(t.params(), &[38, 5, 100, 48, 2, 1, 2, 3]);
+        assert_eq!(t.to_string(), "\x1b[38;5;100;48;2;1;2;3m");
+    }
+
+    #[test]
+    fn test_render() {
+        assert_eq!(render(vec![]), "");
+        assert_eq!(render(vec![(Style::new(), "plain")]), "plain");
+        assert_eq!(render(vec![(Red.normal(), "a"), (Red.normal(), "b")]), "\x1b[31mab\x1b[0m");
+        assert_eq!(render(vec![(Red.normal(), "a"), (Blue.normal(), ""), (Red.bold(), "b")]),
+                   "\x1b[31ma\x1b[1mb\x1b[0m");
+        assert_eq!(render(vec![(Red.underline(), "a"), (Style::new(), " "), (Red.underline(), "b")]),
+                   "\x1b[4;31ma\x1b[0m \x1b[4;31mb\x1b[0m");
+        assert_eq!(render(vec![(Red.on(Blue).bold(), "a"), (Red.bold(), "b"), (Blue.bold(), "c")]),
+                   "\x1b[1;31;44ma\x1b[49mb\x1b[34mc\x1b[0m");
+    }
+}
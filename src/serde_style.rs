@@ -0,0 +1,109 @@
+//! Serialization of styles as strings in Git's color syntax.
+//!
+//! With the `serde` feature, `StyleSpec` and `StyleString` serialize as
+//! the string their `Display` writes, and deserialize from any string
+//! their `FromStr` accepts. For a field typed as `ansi_term::Style`, use
+//! this module with `#[serde(with = "colorparse::serde_style")]`:
+//!
+//!     # extern crate ansi_term;
+//!     # extern crate colorparse;
+//!     # #[macro_use] extern crate serde;
+//!     #[derive(Deserialize, Serialize)]
+//!     struct Config {
+//!         #[serde(with = "colorparse::serde_style")]
+//!         error: ansi_term::Style,
+//!     }
+//!
+//!     # fn main() {
+//!     # extern crate serde_json;
+//!     let config: Config = serde_json::from_str(r#"{"error": "bold red"}"#).unwrap();
+//!     assert_eq!(config.error, colorparse::parse("bold red").unwrap());
+//!     # }
+
+use std::fmt;
+
+use ansi_term::Style;
+use serde::de::{self, Deserializer, Visitor};
+use serde::ser::Serializer;
+use serde::{Deserialize, Serialize};
+
+use crate::{parse, parse_spec, Error, StyleSpec, StyleString};
+
+/// A visitor for a string, which it parses with the function it holds,
+/// expecting what the description it holds describes.
+struct StrVisitor<F>(F, &'static str);
+
+impl<'de, T, F: FnOnce(&str) -> Result<T, Error>> Visitor<'de> for StrVisitor<F> {
+    type Value = T;
+
+    fn expecting(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.write_str(self.1)
+    }
+
+    fn visit_str<E: de::Error>(self, s: &str) -> Result<T, E> {
+        (self.0)(s).map_err(E::custom)
+    }
+}
+
+/// Serialize `style` as a string in Git syntax, as `to_config_string`
+/// writes it.
+pub fn serialize<S: Serializer>(style: &Style, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(&StyleSpec::from(*style))
+}
+
+/// Deserialize a style from a string in Git syntax, with `parse`.
+pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Style, D::Error> {
+    deserializer.deserialize_str(StrVisitor(parse, "a color in Git syntax"))
+}
+
+impl Serialize for StyleSpec {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for StyleSpec {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_str(StrVisitor(parse_spec, "a color in Git syntax"))
+    }
+}
+
+impl Serialize for StyleString {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize(&self.0, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for StyleString {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize(deserializer).map(StyleString)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ansi_term::Color::*;
+    use crate::UnderlineStyle;
+
+    #[test]
+    fn test_serde_style() {
+        let mut json = Vec::new();
+        serialize(&Red.on(Blue).bold(), &mut serde_json::Serializer::new(&mut json)).unwrap();
+        assert_eq!(json, b"\"bold red blue\"");
+        assert_eq!(deserialize(&mut serde_json::Deserializer::from_str("\"ul 208\"")).ok(), Some(Fixed(208).underline()));
+        let err = deserialize(&mut serde_json::Deserializer::from_str("\"bold blu\"")).unwrap_err();
+        assert!(err.to_string().starts_with(&parse("bold blu").unwrap_err().to_string()), "{}", err);
+        assert!(deserialize(&mut serde_json::Deserializer::from_str("1")).is_err());
+    }
+
+    #[test]
+    fn test_serde_spec() {
+        let spec = StyleSpec::builder().underline_style(UnderlineStyle::Curly).underline_color(Red).build();
+        assert_eq!(serde_json::to_string(&spec).unwrap(), "\"curly-ul ul=red\"");
+        assert_eq!(serde_json::from_str::<StyleSpec>("\"Curly-UL ul=red\"").unwrap(), spec);
+        assert_eq!(serde_json::to_string(&StyleString(Green.italic())).unwrap(), "\"italic green\"");
+        assert_eq!(serde_json::from_str::<StyleString>("\" italic  green \"").unwrap(), StyleString(Green.italic()));
+        assert!(serde_json::from_str::<StyleString>("\"red blue green\"").is_err());
+    }
+}
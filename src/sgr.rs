@@ -0,0 +1,150 @@
+use ansi_term::Color;
+
+use crate::downgrade::from_index;
+use crate::render::ATTRIBUTES;
+use crate::{Attribute, Error, ErrorKind, StyleBuilder, StyleSpec, UnderlineStyle};
+
+/// Read the color following an extended color parameter (38 or 48):
+/// `5;n` or `2;r;g;b`.
+fn extended_color<'a, I: Iterator<Item = &'a str>>(params: &mut I) -> Option<Color> {
+    let mut next = || params.next()?.parse::<u8>().ok();
+    match next()? {
+        5 => Some(Color::Fixed(next()?)),
+        2 => Some(Color::RGB(next()?, next()?, next()?)),
+        _ => None,
+    }
+}
+
+/// Read the color in the subparameters following an extended color
+/// parameter, as in `38:5:n`, `38:2:r:g:b`, or `38:2::r:g:b` with the
+/// color space left out.
+fn colon_color(sub: &[&str]) -> Option<Color> {
+    let n = |s: &str| s.parse::<u8>().ok();
+    match *sub {
+        ["5", i] => Some(Color::Fixed(n(i)?)),
+        ["2", r, g, b] | ["2", _, r, g, b] => Some(Color::RGB(n(r)?, n(g)?, n(b)?)),
+        _ => None,
+    }
+}
+
+/// Parse a list of numeric SGR parameters, such as `1;4;31` from
+/// `ESC [ 1 ; 4 ; 31 m`, into the spec they apply.
+///
+/// Parameters take effect in order, so a later one overrides an earlier
+/// one, and 0, or an empty parameter, is a reset that discards everything
+/// before it. The parameters that disable attributes, such as 22, clear
+/// them in the spec, and 39 and 49 select the default colors. Colors may
+/// be given as 30–37 and 40–47, the bright 90–97 and 100–107, or the
+/// extended `38;5;n` and `38;2;r;g;b`, also with colons as in `38:5:n`,
+/// and likewise 48 for the background and 58 for the underline. The
+/// underline shape comes from the subparameter of `4:n`, and 21 is a
+/// double underline.
+///
+/// # Examples
+///
+///     # extern crate ansi_term;
+///     # extern crate colorparse;
+///     use ansi_term::Color::{Fixed, Red};
+///     use colorparse::{parse_sgr, StyleSpec};
+///
+///     # fn main() {
+///     assert_eq!(parse_sgr("1;4;31"), Ok(StyleSpec::builder().bold().underline().fg(Red).build()));
+///     assert_eq!(parse_sgr("22;48;5;208").unwrap().to_string(), "nobold nodim normal 208");
+///     assert_eq!(parse_sgr("38;5").unwrap_err().word(), "38");
+///     # }
+pub fn parse_sgr(s: &str) -> Result<StyleSpec, Error> {
+    let mut spec = StyleSpec::new();
+    apply_sgr(&mut spec, s)?;
+    Ok(spec)
+}
+
+/// Apply the SGR parameters in `s` to `spec`, as `parse_sgr` reads them.
+/// On error, `spec` may be left with some of the parameters applied.
+pub(crate) fn apply_sgr(spec: &mut StyleSpec, s: &str) -> Result<(), Error> {
+    let mut params = s.split(';');
+    while let Some(param) = params.next() {
+        let invalid = || Error::at(ErrorKind::InvalidSgr, s, param);
+        let mut parts = param.split(':');
+        let n = match parts.next() {
+            Some("") | None => 0,
+            Some(n) => n.parse::<u8>().map_err(|_| invalid())?,
+        };
+        let sub: Vec<&str> = parts.collect();
+        let mut color = || if sub.is_empty() { extended_color(&mut params) } else { colon_color(&sub) };
+        match n {
+            4 if !sub.is_empty() => match *sub {
+                ["0"] => spec.clear_attr(Attribute::Underline),
+                [k] => match UnderlineStyle::ALL.iter().find(|style| style.sgr_subparameter().to_string() == k) {
+                    Some(&style) => spec.set_underline_style(style),
+                    None => return Err(invalid()),
+                },
+                _ => return Err(invalid()),
+            },
+            38 => spec.set_fg(color().ok_or_else(invalid)?.into()),
+            48 => spec.set_bg(color().ok_or_else(invalid)?.into()),
+            58 => spec.set_underline_color(color().ok_or_else(invalid)?.into()),
+            _ if !sub.is_empty() => return Err(invalid()),
+            0 => spec.reset(),
+            21 => spec.set_underline_style(UnderlineStyle::Double),
+            30..=37 => spec.set_fg(from_index(n - 30).into()),
+            39 => spec.set_default_fg(),
+            40..=47 => spec.set_bg(from_index(n - 40).into()),
+            49 => spec.set_default_bg(),
+            59 => spec.underline_color = None,
+            90..=97 => spec.set_fg(Color::Fixed(n - 90 + 8).into()),
+            100..=107 => spec.set_bg(Color::Fixed(n - 100 + 8).into()),
+            _ => match ATTRIBUTES.iter().find(|&&(_, on, _)| on == n) {
+                Some(&(attribute, _, _)) => spec.set_attr(attribute),
+                None if ATTRIBUTES.iter().any(|&(_, _, off)| off == n) => {
+                    for &(attribute, _, off) in &ATTRIBUTES {
+                        if off == n { spec.clear_attr(attribute); }
+                    }
+                }
+                None => return Err(invalid()),
+            },
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_sgr() {
+        macro_rules! test {
+            ($s:expr => $spec:expr) => {
+                assert_eq!(parse_sgr($s), crate::parse_spec($spec), "{}", $s);
+            };
+        }
+
+        test!("" => "reset");
+        test!("1;4;31" => "bold ul red");
+        test!("01;034;42" => "bold blue green");
+        test!("91;104;53;6" => "9 12 overline rapidblink");
+        test!("38;5;208;48;2;1;2;3" => "208 #010203");
+        test!("38:5:208;48:2::1:2:3;58:2:4:5:6" => "208 #010203 ul=#040506");
+        test!("31;1;0;2" => "reset dim");
+        test!("22;25;29;55" => "nobold nodim noblink nostrike nooverline norapidblink");
+        test!("1;22" => "nobold nodim");
+        test!("39;49" => "default default");
+        test!("31;39" => "default");
+        test!("4:3;58;5;1;59" => "curly-ul");
+        test!("21" => "double-ul");
+        test!("4;4:0" => "noul");
+    }
+
+    #[test]
+    fn test_parse_sgr_invalid() {
+        for &(s, word) in &[("x", "x"), ("1;256", "256"), ("38;5", "38"), ("38;3;1", "38"), ("1:2", "1:2"),
+                            ("4:9", "4:9"), ("38:5:1:2", "38:5:1:2"), ("12", "12"), ("1;;x", "x")] {
+            let err = parse_sgr(s).unwrap_err();
+            assert_eq!((err.kind(), err.word()), (&ErrorKind::InvalidSgr, word), "{}", s);
+        }
+        let err = parse_sgr("1;2;60").unwrap_err();
+        assert_eq!(err.span(), 4..6);
+        assert_eq!(err.to_string(), "Error parsing SGR parameters \"1;2;60\": invalid parameter \"60\"");
+        assert_eq!(err.to_compact_string(), "CP0008 invalid-sgr input=\"1;2;60\" word=\"60\"");
+    }
+}
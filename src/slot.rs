@@ -0,0 +1,318 @@
+use std::fmt;
+use std::str::FromStr;
+
+use ansi_term::Style;
+
+use crate::suggest::closest;
+
+macro_rules! slots {
+    ($($(#[doc = $doc:expr])* $variant:ident($default:expr) => $name:expr $(, $alias:expr)*;)*) => {
+        /// A color slot documented by Git, named by its config key without
+        /// the leading `color.`.
+        ///
+        /// Parsing with `FromStr` ignores case, accepts an optional `color.`
+        /// prefix, and accepts Git's older aliases for a few slots, such as
+        /// `diff.plain` for `diff.context`. `Display` writes the documented
+        /// spelling.
+        ///
+        /// # Examples
+        ///
+        ///     use colorparse::SlotName;
+        ///
+        ///     let slot: SlotName = "color.diff.oldmoved".parse().unwrap();
+        ///     assert_eq!(slot, SlotName::DiffOldMoved);
+        ///     assert_eq!(slot.to_string(), "diff.oldMoved");
+        ///
+        ///     assert_eq!(slot.default_spec(), "bold magenta");
+        ///
+        ///     let err = "diff.olf".parse::<SlotName>().unwrap_err();
+        ///     assert_eq!(err.suggestion(), Some(SlotName::DiffOld));
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub enum SlotName {
+            $($(#[doc = $doc])* $variant,)*
+        }
+
+        impl SlotName {
+            /// Every documented slot.
+            pub const ALL: &'static [SlotName] = &[$(SlotName::$variant,)*];
+
+            /// Return the documented config key for this slot, without
+            /// the leading `color.`.
+            pub fn name(self) -> &'static str {
+                match self {
+                    $(SlotName::$variant => $name,)*
+                }
+            }
+
+            /// Return Git's default style for this slot, in Git's syntax,
+            /// which Git uses when the slot is not configured; the empty
+            /// string is the plain style.
+            pub fn default_spec(self) -> &'static str {
+                match self {
+                    $(SlotName::$variant => $default,)*
+                }
+            }
+
+            fn aliases(self) -> &'static [&'static str] {
+                match self {
+                    $(SlotName::$variant => &[$($alias),*],)*
+                }
+            }
+        }
+    };
+}
+
+slots! {
+    /// The current branch, in `git branch`.
+    BranchCurrent("green") => "branch.current";
+    /// A local branch.
+    BranchLocal("") => "branch.local";
+    /// A remote-tracking branch.
+    BranchRemote("red") => "branch.remote";
+    /// An upstream tracking branch.
+    BranchUpstream("blue") => "branch.upstream";
+    /// Any other ref.
+    BranchPlain("") => "branch.plain";
+
+    /// Context lines in a diff.
+    DiffContext("") => "diff.context", "diff.plain";
+    /// Metainformation in a diff.
+    DiffMeta("bold") => "diff.meta";
+    /// Hunk headers in a diff.
+    DiffFrag("cyan") => "diff.frag";
+    /// The function in a hunk header.
+    DiffFunc("") => "diff.func";
+    /// Removed lines.
+    DiffOld("red") => "diff.old";
+    /// Added lines.
+    DiffNew("green") => "diff.new";
+    /// Commit headers.
+    DiffCommit("yellow") => "diff.commit";
+    /// Whitespace errors.
+    DiffWhitespace("normal red") => "diff.whitespace";
+    /// Removed lines that were moved elsewhere.
+    DiffOldMoved("bold magenta") => "diff.oldMoved";
+    /// Added lines that were moved from elsewhere.
+    DiffNewMoved("bold cyan") => "diff.newMoved";
+    /// Dimmed removed moved lines.
+    DiffOldMovedDimmed("dim") => "diff.oldMovedDimmed";
+    /// Removed moved lines in an alternating block.
+    DiffOldMovedAlternative("bold blue") => "diff.oldMovedAlternative";
+    /// Dimmed removed moved lines in an alternating block.
+    DiffOldMovedAlternativeDimmed("dim italic") => "diff.oldMovedAlternativeDimmed";
+    /// Dimmed added moved lines.
+    DiffNewMovedDimmed("dim") => "diff.newMovedDimmed";
+    /// Added moved lines in an alternating block.
+    DiffNewMovedAlternative("bold yellow") => "diff.newMovedAlternative";
+    /// Dimmed added moved lines in an alternating block.
+    DiffNewMovedAlternativeDimmed("dim italic") => "diff.newMovedAlternativeDimmed";
+    /// Context lines in a range diff that differ in the outer diff only.
+    DiffContextDimmed("dim") => "diff.contextDimmed";
+    /// Removed lines in a range diff that differ in the outer diff only.
+    DiffOldDimmed("dim red") => "diff.oldDimmed";
+    /// Added lines in a range diff that differ in the outer diff only.
+    DiffNewDimmed("dim green") => "diff.newDimmed";
+    /// Context lines in a range diff that changed between the ranges.
+    DiffContextBold("bold") => "diff.contextBold";
+    /// Removed lines in a range diff that changed between the ranges.
+    DiffOldBold("bold red") => "diff.oldBold";
+    /// Added lines in a range diff that changed between the ranges.
+    DiffNewBold("bold green") => "diff.newBold";
+
+    /// Local branches in `git log --decorate`.
+    DecorateBranch("bold green") => "decorate.branch";
+    /// Remote-tracking branches.
+    DecorateRemoteBranch("bold red") => "decorate.remoteBranch";
+    /// Tags.
+    DecorateTag("bold yellow") => "decorate.tag";
+    /// The stash.
+    DecorateStash("bold magenta") => "decorate.stash";
+    /// `HEAD`.
+    DecorateHead("bold cyan") => "decorate.HEAD";
+    /// Grafted commits.
+    DecorateGrafted("bold blue") => "decorate.grafted";
+
+    /// Non-matching text in selected lines, in `git grep`.
+    GrepContext("") => "grep.context";
+    /// File names.
+    GrepFilename("magenta") => "grep.filename";
+    /// Function name lines.
+    GrepFunction("") => "grep.function";
+    /// Line numbers.
+    GrepLineNumber("green") => "grep.lineNumber";
+    /// Column numbers.
+    GrepColumn("green") => "grep.column";
+    /// Matching text in all lines.
+    GrepMatch("bold red") => "grep.match";
+    /// Matching text in context lines.
+    GrepMatchContext("bold red") => "grep.matchContext";
+    /// Matching text in selected lines.
+    GrepMatchSelected("bold red") => "grep.matchSelected";
+    /// Non-matching text in selected lines.
+    GrepSelected("") => "grep.selected";
+    /// Separators between fields and hunks.
+    GrepSeparator("cyan") => "grep.separator";
+
+    /// Prompts, in interactive commands.
+    InteractivePrompt("bold blue") => "interactive.prompt";
+    /// Headers.
+    InteractiveHeader("bold") => "interactive.header";
+    /// Help text.
+    InteractiveHelp("bold red") => "interactive.help";
+    /// Errors.
+    InteractiveError("bold red") => "interactive.error";
+
+    /// The header text of `git status`.
+    StatusHeader("") => "status.header";
+    /// Added files.
+    StatusAdded("green") => "status.added", "status.updated";
+    /// Changed but not added files.
+    StatusChanged("red") => "status.changed";
+    /// Untracked files.
+    StatusUntracked("red") => "status.untracked";
+    /// The current branch.
+    StatusBranch("green") => "status.branch";
+    /// The warning shown when not on a branch.
+    StatusNoBranch("red") => "status.nobranch";
+    /// The local branch in the short format.
+    StatusLocalBranch("green") => "status.localBranch";
+    /// The remote-tracking branch in the short format.
+    StatusRemoteBranch("red") => "status.remoteBranch";
+    /// Unmerged files.
+    StatusUnmerged("red") => "status.unmerged";
+
+    /// Lines repeated from the previous line, in `git blame`.
+    BlameRepeatedLines("cyan") => "blame.repeatedLines";
+    /// Push errors.
+    PushError("red") => "push.error";
+    /// Hints from the remote.
+    RemoteHint("yellow") => "remote.hint";
+    /// Warnings from the remote.
+    RemoteWarning("bold yellow") => "remote.warning";
+    /// Success messages from the remote.
+    RemoteSuccess("bold green") => "remote.success";
+    /// Errors from the remote.
+    RemoteError("bold red") => "remote.error";
+    /// Rejected pushes.
+    TransportRejected("red") => "transport.rejected";
+    /// Advice hints.
+    AdviceHint("yellow") => "advice.hint";
+}
+
+impl SlotName {
+    /// Return Git's default style for this slot, as `default_spec` gives
+    /// it, for falling back to when a configuration leaves the slot out.
+    ///
+    /// # Examples
+    ///
+    ///     # extern crate ansi_term;
+    ///     # extern crate colorparse;
+    ///     use ansi_term::Color::Green;
+    ///     use colorparse::{SlotName, Theme};
+    ///
+    ///     # fn main() {
+    ///     let theme = Theme::new();
+    ///     let slot = SlotName::DiffNew;
+    ///     assert_eq!(theme.get(slot.name()).unwrap_or(slot.default_style()), Green.normal());
+    ///     # }
+    pub fn default_style(self) -> Style {
+        crate::parse(self.default_spec()).unwrap()
+    }
+}
+
+impl fmt::Display for SlotName {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.write_str(self.name())
+    }
+}
+
+impl FromStr for SlotName {
+    type Err = SlotNameError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let key = s.to_lowercase();
+        let key = key.strip_prefix("color.").unwrap_or(&key);
+        let names = |slot: &SlotName| std::iter::once(slot.name()).chain(slot.aliases().iter().cloned());
+        if let Some(&slot) = SlotName::ALL.iter().find(|slot| names(slot).any(|name| name.eq_ignore_ascii_case(key))) {
+            return Ok(slot);
+        }
+        let suggestion = closest(key, SlotName::ALL.iter().map(|slot| slot.name()))
+            .and_then(|name| SlotName::ALL.iter().cloned().find(|slot| slot.name() == name));
+        Err(SlotNameError::UnknownSlot(s.to_string(), suggestion))
+    }
+}
+
+/// Type for errors returned when parsing a `SlotName`.
+#[derive(Debug, PartialEq)]
+pub enum SlotNameError {
+    /// The key does not name a documented Git color slot; carries the
+    /// closest documented slot, if any is close.
+    UnknownSlot(String, Option<SlotName>),
+}
+
+impl SlotNameError {
+    /// Return the documented slot the key was most likely meant to be.
+    pub fn suggestion(&self) -> Option<SlotName> {
+        match self {
+            Self::UnknownSlot(_, suggestion) => *suggestion,
+        }
+    }
+}
+
+impl fmt::Display for SlotNameError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::UnknownSlot(key, None) => write!(fmt, "Unknown color slot \"{}\"", key),
+            Self::UnknownSlot(key, Some(slot)) => {
+                write!(fmt, "Unknown color slot \"{}\"; did you mean \"{}\"?", key, slot)
+            }
+        }
+    }
+}
+
+impl std::error::Error for SlotNameError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::SlotName::*;
+
+    #[test]
+    fn test_parse_slot_name() {
+        assert_eq!("diff.old".parse(), Ok(DiffOld));
+        assert_eq!("color.status.added".parse(), Ok(StatusAdded));
+        assert_eq!("status.updated".parse(), Ok(StatusAdded));
+        assert_eq!("DIFF.PLAIN".parse(), Ok(DiffContext));
+        assert_eq!("decorate.head".parse(), Ok(DecorateHead));
+        for &slot in SlotName::ALL {
+            assert_eq!(slot.name().parse(), Ok(slot));
+            assert_eq!(slot.to_string(), slot.name());
+        }
+    }
+
+    #[test]
+    fn test_default_style() {
+        use ansi_term::Color::*;
+
+        assert_eq!(DiffNew.default_style(), Green.normal());
+        assert_eq!(DiffWhitespace.default_style(), Style::new().on(Red));
+        assert_eq!(DiffOldMovedAlternativeDimmed.default_style(), Style::new().dimmed().italic());
+        assert_eq!(DecorateHead.default_style(), Cyan.bold());
+        assert_eq!(StatusHeader.default_style(), Style::new());
+        assert_eq!(GrepMatch.default_style(), Red.bold());
+        for &slot in SlotName::ALL {
+            assert!(crate::parse(slot.default_spec()).is_ok(), "{}", slot);
+        }
+    }
+
+    #[test]
+    fn test_parse_slot_name_err() {
+        let err = "diff.olf".parse::<SlotName>().unwrap_err();
+        assert_eq!(err, SlotNameError::UnknownSlot("diff.olf".to_string(), Some(DiffOld)));
+        assert_eq!(err.to_string(), "Unknown color slot \"diff.olf\"; did you mean \"diff.old\"?");
+        assert_eq!("color.staus.branch".parse::<SlotName>().unwrap_err().suggestion(), Some(StatusBranch));
+        let err = "pager.log".parse::<SlotName>().unwrap_err();
+        assert_eq!(err.suggestion(), None);
+        assert_eq!(err.to_string(), "Unknown color slot \"pager.log\"");
+    }
+}
@@ -0,0 +1,810 @@
+use std::fmt;
+use std::ops::{Add, AddAssign, Not};
+use std::str::FromStr;
+
+use ansi_term::{Color, Style};
+
+use crate::downgrade::{from_index, index};
+use crate::{parse_spec, Attribute, ColorLevel, Error};
+
+/// A style built in code, independent of any terminal library.
+///
+/// A `StyleSpec` can hold properties that `ansi_term::Style` cannot, such
+/// as an underline color. Its `Display` implementation writes the style in
+/// Git syntax, for storing in a config file, `FromStr` parses it back, and
+/// it converts to an `ansi_term::Style` with `From`.
+///
+/// Besides enabling attributes, a spec can explicitly clear them, as Git's
+/// `nobold` does. Specs combine with `+`, where the right-hand side applies
+/// on top of the left: its colors replace those on the left, its
+/// attributes add to them, and the attributes it clears are removed. `!`
+/// produces the spec that clears every attribute a spec enables.
+///
+/// A spec can also start with a reset, as Git's `reset` does, so that it
+/// replaces whatever it would otherwise combine with.
+///
+/// When the spec enables underline, it also records the shape of the
+/// underline, as an `UnderlineStyle`.
+///
+/// Either color may also be the terminal's default color, as Git's
+/// `default` sets it, which differs from leaving the color unset: it
+/// replaces the color of a spec it combines with, rather than keeping it.
+///
+/// # Examples
+///
+///     # extern crate ansi_term;
+///     # extern crate colorparse;
+///     use ansi_term::Color::{Blue, Red};
+///     use colorparse::StyleSpec;
+///
+///     # fn main() {
+///     let spec = StyleSpec::builder().fg(Red).bg(Blue).bold().build();
+///     assert_eq!(spec.to_string(), "bold red blue");
+///     assert_eq!(ansi_term::Style::from(spec), colorparse::parse("bold red blue").unwrap());
+///
+///     let emphasis = StyleSpec::builder().bold().underline().build();
+///     assert_eq!((spec + emphasis).to_string(), "bold ul red blue");
+///     assert_eq!((spec + emphasis + !emphasis).to_string(), "nobold noul red blue");
+///     # }
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct StyleSpec {
+    pub(crate) foreground: Option<Color>,
+    pub(crate) background: Option<Color>,
+    pub(crate) underline_color: Option<Color>,
+    pub(crate) underline_style: UnderlineStyle,
+    pub(crate) default_foreground: bool,
+    pub(crate) default_background: bool,
+    attributes: u16,
+    cleared: u16,
+    reset: bool,
+}
+
+impl StyleSpec {
+    /// Create a spec with no colors or attributes.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a builder for a spec.
+    pub fn builder() -> StyleSpecBuilder {
+        StyleSpecBuilder::default()
+    }
+
+    /// Return the foreground color, if any.
+    pub fn foreground(&self) -> Option<Color> {
+        self.foreground
+    }
+
+    /// Return the background color, if any.
+    pub fn background(&self) -> Option<Color> {
+        self.background
+    }
+
+    /// Return the underline color, if any.
+    pub fn underline_color(&self) -> Option<Color> {
+        self.underline_color
+    }
+
+    /// Return the shape of the underline, which is `UnderlineStyle::Single`
+    /// unless the spec enables underline with another shape.
+    pub fn underline_style(&self) -> UnderlineStyle {
+        self.underline_style
+    }
+
+    /// Return true if this spec sets the foreground to the terminal's
+    /// default color, in which case `foreground` returns `None`.
+    pub fn is_default_fg(&self) -> bool {
+        self.default_foreground
+    }
+
+    /// Return true if this spec sets the background to the terminal's
+    /// default color, in which case `background` returns `None`.
+    pub fn is_default_bg(&self) -> bool {
+        self.default_background
+    }
+
+    /// Return the foreground color as a `ColorValue`, if any.
+    pub fn fg(&self) -> Option<ColorValue> {
+        self.foreground.map(ColorValue::from)
+    }
+
+    /// Return the background color as a `ColorValue`, if any.
+    pub fn bg(&self) -> Option<ColorValue> {
+        self.background.map(ColorValue::from)
+    }
+
+    /// Return an iterator over the attributes this spec enables, in the
+    /// order of `Attribute::ALL`.
+    ///
+    /// # Examples
+    ///
+    ///     use colorparse::{Attribute, StyleSpec};
+    ///
+    ///     let spec = StyleSpec::from(colorparse::parse("ul bold red").unwrap());
+    ///     let attributes: Vec<Attribute> = spec.attributes().collect();
+    ///     assert_eq!(attributes, [Attribute::Bold, Attribute::Underline]);
+    pub fn attributes(&self) -> impl Iterator<Item = Attribute> {
+        let spec = *self;
+        Attribute::ALL.iter().cloned().filter(move |&a| spec.has(a))
+    }
+
+    /// Return an iterator over the attributes this spec explicitly clears,
+    /// in the order of `Attribute::ALL`.
+    pub fn cleared(&self) -> impl Iterator<Item = Attribute> {
+        let spec = *self;
+        Attribute::ALL.iter().cloned().filter(move |&a| spec.clears(a))
+    }
+
+    /// Return true if this spec enables `attribute`.
+    pub fn has(&self, attribute: Attribute) -> bool {
+        self.attributes & (1 << attribute as u16) != 0
+    }
+
+    /// Return true if this spec explicitly clears `attribute`.
+    pub fn clears(&self, attribute: Attribute) -> bool {
+        self.cleared & (1 << attribute as u16) != 0
+    }
+
+    /// Return true if this spec starts with a reset, clearing every color
+    /// and attribute before applying its own.
+    pub fn is_reset(&self) -> bool {
+        self.reset
+    }
+
+    /// Return a short, single-line description of this spec, such as
+    /// `fg=Red bg=Fixed(8) +bold+ul`, whose format stays the same across
+    /// versions, for snapshot tests.
+    ///
+    /// The colors come first, as `fg=`, `bg=`, and `ul=` for the underline
+    /// color, each written as `Black` through `White` (with `Purple` for
+    /// magenta), `Fixed(n)`, `RGB(r,g,b)`, or `default` for the terminal's
+    /// default color, after `reset` if the spec starts with a reset. Then
+    /// come the enabled attributes, each as `+` and its Git keyword (for
+    /// underline, the keyword of its shape, such as `curly-ul`), and the
+    /// cleared attributes, each as `-` and its keyword. A plain spec gives
+    /// `plain`.
+    ///
+    /// # Examples
+    ///
+    ///     # extern crate ansi_term;
+    ///     # extern crate colorparse;
+    ///     use ansi_term::Color::{Fixed, Red};
+    ///     use colorparse::StyleSpec;
+    ///
+    ///     # fn main() {
+    ///     let spec = StyleSpec::builder().fg(Red).bg(Fixed(8)).bold().underline().build();
+    ///     assert_eq!(spec.to_compact_string(), "fg=Red bg=Fixed(8) +bold+ul");
+    ///     # }
+    pub fn to_compact_string(&self) -> String {
+        if self.is_plain() {
+            return "plain".to_string();
+        }
+        let colors = [
+            ("fg", self.foreground.map(compact_color), self.default_foreground),
+            ("bg", self.background.map(compact_color), self.default_background),
+            ("ul", self.underline_color.map(compact_color), false),
+        ];
+        let reset = if self.reset { Some("reset".to_string()) } else { None };
+        let mut parts: Vec<String> = reset.into_iter()
+            .chain(colors.iter().filter_map(|(name, color, default)| {
+                let color = if *default { Some("default") } else { color.as_deref() };
+                color.map(|color| format!("{}={}", name, color))
+            }))
+            .collect();
+        let attributes: String = self.attributes().map(|a| format!("+{}", self.keyword(a)))
+            .chain(self.cleared().map(|a| format!("-{}", a.name())))
+            .collect();
+        if !attributes.is_empty() {
+            parts.push(attributes);
+        }
+        parts.join(" ")
+    }
+
+    /// Return the spec in Git syntax with its colors first, as `normalize`
+    /// writes it: the reset, foreground, and background, then what
+    /// `Display` writes after them. Colors 0–7 are written by name, so
+    /// that `0` and `black` normalize the same.
+    pub(crate) fn normalized(&self) -> String {
+        let canonical = |color: Option<Color>| color.map(|color| ColorValue::from(color).into());
+        let colors = StyleSpec {
+            foreground: canonical(self.foreground),
+            background: canonical(self.background),
+            default_foreground: self.default_foreground,
+            default_background: self.default_background,
+            reset: self.reset,
+            ..StyleSpec::new()
+        };
+        let rest = StyleSpec {
+            foreground: None,
+            background: None,
+            default_foreground: false,
+            default_background: false,
+            underline_color: canonical(self.underline_color),
+            reset: false,
+            ..*self
+        };
+        match (colors.is_plain(), rest.is_plain()) {
+            (_, true) => colors.to_string(),
+            (true, false) => rest.to_string(),
+            (false, false) => format!("{} {}", colors, rest),
+        }
+    }
+
+    /// Return true if this spec sets no colors or attributes.
+    pub fn is_plain(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Return the keyword that enables `attribute` in this spec.
+    fn keyword(&self, attribute: Attribute) -> &'static str {
+        match attribute {
+            Attribute::Underline => self.underline_style.name(),
+            _ => attribute.name(),
+        }
+    }
+}
+
+/// The shape of an underline. Terminals such as Kitty, WezTerm, and foot
+/// draw the shapes other than `Single` when sent SGR `4:2` through `4:5`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum UnderlineStyle {
+    /// A single straight line (`ul`).
+    #[default]
+    Single,
+    /// Two straight lines (`double-ul`).
+    Double,
+    /// A wavy line (`curly-ul`).
+    Curly,
+    /// A dotted line (`dotted-ul`).
+    Dotted,
+    /// A dashed line (`dashed-ul`).
+    Dashed,
+}
+
+impl UnderlineStyle {
+    /// Every underline style, in the order of their SGR subparameters.
+    pub const ALL: [UnderlineStyle; 5] = [
+        UnderlineStyle::Single,
+        UnderlineStyle::Double,
+        UnderlineStyle::Curly,
+        UnderlineStyle::Dotted,
+        UnderlineStyle::Dashed,
+    ];
+
+    /// Return the keyword that enables underline with this shape, such as
+    /// `curly-ul`.
+    pub fn name(self) -> &'static str {
+        match self {
+            UnderlineStyle::Single => "ul",
+            UnderlineStyle::Double => "double-ul",
+            UnderlineStyle::Curly => "curly-ul",
+            UnderlineStyle::Dotted => "dotted-ul",
+            UnderlineStyle::Dashed => "dashed-ul",
+        }
+    }
+
+    /// Return the subparameter `n` of the SGR `4:n` sequence that selects
+    /// this shape.
+    pub fn sgr_subparameter(self) -> u8 {
+        self as u8 + 1
+    }
+}
+
+/// Return the name of `color` used by `StyleSpec::to_compact_string`.
+fn compact_color(color: Color) -> String {
+    match color {
+        Color::Black => "Black".to_string(),
+        Color::Red => "Red".to_string(),
+        Color::Green => "Green".to_string(),
+        Color::Yellow => "Yellow".to_string(),
+        Color::Blue => "Blue".to_string(),
+        Color::Purple => "Purple".to_string(),
+        Color::Cyan => "Cyan".to_string(),
+        Color::White => "White".to_string(),
+        Color::Fixed(n) => format!("Fixed({})", n),
+        Color::RGB(r, g, b) => format!("RGB({},{},{})", r, g, b),
+    }
+}
+
+/// Write `color` in Git syntax.
+pub(crate) fn write_color(fmt: &mut fmt::Formatter, color: Color) -> fmt::Result {
+    match color {
+        Color::Black => fmt.write_str("black"),
+        Color::Red => fmt.write_str("red"),
+        Color::Green => fmt.write_str("green"),
+        Color::Yellow => fmt.write_str("yellow"),
+        Color::Blue => fmt.write_str("blue"),
+        Color::Purple => fmt.write_str("magenta"),
+        Color::Cyan => fmt.write_str("cyan"),
+        Color::White => fmt.write_str("white"),
+        Color::Fixed(n) => write!(fmt, "{}", n),
+        Color::RGB(r, g, b) => write!(fmt, "#{:02x}{:02x}{:02x}", r, g, b),
+    }
+}
+
+/// Writes the spec in Git syntax: `reset` if the spec starts with a reset,
+/// then attributes, then the foreground and background colors, using
+/// `normal` for an unset foreground before a background and `default` for
+/// the terminal's default color. Cleared attributes are written with a `no`
+/// prefix, the underline with the keyword of its shape, and the underline
+/// color as `ul=<color>`. A plain spec writes as `normal`.
+impl fmt::Display for StyleSpec {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_plain() {
+            return fmt.write_str("normal");
+        }
+        let mut sep = "";
+        if self.reset {
+            fmt.write_str("reset")?;
+            sep = " ";
+        }
+        for attribute in self.attributes() {
+            write!(fmt, "{}{}", sep, self.keyword(attribute))?;
+            sep = " ";
+        }
+        for attribute in self.cleared() {
+            write!(fmt, "{}no{}", sep, attribute.name())?;
+            sep = " ";
+        }
+        let has_background = self.background.is_some() || self.default_background;
+        if self.foreground.is_some() || self.default_foreground || has_background {
+            fmt.write_str(sep)?;
+            match self.foreground {
+                Some(color) => write_color(fmt, color)?,
+                None if self.default_foreground => fmt.write_str("default")?,
+                None => fmt.write_str("normal")?,
+            }
+            if has_background {
+                fmt.write_str(" ")?;
+                match self.background {
+                    Some(color) => write_color(fmt, color)?,
+                    None => fmt.write_str("default")?,
+                }
+            }
+            sep = " ";
+        }
+        if let Some(color) = self.underline_color {
+            write!(fmt, "{}ul=", sep)?;
+            write_color(fmt, color)?;
+        }
+        Ok(())
+    }
+}
+
+/// Parses the string with `parse_spec`.
+impl FromStr for StyleSpec {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_spec(s)
+    }
+}
+
+impl From<Style> for StyleSpec {
+    fn from(style: Style) -> Self {
+        let mut builder = StyleSpec::builder();
+        builder.spec.foreground = style.foreground;
+        builder.spec.background = style.background;
+        for &attribute in Attribute::ALL.iter().filter(|a| a.is_set(&style)) {
+            builder = builder.attribute(attribute);
+        }
+        builder.build()
+    }
+}
+
+impl<'a> From<&'a Style> for StyleSpec {
+    fn from(style: &'a Style) -> Self {
+        StyleSpec::from(*style)
+    }
+}
+
+impl<'a> From<&'a StyleSpec> for StyleSpec {
+    fn from(spec: &'a StyleSpec) -> Self {
+        *spec
+    }
+}
+
+impl Add for StyleSpec {
+    type Output = StyleSpec;
+
+    fn add(self, overlay: StyleSpec) -> StyleSpec {
+        if overlay.reset {
+            return overlay;
+        }
+        let replaces_fg = overlay.foreground.is_some() || overlay.default_foreground;
+        let replaces_bg = overlay.background.is_some() || overlay.default_background;
+        StyleSpec {
+            foreground: if replaces_fg { overlay.foreground } else { self.foreground },
+            background: if replaces_bg { overlay.background } else { self.background },
+            default_foreground: if replaces_fg { overlay.default_foreground } else { self.default_foreground },
+            default_background: if replaces_bg { overlay.default_background } else { self.default_background },
+            underline_color: overlay.underline_color.or(self.underline_color),
+            underline_style: if overlay.has(Attribute::Underline) || overlay.clears(Attribute::Underline) {
+                overlay.underline_style
+            } else {
+                self.underline_style
+            },
+            attributes: (self.attributes & !overlay.cleared) | overlay.attributes,
+            cleared: (self.cleared & !overlay.attributes) | overlay.cleared,
+            reset: self.reset,
+        }
+    }
+}
+
+impl AddAssign for StyleSpec {
+    fn add_assign(&mut self, overlay: StyleSpec) {
+        *self = *self + overlay;
+    }
+}
+
+/// Returns a spec with no colors that clears every attribute this spec
+/// enables, and enables every attribute this spec clears.
+impl Not for StyleSpec {
+    type Output = StyleSpec;
+
+    fn not(self) -> StyleSpec {
+        StyleSpec {
+            attributes: self.cleared,
+            cleared: self.attributes,
+            ..StyleSpec::new()
+        }
+    }
+}
+
+/// Converts to an `ansi_term::Style`, dropping the underline color and
+/// shape, cleared attributes, and reset, and leaving a default color
+/// unset.
+impl From<StyleSpec> for Style {
+    fn from(spec: StyleSpec) -> Self {
+        let style = Style {
+            foreground: spec.foreground,
+            background: spec.background,
+            ..Style::new()
+        };
+        spec.attributes().fold(style, |style, a| a.apply(style))
+    }
+}
+
+/// A color, classified by the range of the palette it comes from.
+///
+/// Unlike `ansi_term::Color`, a `ColorValue` has a single representation
+/// for each of the 16 basic colors, whether written as a name or a number.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ColorValue {
+    /// One of the 16 basic colors, by index: 0–7 for black, red, green,
+    /// yellow, blue, magenta, cyan, and white, and 8–15 for their bright
+    /// variants.
+    Basic(u8),
+    /// A color from the 256-color palette, at index 16 or above.
+    Indexed(u8),
+    /// A 24-bit color.
+    Rgb(u8, u8, u8),
+}
+
+impl ColorValue {
+    /// Return the color level needed to display this color.
+    pub fn level(self) -> ColorLevel {
+        ColorLevel::of(self.into())
+    }
+}
+
+impl From<Color> for ColorValue {
+    fn from(color: Color) -> Self {
+        match (index(color), color) {
+            (Some(n), _) if n < 16 => ColorValue::Basic(n),
+            (Some(n), _) => ColorValue::Indexed(n),
+            (None, Color::RGB(r, g, b)) => ColorValue::Rgb(r, g, b),
+            (None, _) => unreachable!(),
+        }
+    }
+}
+
+/// Converts to an `ansi_term::Color`, using the named colors for basic
+/// colors 0–7 and `Color::Fixed` for the rest of the palette.
+impl From<ColorValue> for Color {
+    fn from(value: ColorValue) -> Self {
+        match value {
+            ColorValue::Basic(n) if n < 16 => from_index(n),
+            ColorValue::Basic(n) | ColorValue::Indexed(n) => Color::Fixed(n),
+            ColorValue::Rgb(r, g, b) => Color::RGB(r, g, b),
+        }
+    }
+}
+
+/// Writes the color in Git syntax.
+impl fmt::Display for ColorValue {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write_color(fmt, (*self).into())
+    }
+}
+
+/// A builder for a `StyleSpec`, created by `StyleSpec::builder`.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct StyleSpecBuilder {
+    pub(crate) spec: StyleSpec,
+}
+
+impl StyleSpecBuilder {
+    /// Set the foreground color.
+    pub fn fg(mut self, color: Color) -> Self {
+        self.spec.foreground = Some(color);
+        self.spec.default_foreground = false;
+        self
+    }
+
+    /// Set the background color.
+    pub fn bg(mut self, color: Color) -> Self {
+        self.spec.background = Some(color);
+        self.spec.default_background = false;
+        self
+    }
+
+    /// Set the foreground to the terminal's default color, as Git's
+    /// `default` does.
+    pub fn default_fg(mut self) -> Self {
+        self.spec.foreground = None;
+        self.spec.default_foreground = true;
+        self
+    }
+
+    /// Set the background to the terminal's default color.
+    pub fn default_bg(mut self) -> Self {
+        self.spec.background = None;
+        self.spec.default_background = true;
+        self
+    }
+
+    /// Set the underline color.
+    pub fn underline_color(mut self, color: Color) -> Self {
+        self.spec.underline_color = Some(color);
+        self
+    }
+
+    /// Enable `attribute`. Enabling underline this way gives a single
+    /// straight line.
+    pub fn attribute(mut self, attribute: Attribute) -> Self {
+        self.spec.attributes |= 1 << attribute as u16;
+        self.spec.cleared &= !(1 << attribute as u16);
+        if attribute == Attribute::Underline {
+            self.spec.underline_style = UnderlineStyle::Single;
+        }
+        self
+    }
+
+    /// Explicitly clear `attribute`, as Git's `no` prefix does.
+    pub fn no(mut self, attribute: Attribute) -> Self {
+        self.spec.cleared |= 1 << attribute as u16;
+        self.spec.attributes &= !(1 << attribute as u16);
+        if attribute == Attribute::Underline {
+            self.spec.underline_style = UnderlineStyle::Single;
+        }
+        self
+    }
+
+    /// Enable bold.
+    pub fn bold(self) -> Self {
+        self.attribute(Attribute::Bold)
+    }
+
+    /// Enable dim.
+    pub fn dimmed(self) -> Self {
+        self.attribute(Attribute::Dimmed)
+    }
+
+    /// Enable italic.
+    pub fn italic(self) -> Self {
+        self.attribute(Attribute::Italic)
+    }
+
+    /// Enable underline.
+    pub fn underline(self) -> Self {
+        self.attribute(Attribute::Underline)
+    }
+
+    /// Enable underline with the shape `style`.
+    pub fn underline_style(self, style: UnderlineStyle) -> Self {
+        let mut builder = self.underline();
+        builder.spec.underline_style = style;
+        builder
+    }
+
+    /// Enable blink.
+    pub fn blink(self) -> Self {
+        self.attribute(Attribute::Blink)
+    }
+
+    /// Enable rapid blink.
+    pub fn rapid_blink(self) -> Self {
+        self.attribute(Attribute::RapidBlink)
+    }
+
+    /// Enable reverse video.
+    pub fn reverse(self) -> Self {
+        self.attribute(Attribute::Reverse)
+    }
+
+    /// Enable hidden text.
+    pub fn hidden(self) -> Self {
+        self.attribute(Attribute::Hidden)
+    }
+
+    /// Enable strikethrough.
+    pub fn strikethrough(self) -> Self {
+        self.attribute(Attribute::Strikethrough)
+    }
+
+    /// Enable overline.
+    pub fn overline(self) -> Self {
+        self.attribute(Attribute::Overline)
+    }
+
+    /// Discard everything set so far, and start the spec with a reset, as
+    /// Git's `reset` does.
+    pub fn reset(mut self) -> Self {
+        self.spec = StyleSpec { reset: true, ..StyleSpec::new() };
+        self
+    }
+
+    /// Return the spec built so far.
+    pub fn build(self) -> StyleSpec {
+        self.spec
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ansi_term::Color::*;
+    use crate::parse;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(StyleSpec::new().to_string(), "normal");
+        assert_eq!(StyleSpec::builder().fg(Purple).build().to_string(), "magenta");
+        assert_eq!(StyleSpec::builder().bg(Fixed(208)).build().to_string(), "normal 208");
+        assert_eq!(StyleSpec::builder().strikethrough().bold().italic().build().to_string(), "bold italic strike");
+        assert_eq!(StyleSpec::builder().fg(RGB(0xff, 0x80, 0)).dimmed().underline().build().to_string(),
+                   "dim ul #ff8000");
+        assert_eq!(StyleSpec::builder().underline().underline_color(Red).build().to_string(), "ul ul=red");
+    }
+
+    #[test]
+    fn test_conversion() {
+        for s in ["bold red blue", "dim ul #ff8000", "normal 208", "italic blink reverse strike", "normal"].iter() {
+            let style = parse(s).unwrap();
+            let spec = StyleSpec::from(style);
+            assert_eq!(spec.to_string(), *s);
+            assert_eq!(Style::from(spec), style);
+            assert_eq!(parse(&spec.to_string()), Ok(style));
+        }
+        let spec = StyleSpec::builder().fg(Red).hidden().underline_color(Blue).build();
+        assert_eq!(Style::from(spec), Red.hidden());
+    }
+
+    #[test]
+    fn test_accessors() {
+        let spec = StyleSpec::builder().fg(Red).bg(Blue).underline_color(Green).reverse().build();
+        assert_eq!(spec.foreground(), Some(Red));
+        assert_eq!(spec.background(), Some(Blue));
+        assert_eq!(spec.underline_color(), Some(Green));
+        assert!(spec.has(Attribute::Reverse));
+        assert!(!spec.has(Attribute::Bold));
+        assert!(!spec.is_plain());
+        assert!(!spec.clears(Attribute::Reverse));
+        let spec = StyleSpec::builder().bold().no(Attribute::Bold).build();
+        assert!(spec.clears(Attribute::Bold));
+        assert!(!spec.has(Attribute::Bold));
+        assert_eq!(spec.to_string(), "nobold");
+    }
+
+    #[test]
+    fn test_introspection() {
+        let spec = StyleSpec::builder().fg(Fixed(3)).bg(RGB(1, 2, 3)).strikethrough().bold().no(Attribute::Dimmed).build();
+        assert_eq!(spec.fg(), Some(ColorValue::Basic(3)));
+        assert_eq!(spec.bg(), Some(ColorValue::Rgb(1, 2, 3)));
+        assert_eq!(spec.attributes().collect::<Vec<_>>(), [Attribute::Bold, Attribute::Strikethrough]);
+        assert_eq!(spec.cleared().collect::<Vec<_>>(), [Attribute::Dimmed]);
+        assert_eq!(StyleSpec::new().fg(), None);
+        assert_eq!(StyleSpec::new().attributes().count(), 0);
+
+        assert_eq!(ColorValue::from(Purple), ColorValue::Basic(5));
+        assert_eq!(ColorValue::from(Fixed(12)), ColorValue::Basic(12));
+        assert_eq!(ColorValue::from(Fixed(208)), ColorValue::Indexed(208));
+        assert_eq!(Color::from(ColorValue::Basic(3)), Yellow);
+        assert_eq!(Color::from(ColorValue::Basic(12)), Fixed(12));
+        assert_eq!(Color::from(ColorValue::Indexed(208)), Fixed(208));
+        assert_eq!(ColorValue::Basic(5).to_string(), "magenta");
+        assert_eq!(ColorValue::Rgb(0xab, 0xcd, 0xef).to_string(), "#abcdef");
+        assert_eq!(ColorValue::Indexed(208).level(), ColorLevel::Ansi256);
+    }
+
+    #[test]
+    fn test_to_compact_string() {
+        assert_eq!(StyleSpec::new().to_compact_string(), "plain");
+        assert_eq!(StyleSpec::builder().bg(RGB(1, 2, 3)).build().to_compact_string(), "bg=RGB(1,2,3)");
+        assert_eq!(StyleSpec::builder().italic().no(Attribute::Bold).build().to_compact_string(), "+italic-bold");
+        let spec = StyleSpec::builder().fg(Purple).underline().underline_color(Fixed(200)).strikethrough().build();
+        assert_eq!(spec.to_compact_string(), "fg=Purple ul=Fixed(200) +ul+strike");
+    }
+
+    #[test]
+    fn test_operators() {
+        let base = StyleSpec::builder().fg(Red).bg(Blue).bold().underline_color(Green).build();
+        let overlay = StyleSpec::builder().fg(Yellow).italic().no(Attribute::Bold).build();
+        let combined = base + overlay;
+        assert_eq!(combined.to_string(), "italic nobold yellow blue ul=green");
+        assert_eq!((overlay + base).to_string(), "bold italic red blue ul=green");
+
+        let mut spec = base;
+        spec += overlay;
+        assert_eq!(spec, combined);
+
+        assert_eq!((!base).to_string(), "nobold");
+        assert_eq!((!combined).to_string(), "bold noitalic");
+        assert_eq!(base + !base, StyleSpec::builder().fg(Red).bg(Blue).underline_color(Green).no(Attribute::Bold).build());
+        assert_eq!(StyleSpec::new() + StyleSpec::new(), StyleSpec::new());
+        assert_eq!(Style::from(base + !base), Red.on(Blue));
+    }
+
+    #[test]
+    fn test_underline_style() {
+        let curly = StyleSpec::builder().fg(Red).underline_style(UnderlineStyle::Curly).build();
+        assert!(curly.has(Attribute::Underline));
+        assert_eq!(curly.underline_style(), UnderlineStyle::Curly);
+        assert_eq!(curly.to_string(), "curly-ul red");
+        assert_eq!(curly.to_compact_string(), "fg=Red +curly-ul");
+        assert_eq!(Style::from(curly), Red.underline());
+        assert_eq!(StyleSpec::builder().underline_style(UnderlineStyle::Dashed).underline().build(),
+                   StyleSpec::builder().underline().build());
+
+        let plain_ul = StyleSpec::builder().underline().build();
+        assert_eq!((curly + plain_ul).underline_style(), UnderlineStyle::Single);
+        assert_eq!((curly + StyleSpec::builder().bold().build()).to_string(), "bold curly-ul red");
+        assert_eq!(curly + !curly, StyleSpec::builder().fg(Red).no(Attribute::Underline).build());
+        assert_eq!(UnderlineStyle::ALL.iter().map(|s| s.sgr_subparameter()).collect::<Vec<_>>(), [1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn test_from_str() {
+        let spec: StyleSpec = "curly-ul Red ul=blue".parse().unwrap();
+        assert_eq!(spec, StyleSpec::builder().underline_style(UnderlineStyle::Curly).fg(Red).underline_color(Blue).build());
+        assert_eq!(spec.to_string().parse(), Ok(spec));
+        assert!("bold sparkly".parse::<StyleSpec>().is_err());
+    }
+
+    #[test]
+    fn test_default_colors() {
+        let spec = StyleSpec::builder().default_fg().bold().build();
+        assert!(spec.is_default_fg());
+        assert!(!spec.is_default_bg());
+        assert_eq!(spec.foreground(), None);
+        assert_eq!(spec.to_string(), "bold default");
+        assert_eq!(spec.to_compact_string(), "fg=default +bold");
+        assert_eq!(StyleSpec::builder().default_bg().build().to_string(), "normal default");
+        assert_eq!(StyleSpec::builder().default_fg().fg(Red).build(), StyleSpec::builder().fg(Red).build());
+        assert_eq!(Style::from(spec), Style::new().bold());
+
+        let base = StyleSpec::builder().fg(Red).bg(Blue).build();
+        let defaults = StyleSpec::builder().default_fg().default_bg().build();
+        assert_eq!((base + defaults).to_string(), "default default");
+        assert_eq!(defaults + base, base);
+        assert_eq!((defaults + StyleSpec::builder().bg(Green).build()).to_string(), "default green");
+        assert_eq!(Style::from(base + defaults), Style::new());
+    }
+
+    #[test]
+    fn test_reset() {
+        let reset = StyleSpec::builder().bold().reset().fg(Red).build();
+        assert!(reset.is_reset());
+        assert!(!reset.has(Attribute::Bold));
+        assert_eq!(reset.to_string(), "reset red");
+        assert_eq!(reset.to_compact_string(), "reset fg=Red");
+        assert_eq!(StyleSpec::builder().reset().build().to_string(), "reset");
+
+        let base = StyleSpec::builder().fg(Blue).bg(Green).italic().build();
+        assert_eq!(base + reset, reset);
+        assert_eq!((reset + base).to_string(), "reset italic blue green");
+        assert!(!(!reset).is_reset());
+        assert_eq!(Style::from(reset), Red.normal());
+    }
+}
@@ -0,0 +1,113 @@
+use crate::render::appearance;
+use crate::StyleSpec;
+
+/// A stack of nested styled regions, tracking the style in effect inside
+/// the innermost one.
+///
+/// Each pushed style applies on top of the styles enclosing it, as the
+/// `+` of `StyleSpec` layers specs: its colors replace the enclosing
+/// colors, its attributes add to the enclosing attributes, and the
+/// attributes it clears, as with `nobold`, are cleared inside it.
+///
+/// # Examples
+///
+///     use colorparse::{parse_spec, StyleStack};
+///
+///     let mut stack = StyleStack::new();
+///     stack.push(parse_spec("red bold").unwrap());
+///     stack.push(parse_spec("normal blue ul").unwrap());
+///     assert_eq!(stack.effective(), parse_spec("red blue bold ul").unwrap());
+///     stack.push(parse_spec("nobold").unwrap());
+///     assert_eq!(stack.effective(), parse_spec("red blue ul").unwrap());
+///     stack.pop();
+///     stack.pop();
+///     assert_eq!(stack.effective(), parse_spec("red bold").unwrap());
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct StyleStack {
+    /// Each pushed spec, along with the effective spec at that depth.
+    entries: Vec<(StyleSpec, StyleSpec)>,
+}
+
+impl StyleStack {
+    /// Create an empty stack, whose effective style is the plain style.
+    pub fn new() -> Self {
+        StyleStack { entries: Vec::new() }
+    }
+
+    /// Enter a region styled with `style`, a `Style` or a `StyleSpec`.
+    pub fn push<S: Into<StyleSpec>>(&mut self, style: S) {
+        let spec = style.into();
+        let effective = appearance(&(self.effective() + spec));
+        self.entries.push((spec, effective));
+    }
+
+    /// Leave the innermost region, returning the spec pushed for it, or
+    /// `None` if the stack is empty.
+    pub fn pop(&mut self) -> Option<StyleSpec> {
+        self.entries.pop().map(|(spec, _)| spec)
+    }
+
+    /// Return the net style in effect inside the innermost region, as a
+    /// spec with no cleared attributes; convert it with `Style::from` to
+    /// paint with it.
+    pub fn effective(&self) -> StyleSpec {
+        self.entries.last().map_or_else(StyleSpec::new, |&(_, effective)| effective)
+    }
+
+    /// Return the number of nested regions.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Return true if no regions have been pushed.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_spec, Attribute};
+    use ansi_term::Color::*;
+    use ansi_term::Style;
+
+    #[test]
+    fn test_style_stack() {
+        let mut stack = StyleStack::new();
+        assert!(stack.is_empty());
+        assert_eq!(stack.effective(), StyleSpec::new());
+        assert_eq!(stack.pop(), None);
+
+        stack.push(Red.on(Blue));
+        stack.push(Style::new().bold());
+        assert_eq!(Style::from(stack.effective()), Red.on(Blue).bold());
+        stack.push(Green.italic());
+        assert_eq!(stack.len(), 3);
+        assert_eq!(Style::from(stack.effective()), Green.on(Blue).bold().italic());
+
+        assert_eq!(stack.pop(), Some(StyleSpec::from(Green.italic())));
+        assert_eq!(Style::from(stack.effective()), Red.on(Blue).bold());
+        assert_eq!(stack.pop(), Some(StyleSpec::from(Style::new().bold())));
+        assert_eq!(stack.pop(), Some(StyleSpec::from(Red.on(Blue))));
+        assert_eq!(stack.effective(), StyleSpec::new());
+    }
+
+    #[test]
+    fn test_style_stack_clears() {
+        let spec = |s| parse_spec(s).unwrap();
+        let mut stack = StyleStack::new();
+        stack.push(spec("bold curly-ul green ul=red"));
+        stack.push(spec("nobold noul"));
+        assert_eq!(stack.effective(), spec("green ul=red"));
+        assert!(!stack.effective().clears(Attribute::Bold));
+        stack.push(spec("bold"));
+        assert_eq!(stack.effective(), spec("bold green ul=red"));
+        stack.push(spec("reset blue"));
+        assert_eq!(stack.effective(), spec("blue"));
+        stack.pop();
+        stack.pop();
+        stack.pop();
+        assert_eq!(stack.effective(), spec("bold curly-ul green ul=red"));
+    }
+}
@@ -0,0 +1,186 @@
+use ansi_term::{Color, Style};
+
+use crate::{parse_spec, Attribute, ColorValue, Error, StyleSpec, UnderlineStyle};
+
+/// A style type that `parse_into` can build from a string in Git syntax.
+///
+/// Implementing this trait for the style type of a terminal library gives
+/// it a parser for Git-syntax specs. Only the colors and the attributes
+/// being set are required; the other methods, for what not every library
+/// can represent, do nothing by default.
+///
+/// # Examples
+///
+///     use colorparse::{parse_into, Attribute, ColorValue, StyleBuilder};
+///
+///     #[derive(Default)]
+///     struct Html(Vec<String>);
+///
+///     impl StyleBuilder for Html {
+///         fn set_fg(&mut self, color: ColorValue) {
+///             self.0.push(format!("fg:{}", color));
+///         }
+///
+///         fn set_bg(&mut self, color: ColorValue) {
+///             self.0.push(format!("bg:{}", color));
+///         }
+///
+///         fn set_attr(&mut self, attribute: Attribute) {
+///             self.0.push(attribute.name().to_string());
+///         }
+///     }
+///
+///     let html: Html = parse_into("ul bold red #0000ff").unwrap();
+///     assert_eq!(html.0, ["fg:red", "bg:#0000ff", "bold", "ul"]);
+pub trait StyleBuilder: Default {
+    /// Set the foreground color.
+    fn set_fg(&mut self, color: ColorValue);
+
+    /// Set the background color.
+    fn set_bg(&mut self, color: ColorValue);
+
+    /// Enable `attribute`.
+    fn set_attr(&mut self, attribute: Attribute);
+
+    /// Explicitly clear `attribute`, as Git's `no` prefix does.
+    fn clear_attr(&mut self, _attribute: Attribute) {}
+
+    /// Set the foreground to the terminal's default color.
+    fn set_default_fg(&mut self) {}
+
+    /// Set the background to the terminal's default color.
+    fn set_default_bg(&mut self) {}
+
+    /// Set the shape of the underline, after enabling underline.
+    fn set_underline_style(&mut self, _style: UnderlineStyle) {}
+
+    /// Set the underline color.
+    fn set_underline_color(&mut self, _color: ColorValue) {}
+
+    /// Start with a reset, discarding every color and attribute before the
+    /// style, as Git's `reset` does. Called first, if at all.
+    fn reset(&mut self) {}
+}
+
+/// Replay `spec` into a new `B`: the reset, the colors, the attributes set
+/// and cleared, in the order of `Attribute::ALL`, and then the underline
+/// shape and color.
+pub(crate) fn build<B: StyleBuilder>(spec: &StyleSpec) -> B {
+    let mut builder = B::default();
+    if spec.is_reset() {
+        builder.reset();
+    }
+    match spec.fg() {
+        Some(fg) => builder.set_fg(fg),
+        None if spec.is_default_fg() => builder.set_default_fg(),
+        None => {}
+    }
+    match spec.bg() {
+        Some(bg) => builder.set_bg(bg),
+        None if spec.is_default_bg() => builder.set_default_bg(),
+        None => {}
+    }
+    spec.attributes().for_each(|attribute| builder.set_attr(attribute));
+    spec.cleared().for_each(|attribute| builder.clear_attr(attribute));
+    if spec.has(Attribute::Underline) && spec.underline_style() != UnderlineStyle::Single {
+        builder.set_underline_style(spec.underline_style());
+    }
+    if let Some(color) = spec.underline_color() {
+        builder.set_underline_color(color.into());
+    }
+    builder
+}
+
+/// Parse a string in Git's color configuration syntax, as `parse_spec`
+/// does, into any type implementing `StyleBuilder`.
+///
+/// # Examples
+///
+///     use colorparse::{parse, parse_into};
+///
+///     let style: ansi_term::Style = parse_into("bold red blue").unwrap();
+///     assert_eq!(style, parse("bold red blue").unwrap());
+pub fn parse_into<B: StyleBuilder>(s: &str) -> Result<B, Error> {
+    parse_spec(s).map(|spec| build(&spec))
+}
+
+/// Builds the style `parse` returns, except that colors 0–7 written as
+/// numbers become the named colors, such as `Color::Red` for `1`, and
+/// what `ansi_term` cannot hold is dropped.
+impl StyleBuilder for Style {
+    fn set_fg(&mut self, color: ColorValue) {
+        self.foreground = Some(Color::from(color));
+    }
+
+    fn set_bg(&mut self, color: ColorValue) {
+        self.background = Some(Color::from(color));
+    }
+
+    fn set_attr(&mut self, attribute: Attribute) {
+        *self = attribute.apply(*self);
+    }
+}
+
+/// Builds the spec `parse_spec` returns, except that colors 0–7 written as
+/// numbers become the named colors.
+impl StyleBuilder for StyleSpec {
+    fn set_fg(&mut self, color: ColorValue) {
+        *self += StyleSpec::builder().fg(color.into()).build();
+    }
+
+    fn set_bg(&mut self, color: ColorValue) {
+        *self += StyleSpec::builder().bg(color.into()).build();
+    }
+
+    fn set_attr(&mut self, attribute: Attribute) {
+        *self += StyleSpec::builder().attribute(attribute).build();
+    }
+
+    fn clear_attr(&mut self, attribute: Attribute) {
+        *self += StyleSpec::builder().no(attribute).build();
+    }
+
+    fn set_default_fg(&mut self) {
+        *self += StyleSpec::builder().default_fg().build();
+    }
+
+    fn set_default_bg(&mut self) {
+        *self += StyleSpec::builder().default_bg().build();
+    }
+
+    fn set_underline_style(&mut self, style: UnderlineStyle) {
+        *self += StyleSpec::builder().underline_style(style).build();
+    }
+
+    fn set_underline_color(&mut self, color: ColorValue) {
+        self.underline_color = Some(color.into());
+    }
+
+    fn reset(&mut self) {
+        *self = StyleSpec::builder().reset().build();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ansi_term::Color::*;
+    use crate::parse;
+
+    #[test]
+    fn test_parse_into_style() {
+        for s in ["", "bold red blue", "dim ul 208 #ff8000", "nobold italic", "reset strike 9", "default green"].iter() {
+            assert_eq!(parse_into::<Style>(s), parse(s), "{}", s);
+        }
+        assert_eq!(parse_into::<Style>("0 magenta"), Ok(Black.on(Purple)));
+        assert_eq!(parse_into::<Style>("red blue green"), Err(parse("red blue green").unwrap_err()));
+    }
+
+    #[test]
+    fn test_parse_into_spec() {
+        for s in ["bold red blue", "reset nodim curly-ul ul=red", "default default no-reverse bold", "overline 9 #ff8000"].iter() {
+            assert_eq!(parse_into::<StyleSpec>(s), parse_spec(s), "{}", s);
+        }
+        assert_eq!(parse_into::<StyleSpec>("1"), Ok(StyleSpec::builder().fg(Red).build()));
+    }
+}
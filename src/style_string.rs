@@ -0,0 +1,77 @@
+use std::fmt;
+use std::ops::Deref;
+use std::str::FromStr;
+
+use ansi_term::Style;
+
+use crate::{parse, Error, StyleSpec};
+
+/// An `ansi_term::Style` that parses from, and displays as, a string in
+/// Git's color syntax, for fields of command-line arguments and config
+/// structs, and for `str::parse`.
+///
+/// It dereferences to the `Style`, so it can paint text directly.
+///
+/// # Examples
+///
+///     use colorparse::StyleString;
+///
+///     let style: StyleString = "Bold RED".parse().unwrap();
+///     assert_eq!(*style, colorparse::parse("bold red").unwrap());
+///     assert_eq!(style.to_string(), "bold red");
+///     println!("{}", style.paint("error"));
+///     assert!("bold sparkly".parse::<StyleString>().is_err());
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct StyleString(pub Style);
+
+/// Parses the string with `parse`.
+impl FromStr for StyleString {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse(s).map(StyleString)
+    }
+}
+
+/// Writes the style in Git syntax, as `to_config_string` does.
+impl fmt::Display for StyleString {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        StyleSpec::from(self.0).fmt(fmt)
+    }
+}
+
+impl Deref for StyleString {
+    type Target = Style;
+
+    fn deref(&self) -> &Style {
+        &self.0
+    }
+}
+
+impl From<Style> for StyleString {
+    fn from(style: Style) -> Self {
+        StyleString(style)
+    }
+}
+
+impl From<StyleString> for Style {
+    fn from(style: StyleString) -> Self {
+        style.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ansi_term::Color::*;
+
+    #[test]
+    fn test_style_string() {
+        assert_eq!("ul 208 blue".parse(), Ok(StyleString(Fixed(208).on(Blue).underline())));
+        assert_eq!("".parse(), Ok(StyleString::default()));
+        assert_eq!("red blue green".parse::<StyleString>(), Err(parse("red blue green").unwrap_err()));
+        assert_eq!(StyleString(Red.on(Fixed(8)).italic()).to_string(), "italic red 8");
+        assert_eq!(Style::from(StyleString::from(Cyan.dimmed())), Cyan.dimmed());
+        assert_eq!(StyleString(Green.normal()).foreground, Some(Green));
+    }
+}
@@ -0,0 +1,55 @@
+/// Return the edit distance between `a` and `b`, counting insertions,
+/// deletions, substitutions, and transpositions of adjacent characters.
+fn distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut rows = vec![(0..=b.len()).collect::<Vec<usize>>()];
+    for i in 1..=a.len() {
+        let mut row = vec![i; b.len() + 1];
+        for j in 1..=b.len() {
+            let cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+            row[j] = (rows[i - 1][j] + 1).min(row[j - 1] + 1).min(rows[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                row[j] = row[j].min(rows[i - 2][j - 2] + 1);
+            }
+        }
+        rows.push(row);
+    }
+    rows[a.len()][b.len()]
+}
+
+/// Return the candidate closest to `word`, ignoring case, if any is close
+/// enough to be a plausible misspelling of it.
+pub(crate) fn closest<'a, I: IntoIterator<Item = &'a str>>(word: &str, candidates: I) -> Option<&'a str> {
+    let word = word.to_lowercase();
+    let limit = (word.chars().count() / 3).max(1);
+    candidates.into_iter()
+        .map(|candidate| (distance(&word, &candidate.to_lowercase()), candidate))
+        .filter(|&(d, _)| d <= limit)
+        .min_by_key(|&(d, _)| d)
+        .map(|(_, candidate)| candidate)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_distance() {
+        assert_eq!(distance("", ""), 0);
+        assert_eq!(distance("blue", "blue"), 0);
+        assert_eq!(distance("blu", "blue"), 1);
+        assert_eq!(distance("bule", "blue"), 1);
+        assert_eq!(distance("red", "green"), 3);
+        assert_eq!(distance("", "ul"), 2);
+    }
+
+    #[test]
+    fn test_closest() {
+        let words = ["bold", "blink", "blue", "black"];
+        assert_eq!(closest("blu", words.iter().cloned()), Some("blue"));
+        assert_eq!(closest("BOLD", words.iter().cloned()), Some("bold"));
+        assert_eq!(closest("blnik", words.iter().cloned()), Some("blink"));
+        assert_eq!(closest("purple", words.iter().cloned()), None);
+    }
+}
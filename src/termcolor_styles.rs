@@ -0,0 +1,138 @@
+//! Parsing Git-syntax specs into `termcolor` color specs.
+//!
+//! `termcolor` writes colors either as escape sequences or through the
+//! Windows console API. This module converts a `StyleSpec`, or a `Style`,
+//! into a `termcolor::ColorSpec`, so that programs using `termcolor` for
+//! Windows support can take their colors from Git-syntax config.
+//!
+//! # Examples
+//!
+//!     # extern crate colorparse;
+//!     # extern crate termcolor;
+//!     use std::io::Write;
+//!     use termcolor::{Color, ColorChoice, ColorSpec, StandardStream, WriteColor};
+//!
+//!     # fn main() {
+//!     let spec = colorparse::termcolor_styles::parse("bold red blue").unwrap();
+//!     assert_eq!(&spec, ColorSpec::new().set_fg(Some(Color::Red)).set_bg(Some(Color::Blue)).set_bold(true));
+//!
+//!     let mut stdout = StandardStream::stdout(ColorChoice::Auto);
+//!     stdout.set_color(&spec).unwrap();
+//!     writeln!(stdout, "Bold red on blue").unwrap();
+//!     stdout.reset().unwrap();
+//!     # }
+
+use ansi_term::Color;
+use termcolor::{Color as TermColor, ColorSpec};
+
+use crate::style_builder::build;
+use crate::{parse_spec, Attribute, ColorValue, Error, StyleBuilder, StyleSpec};
+
+/// Convert `color` to the `termcolor::Color` that gives the same color,
+/// with colors 8–15 as `Ansi256`.
+pub fn to_termcolor_color(color: Color) -> TermColor {
+    match color {
+        Color::Black => TermColor::Black,
+        Color::Red => TermColor::Red,
+        Color::Green => TermColor::Green,
+        Color::Yellow => TermColor::Yellow,
+        Color::Blue => TermColor::Blue,
+        Color::Purple => TermColor::Magenta,
+        Color::Cyan => TermColor::Cyan,
+        Color::White => TermColor::White,
+        Color::Fixed(n) => TermColor::Ansi256(n),
+        Color::RGB(r, g, b) => TermColor::Rgb(r, g, b),
+    }
+}
+
+/// Convert a `StyleSpec` or an `ansi_term::Style`, or a reference to
+/// either, to a `termcolor::ColorSpec`.
+///
+/// # Examples
+///
+///     # extern crate colorparse;
+///     # extern crate termcolor;
+///     use colorparse::termcolor_styles::to_termcolor;
+///     use termcolor::{Color, ColorSpec};
+///
+///     # fn main() {
+///     let style = colorparse::parse("ul green").unwrap();
+///     assert_eq!(&to_termcolor(&style), ColorSpec::new().set_fg(Some(Color::Green)).set_underline(true));
+///     # }
+pub fn to_termcolor<S: Into<StyleSpec>>(style: S) -> ColorSpec {
+    ColorSpec::from(style.into())
+}
+
+/// Parse a string in Git's color configuration syntax, as `parse_spec`
+/// does, into a `termcolor::ColorSpec`.
+pub fn parse(s: &str) -> Result<ColorSpec, Error> {
+    parse_spec(s).map(ColorSpec::from)
+}
+
+/// Builds a `termcolor::ColorSpec`. Like every `ColorSpec`, it resets the
+/// terminal before setting its colors, so a Git `reset` changes nothing.
+impl StyleBuilder for ColorSpec {
+    fn set_fg(&mut self, color: ColorValue) {
+        ColorSpec::set_fg(self, Some(to_termcolor_color(color.into())));
+    }
+
+    fn set_bg(&mut self, color: ColorValue) {
+        ColorSpec::set_bg(self, Some(to_termcolor_color(color.into())));
+    }
+
+    fn set_attr(&mut self, attribute: Attribute) {
+        match attribute {
+            Attribute::Bold => { self.set_bold(true); }
+            Attribute::Dimmed => { self.set_dimmed(true); }
+            Attribute::Italic => { self.set_italic(true); }
+            Attribute::Underline => { self.set_underline(true); }
+            Attribute::Strikethrough => { self.set_strikethrough(true); }
+            Attribute::Blink | Attribute::RapidBlink | Attribute::Reverse | Attribute::Hidden | Attribute::Overline => {}
+        }
+    }
+}
+
+/// Converts to a `termcolor::ColorSpec`, as `StyleBuilder` builds it,
+/// dropping blink, reverse, hidden, overline, the underline color and
+/// shape, and cleared attributes, which `termcolor` cannot write.
+impl From<StyleSpec> for ColorSpec {
+    fn from(spec: StyleSpec) -> Self {
+        build(&spec)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use termcolor::WriteColor;
+
+    #[test]
+    fn test_parse() {
+        let spec = |fg, bg| {
+            let mut spec = ColorSpec::new();
+            spec.set_fg(fg).set_bg(bg);
+            spec
+        };
+        assert_eq!(parse("ul dim 208 #102030"),
+                   Ok(spec(Some(TermColor::Ansi256(208)), Some(TermColor::Rgb(16, 32, 48))).set_underline(true).set_dimmed(true).clone()));
+        assert_eq!(parse("strike italic magenta brightred"),
+                   Ok(spec(Some(TermColor::Magenta), Some(TermColor::Ansi256(9))).set_italic(true).set_strikethrough(true).clone()));
+        assert_eq!(parse("reset blink reverse conceal default"), Ok(ColorSpec::new()));
+        assert_eq!(parse("nobold overline curly-ul ul=red"), Ok(spec(None, None).set_underline(true).clone()));
+        assert_eq!(parse(""), Ok(ColorSpec::new()));
+        assert!(parse("bold sparkly").is_err());
+        assert_eq!(to_termcolor(ansi_term::Color::Cyan.bold()), parse("cyan bold").unwrap());
+    }
+
+    #[test]
+    fn test_escape_sequences() {
+        for s in ["bold red blue", "dim ul 9 208", "italic #ff8000 normal", "strike white black"].iter() {
+            let mut ansi = termcolor::Ansi::new(Vec::new());
+            ansi.set_color(&parse(s).unwrap()).unwrap();
+            let written = String::from_utf8(ansi.into_inner()).unwrap();
+            let params: Vec<&str> = written.split("\x1b[").filter(|p| !p.is_empty()).map(|p| p.trim_end_matches('m')).collect();
+            let spec = crate::parse_sgr(&params.join(";")).unwrap();
+            assert_eq!(ansi_term::Style::from(spec), crate::parse(s).unwrap(), "{}", s);
+        }
+    }
+}
@@ -0,0 +1,241 @@
+use std::io::{self, ErrorKind};
+use std::path::PathBuf;
+
+use crate::ColorLevel;
+
+/// The magic number of terminfo entries with 16-bit numbers.
+const MAGIC_16: u16 = 0o432;
+/// The magic number of terminfo entries with 32-bit numbers.
+const MAGIC_32: u16 = 0o1036;
+/// The index of the `colors` capability among the numbers.
+const COLORS: usize = 13;
+
+fn invalid() -> io::Error {
+    io::Error::new(ErrorKind::InvalidData, "invalid terminfo entry")
+}
+
+/// Reads the little-endian fields of a compiled terminfo entry.
+struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn bytes(&mut self, len: usize) -> io::Result<&'a [u8]> {
+        let bytes = self.data.get(self.pos..self.pos + len).ok_or_else(invalid)?;
+        self.pos += len;
+        Ok(bytes)
+    }
+
+    fn u16(&mut self) -> io::Result<u16> {
+        let b = self.bytes(2)?;
+        Ok(u16::from_le_bytes([b[0], b[1]]))
+    }
+
+    fn count(&mut self) -> io::Result<usize> {
+        match self.u16()? {
+            0xffff => Ok(0),
+            n if n < 0x8000 => Ok(usize::from(n)),
+            _ => Err(invalid()),
+        }
+    }
+
+    /// Read `count` numbers, as `i32`, with negative values meaning absent.
+    fn numbers(&mut self, count: usize, wide: bool) -> io::Result<Vec<i32>> {
+        (0..count).map(|_| if wide {
+            let b = self.bytes(4)?;
+            Ok(i32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        } else {
+            Ok(i32::from(self.u16()? as i16))
+        }).collect()
+    }
+
+    fn align(&mut self) {
+        self.pos += self.pos % 2;
+    }
+}
+
+/// Return the names of the extended boolean capabilities the entry sets,
+/// from the section following the standard capabilities.
+fn extended_booleans(reader: &mut Reader) -> io::Result<Vec<String>> {
+    let bools = reader.count()?;
+    let nums = reader.count()?;
+    let strs = reader.count()?;
+    let _items = reader.count()?;
+    let table_size = reader.count()?;
+    let values = reader.bytes(bools)?.to_vec();
+    reader.align();
+    reader.numbers(nums, false)?;
+    let offsets = reader.numbers(strs, false)?;
+    let name_offsets = reader.numbers(bools + nums + strs, false)?;
+    let table = reader.bytes(table_size)?;
+    // The names follow the values of the strings that are present.
+    let present = offsets.iter().filter(|&&o| o >= 0).count();
+    let names_start = table.split(|&b| b == 0).take(present).map(|s| s.len() + 1).sum::<usize>();
+    let names = table.get(names_start..).ok_or_else(invalid)?;
+    let name = |offset: i32| {
+        let rest = names.get(offset as usize..).ok_or_else(invalid)?;
+        let end = rest.iter().position(|&b| b == 0).unwrap_or(rest.len());
+        Ok(String::from_utf8_lossy(&rest[..end]).into_owned())
+    };
+    values.iter().zip(name_offsets.iter()).filter(|&(&set, _)| set == 1).map(|(_, &offset)| name(offset)).collect()
+}
+
+/// Return the color level a compiled terminfo entry describes.
+fn level_from_entry(data: &[u8]) -> io::Result<Option<ColorLevel>> {
+    let mut reader = Reader { data, pos: 0 };
+    let wide = match reader.u16()? {
+        MAGIC_16 => false,
+        MAGIC_32 => true,
+        _ => return Err(invalid()),
+    };
+    let names_size = reader.count()?;
+    let bools = reader.count()?;
+    let nums = reader.count()?;
+    let strs = reader.count()?;
+    let table_size = reader.count()?;
+    reader.bytes(names_size)?;
+    reader.bytes(bools)?;
+    reader.align();
+    let colors = reader.numbers(nums, wide)?.get(COLORS).cloned().unwrap_or(-1);
+    reader.bytes(strs * 2)?;
+    reader.bytes(table_size)?;
+    reader.align();
+    let extended = if reader.pos < data.len() { extended_booleans(&mut reader)? } else { Vec::new() };
+    Ok(if extended.iter().any(|name| name == "RGB" || name == "Tc") || colors >= 0x100_0000 {
+        Some(ColorLevel::TrueColor)
+    } else if colors >= 256 {
+        Some(ColorLevel::Ansi256)
+    } else if colors >= 8 {
+        Some(ColorLevel::Ansi16)
+    } else {
+        None
+    })
+}
+
+/// Return the directories to search for terminfo entries, in the order
+/// ncurses searches them.
+fn directories() -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+    if let Some(dir) = std::env::var_os("TERMINFO") {
+        dirs.push(PathBuf::from(dir));
+    }
+    if let Some(home) = std::env::var_os("HOME") {
+        dirs.push(PathBuf::from(home).join(".terminfo"));
+    }
+    if let Some(list) = std::env::var_os("TERMINFO_DIRS") {
+        dirs.extend(std::env::split_paths(&list).filter(|dir| !dir.as_os_str().is_empty()));
+    }
+    dirs.extend(["/etc/terminfo", "/lib/terminfo", "/usr/share/terminfo", "/usr/lib/terminfo"].iter().map(PathBuf::from));
+    dirs
+}
+
+/// Return the color level the terminfo database gives for the terminal
+/// type `term`, from its `colors` capability and the `RGB` and `Tc`
+/// extensions for 24-bit color, or `None` if the terminal has no colors.
+///
+/// Entries are looked up the way ncurses does: in `$TERMINFO`,
+/// `~/.terminfo`, `$TERMINFO_DIRS`, and the system directories, under a
+/// subdirectory named for the first character of `term` or its
+/// hexadecimal code.
+///
+/// # Examples
+///
+///     # extern crate ansi_term;
+///     # extern crate colorparse;
+///     # fn main() {
+///     let level = colorparse::terminfo_level("xterm-256color").ok().flatten();
+///     let style = colorparse::parse("#ff8000 bold").unwrap();
+///     if let Some(level) = level {
+///         println!("{}", colorparse::downgrade_style(style, level).paint("warning"));
+///     }
+///     # }
+pub fn terminfo_level(term: &str) -> io::Result<Option<ColorLevel>> {
+    let first = match term.chars().next() {
+        Some(c) if !term.contains('/') => c,
+        _ => return Err(io::Error::new(ErrorKind::InvalidInput, "invalid terminal name")),
+    };
+    for dir in directories() {
+        for subdir in [first.to_string(), format!("{:x}", u32::from(first))].iter() {
+            match std::fs::read(dir.join(subdir).join(term)) {
+                Ok(data) => return level_from_entry(&data),
+                Err(ref e) if e.kind() == ErrorKind::NotFound => {}
+                Err(e) => return Err(e),
+            }
+        }
+    }
+    Err(io::Error::new(ErrorKind::NotFound, format!("no terminfo entry for \"{}\"", term)))
+}
+
+/// Return the color level the terminfo database gives for the terminal
+/// named by the `TERM` environment variable, as `terminfo_level` does.
+pub fn detect_terminfo_level() -> io::Result<Option<ColorLevel>> {
+    match std::env::var("TERM") {
+        Ok(term) if !term.is_empty() => terminfo_level(&term),
+        _ => Err(io::Error::new(ErrorKind::NotFound, "TERM is not set")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ColorLevel::*;
+
+    /// Build an entry with 32-bit numbers, the given `colors`, and the
+    /// given extended booleans.
+    fn entry(colors: i32, extended: &[(&str, bool)]) -> Vec<u8> {
+        let mut data = Vec::new();
+        let names = b"test|Test terminal\0";
+        for n in [MAGIC_32, names.len() as u16, 1, 14, 1, 2].iter() {
+            data.extend_from_slice(&n.to_le_bytes());
+        }
+        data.extend_from_slice(names);
+        data.push(1);
+        if data.len() % 2 == 1 { data.push(0); }
+        for i in 0..14 {
+            let n: i32 = if i == COLORS { colors } else { -1 };
+            data.extend_from_slice(&n.to_le_bytes());
+        }
+        data.extend_from_slice(&0u16.to_le_bytes());
+        data.extend_from_slice(b"\0\0");
+        if !extended.is_empty() {
+            let mut table = Vec::new();
+            let mut offsets = Vec::new();
+            for (name, _) in extended {
+                offsets.push(table.len() as u16);
+                table.extend_from_slice(name.as_bytes());
+                table.push(0);
+            }
+            for n in [extended.len() as u16, 0, 0, extended.len() as u16, table.len() as u16].iter() {
+                data.extend_from_slice(&n.to_le_bytes());
+            }
+            data.extend(extended.iter().map(|&(_, set)| set as u8));
+            if data.len() % 2 == 1 { data.push(0); }
+            for offset in offsets {
+                data.extend_from_slice(&offset.to_le_bytes());
+            }
+            data.extend_from_slice(&table);
+        }
+        data
+    }
+
+    #[test]
+    fn test_level_from_entry() {
+        assert_eq!(level_from_entry(&entry(-1, &[])).unwrap(), None);
+        assert_eq!(level_from_entry(&entry(8, &[])).unwrap(), Some(Ansi16));
+        assert_eq!(level_from_entry(&entry(256, &[])).unwrap(), Some(Ansi256));
+        assert_eq!(level_from_entry(&entry(0x100_0000, &[])).unwrap(), Some(TrueColor));
+        assert_eq!(level_from_entry(&entry(256, &[("AX", true), ("RGB", true)])).unwrap(), Some(TrueColor));
+        assert_eq!(level_from_entry(&entry(256, &[("Tc", true)])).unwrap(), Some(TrueColor));
+        assert_eq!(level_from_entry(&entry(256, &[("RGB", false)])).unwrap(), Some(Ansi256));
+        assert!(level_from_entry(b"\x1e\x02").is_err());
+        assert!(level_from_entry(&[0; 12]).is_err());
+    }
+
+    #[test]
+    fn test_terminfo_level() {
+        assert_eq!(terminfo_level("").unwrap_err().kind(), ErrorKind::InvalidInput);
+        assert_eq!(terminfo_level("../x").unwrap_err().kind(), ErrorKind::InvalidInput);
+        assert_eq!(terminfo_level("no-such-terminal-type").unwrap_err().kind(), ErrorKind::NotFound);
+    }
+}
@@ -0,0 +1,189 @@
+//! Generators of realistic style strings and specs, and a round-trip
+//! check, for property tests and fuzzing of code that handles color
+//! configuration.
+//!
+//! The generators draw from a seedable `StyleRng`, so a failing case can
+//! be reproduced from its seed, and plug into any property-testing
+//! framework by seeding the generator from the framework's own input.
+//!
+//! # Examples
+//!
+//!     use colorparse::testing::{arbitrary_style_string, assert_round_trip};
+//!     use colorparse::StyleRng;
+//!
+//!     for seed in 0..100 {
+//!         let s = arbitrary_style_string(&mut StyleRng::new(seed));
+//!         assert!(colorparse::parse(&s).is_ok(), "{:?}", s);
+//!         assert_round_trip(&s);
+//!     }
+
+use ansi_term::Color;
+
+use crate::random::random_color;
+use crate::{normalize, parse_spec, Attribute, ColorLevel, StyleRng, StyleSpec, UnderlineStyle};
+use crate::{ATTRIBUTE_NAMES, BOOL_NAMES, COLOR_NAMES, UNDERLINE_STYLE_NAMES};
+
+/// The separators `arbitrary_style_string` puts between words.
+const SEPARATORS: [&str; 5] = [" ", " ", "  ", "\t", "\u{A0}"];
+
+/// Return an element of `items` chosen by `rng`.
+fn pick<'a, T>(rng: &mut StyleRng, items: &'a [T]) -> &'a T {
+    &items[rng.below(items.len())]
+}
+
+/// Return a random color, from any of the three color levels.
+fn arbitrary_color(rng: &mut StyleRng) -> Color {
+    let level = *pick(rng, &[ColorLevel::Ansi16, ColorLevel::Ansi256, ColorLevel::TrueColor]);
+    random_color(level, rng)
+}
+
+/// Return a random spec, with each color unset, the default, or any color,
+/// each attribute enabled, cleared, or left alone, and sometimes a reset,
+/// an underline shape, or an underline color.
+///
+/// # Examples
+///
+///     use colorparse::testing::arbitrary_spec;
+///     use colorparse::{parse_spec, StyleRng};
+///
+///     let spec = arbitrary_spec(&mut StyleRng::new(3));
+///     assert_eq!(parse_spec(&spec.to_string()), Ok(spec));
+pub fn arbitrary_spec(rng: &mut StyleRng) -> StyleSpec {
+    let mut builder = StyleSpec::builder();
+    if rng.below(8) == 0 {
+        builder = builder.reset();
+    }
+    builder = match rng.below(4) {
+        0 => builder,
+        1 => builder.default_fg(),
+        _ => builder.fg(arbitrary_color(rng)),
+    };
+    builder = match rng.below(4) {
+        0 | 1 => builder,
+        2 => builder.default_bg(),
+        _ => builder.bg(arbitrary_color(rng)),
+    };
+    for &attribute in &Attribute::ALL {
+        builder = match rng.below(6) {
+            0 => builder.attribute(attribute),
+            1 => builder.no(attribute),
+            _ => builder,
+        };
+    }
+    if builder.spec.has(Attribute::Underline) && rng.below(2) == 0 {
+        builder = builder.underline_style(*pick(rng, &UnderlineStyle::ALL));
+    }
+    if rng.below(4) == 0 {
+        builder = builder.underline_color(arbitrary_color(rng));
+    }
+    builder.build()
+}
+
+/// Return a random color word in one of the forms `parse` accepts, or one
+/// valid after `ul=` if `underline` is true.
+fn color_text(rng: &mut StyleRng, underline: bool) -> String {
+    let [r, g, b] = [rng.next_u8(), rng.next_u8(), rng.next_u8()];
+    match rng.below(6) {
+        0 | 1 => {
+            let names: Vec<&str> = COLOR_NAMES.iter().cloned().filter(|&n| !underline || n != "default").collect();
+            pick(rng, &names).to_string()
+        }
+        2 => r.to_string(),
+        3 => format!("#{:02x}{:02x}{:02x}", r, g, b),
+        4 => format!("#{:x}{:x}{:x}", r >> 4, g >> 4, b >> 4),
+        _ => format!("rgb({},{}{},{}{})", r, pick(rng, &["", " "]), g, pick(rng, &["", " "]), b),
+    }
+}
+
+/// Return a random word that sets, clears, or shapes an attribute.
+fn attribute_text(rng: &mut StyleRng) -> String {
+    let name = *pick(rng, &ATTRIBUTE_NAMES);
+    match rng.below(8) {
+        0 => format!("no{}", name),
+        1 => format!("no-{}", name),
+        2 => format!("{}={}", name, pick(rng, &BOOL_NAMES)),
+        3 => pick(rng, &UNDERLINE_STYLE_NAMES).to_string(),
+        4 => format!("ul={}", color_text(rng, true)),
+        _ => name.to_string(),
+    }
+}
+
+/// Return a random style string that `parse` accepts, in the variety of
+/// forms found in real config files: colors and attributes in any order,
+/// names in mixed case, colors written as names, numbers, hex codes, or
+/// `rgb()` calls, negated attributes, and assorted whitespace.
+pub fn arbitrary_style_string(rng: &mut StyleRng) -> String {
+    let mut words: Vec<String> = (0..rng.below(3)).map(|_| color_text(rng, false)).collect();
+    for _ in 0..rng.below(5) {
+        let word = attribute_text(rng);
+        let at = rng.below(words.len() + 1);
+        words.insert(at, word);
+    }
+    if rng.below(8) == 0 {
+        let at = rng.below(words.len() + 1);
+        words.insert(at, "reset".to_string());
+    }
+    let mut s = String::new();
+    if rng.below(8) == 0 {
+        s.push_str(SEPARATORS[rng.below(SEPARATORS.len())]);
+    }
+    for (i, word) in words.iter().enumerate() {
+        if i > 0 {
+            s.push_str(SEPARATORS[rng.below(SEPARATORS.len())]);
+        }
+        match rng.below(6) {
+            0 => s.push_str(&word.to_uppercase()),
+            1 => {
+                let mut chars = word.chars();
+                s.extend(chars.next().map(|c| c.to_ascii_uppercase()));
+                s.extend(chars);
+            }
+            _ => s.push_str(word),
+        }
+    }
+    s
+}
+
+/// Check that `s` parses, and that writing its spec back in Git syntax
+/// gives a string that parses to the same spec and normalizes the same,
+/// panicking with a description of the first difference if not.
+pub fn assert_round_trip(s: &str) {
+    let spec = parse_spec(s).unwrap_or_else(|error| panic!("{}", error));
+    let written = spec.to_string();
+    assert_eq!(parse_spec(&written), Ok(spec), "{:?} was written as {:?}", s, written);
+    let normal = normalize(s).unwrap();
+    assert_eq!(normalize(&written).as_ref(), Ok(&normal), "{:?} was written as {:?}", s, written);
+    assert_eq!(normalize(&normal).as_ref(), Ok(&normal), "{:?} normalized to {:?}", s, normal);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse;
+
+    #[test]
+    fn test_arbitrary_style_string() {
+        let mut rng = StyleRng::new(0);
+        for _ in 0..2000 {
+            let s = arbitrary_style_string(&mut rng);
+            assert!(parse(&s).is_ok(), "{:?}", s);
+            assert_round_trip(&s);
+        }
+        assert_eq!(arbitrary_style_string(&mut StyleRng::new(9)), arbitrary_style_string(&mut StyleRng::new(9)));
+    }
+
+    #[test]
+    fn test_arbitrary_spec() {
+        let mut rng = StyleRng::new(0);
+        for _ in 0..2000 {
+            let spec = arbitrary_spec(&mut rng);
+            assert_round_trip(&spec.to_string());
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "sparkly")]
+    fn test_assert_round_trip_invalid() {
+        assert_round_trip("bold sparkly");
+    }
+}
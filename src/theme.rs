@@ -0,0 +1,605 @@
+use std::collections::BTreeMap;
+
+use ansi_term::Style;
+
+use crate::check::check;
+#[cfg(feature = "yaml")]
+use crate::yaml;
+use crate::{parse_spec, render, Capabilities, Error, Finding, StyleSpec};
+
+/// A set of named styles ("slots"), such as `error` or `diff.new`.
+///
+/// # Examples
+///
+///     use colorparse::Theme;
+///
+///     let mut theme = Theme::new();
+///     theme.set("error", "bold red").unwrap();
+///     assert_eq!(theme.get("error"), Some(colorparse::parse("bold red").unwrap()));
+///     assert_eq!(theme.get("warning"), None);
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Theme {
+    styles: BTreeMap<String, StyleSpec>,
+}
+
+impl Theme {
+    /// Create an empty theme.
+    pub fn new() -> Self {
+        Theme { styles: BTreeMap::new() }
+    }
+
+    /// Set the slot `name` to `style`, returning the style it previously had.
+    pub fn insert<S: Into<String>>(&mut self, name: S, style: Style) -> Option<Style> {
+        self.styles.insert(name.into(), StyleSpec::from(style)).map(Style::from)
+    }
+
+    /// Parse `spec` in Git's syntax and set the slot `name` to the result.
+    /// Leaves the theme unchanged if `spec` fails to parse.
+    ///
+    /// The slot keeps the attributes `spec` clears, such as `nobold`, and
+    /// its `default` colors and `reset`, for `resolve` to apply.
+    pub fn set<S: Into<String>>(&mut self, name: S, spec: &str) -> Result<(), Error> {
+        let spec = parse_spec(spec)?;
+        self.styles.insert(name.into(), spec);
+        Ok(())
+    }
+
+    /// Return the style of the slot `name`, if set.
+    pub fn get(&self, name: &str) -> Option<Style> {
+        self.styles.get(name).map(|&spec| Style::from(spec))
+    }
+
+    /// Return the style for the dotted key `key`, cascading from every
+    /// slot that applies to it.
+    ///
+    /// A slot applies if its name matches the first segments of `key`,
+    /// where a `*` segment in the name matches any one segment; so `diff`,
+    /// `diff.old`, and `*.old` all apply to `diff.old.moved`. The styles of
+    /// the applicable slots are layered from the least specific to the
+    /// most specific, as the `+` of `StyleSpec` layers specs: the slot
+    /// matching more segments is more specific, then the one with fewer
+    /// `*` segments, then the one later in name order. So a more specific
+    /// slot set to `nobold` clears the bold of a less specific one, and one
+    /// set to `reset` discards them. Returns `None` if no slot applies.
+    ///
+    /// # Examples
+    ///
+    ///     use colorparse::{parse, Theme};
+    ///
+    ///     let mut theme = Theme::new();
+    ///     theme.set("diff", "bold").unwrap();
+    ///     theme.set("diff.old", "red").unwrap();
+    ///     theme.set("*.*.moved", "ul").unwrap();
+    ///     theme.set("diff.old.moved.dimmed", "dim").unwrap();
+    ///     assert_eq!(theme.resolve("diff.old.moved"), Some(parse("bold ul red").unwrap()));
+    ///     assert_eq!(theme.resolve("diff.old.moved.dimmed"), Some(parse("bold dim ul red").unwrap()));
+    ///     assert_eq!(theme.resolve("diff.new"), Some(parse("bold").unwrap()));
+    ///     assert_eq!(theme.resolve("status"), None);
+    ///
+    ///     theme.set("diff.old.plain", "nobold noul").unwrap();
+    ///     assert_eq!(theme.resolve("diff.old.plain.moved"), Some(parse("red").unwrap()));
+    pub fn resolve(&self, key: &str) -> Option<Style> {
+        let segments: Vec<&str> = key.split('.').collect();
+        let mut matches: Vec<((usize, usize), StyleSpec)> = self.styles.iter().filter_map(|(name, &spec)| {
+            let pattern: Vec<&str> = name.split('.').collect();
+            let applies = pattern.len() <= segments.len()
+                && pattern.iter().zip(segments.iter()).all(|(&p, &s)| p == "*" || p == s);
+            let literal = pattern.iter().filter(|&&p| p != "*").count();
+            if applies { Some(((pattern.len(), literal), spec)) } else { None }
+        }).collect();
+        matches.sort_by_key(|&(specificity, _)| specificity);
+        matches.into_iter().map(|(_, spec)| spec).reduce(|base, overlay| base + overlay).map(Style::from)
+    }
+
+    /// Check every slot against the capabilities of a terminal, returning
+    /// a finding, ordered by name, for each slot the terminal cannot
+    /// display as written: what it will display instead, which attributes
+    /// it will drop, and a portable replacement.
+    ///
+    /// # Examples
+    ///
+    ///     use colorparse::{parse, Attribute, Capabilities, ColorLevel, Theme};
+    ///
+    ///     let mut theme = Theme::new();
+    ///     theme.set("error", "bold #ff0000").unwrap();
+    ///     theme.set("hint", "italic cyan").unwrap();
+    ///     theme.set("warning", "bold yellow").unwrap();
+    ///     let caps = Capabilities::new(ColorLevel::Ansi16).without(Attribute::Italic);
+    ///     let findings = theme.check(&caps);
+    ///     assert_eq!(findings.len(), 2);
+    ///     assert_eq!(findings[0].name, "error");
+    ///     assert!(findings[0].is_downgraded());
+    ///     assert_eq!(findings[0].suggestion, parse("bold red").unwrap());
+    ///     assert_eq!(findings[1].dropped, vec![Attribute::Italic]);
+    ///     assert_eq!(findings[1].suggestion, parse("ul cyan").unwrap());
+    pub fn check(&self, caps: &Capabilities) -> Vec<Finding> {
+        self.styles.iter().filter_map(|(name, &spec)| check(name, Style::from(spec), caps)).collect()
+    }
+
+    /// Remove the slot `name`, returning its style.
+    pub fn remove(&mut self, name: &str) -> Option<Style> {
+        self.styles.remove(name).map(Style::from)
+    }
+
+    /// Iterate over the slots and their styles, ordered by name.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, Style)> {
+        self.styles.iter().map(|(name, &spec)| (name.as_str(), Style::from(spec)))
+    }
+
+    /// Return the number of slots set.
+    pub fn len(&self) -> usize {
+        self.styles.len()
+    }
+
+    /// Return true if no slots are set.
+    pub fn is_empty(&self) -> bool {
+        self.styles.is_empty()
+    }
+}
+
+#[cfg(feature = "toml")]
+impl Theme {
+    /// Parse a TOML theme file, in which each key names a slot and each
+    /// value is a style in Git's syntax.
+    ///
+    /// Tables nest slot names, so `[diff]` with `new = "green"` sets the
+    /// slot `diff.new`, as does the key `diff.new` or `"diff.new"` at the
+    /// top level. Requires the `toml` feature.
+    ///
+    /// # Examples
+    ///
+    ///     # extern crate ansi_term;
+    ///     # extern crate colorparse;
+    ///     use ansi_term::Color::{Cyan, Green, Red};
+    ///     use colorparse::{Theme, ThemeError};
+    ///
+    ///     # fn main() {
+    ///     let theme = Theme::from_toml_str(r#"
+    ///     error = "bold red"
+    ///     hint = "dim cyan"
+    ///
+    ///     [diff]
+    ///     new = "green"
+    ///     "#).unwrap();
+    ///     assert_eq!(theme.get("error"), Some(Red.bold()));
+    ///     assert_eq!(theme.get("hint"), Some(Cyan.dimmed()));
+    ///     assert_eq!(theme.get("diff.new"), Some(Green.normal()));
+    ///
+    ///     match Theme::from_toml_str("error = \"bold rde\"") {
+    ///         Err(ThemeError::InvalidStyle(slot, e)) => {
+    ///             assert_eq!(slot, "error");
+    ///             assert_eq!(e.word(), "rde");
+    ///         }
+    ///         _ => unreachable!(),
+    ///     }
+    ///     # }
+    pub fn from_toml_str(s: &str) -> Result<Self, ThemeError> {
+        let table: toml::Table = s.parse().map_err(|e: toml::de::Error| ThemeError::Syntax(e.message().to_string()))?;
+        let mut theme = Theme::new();
+        theme.set_toml_table("", &table)?;
+        Ok(theme)
+    }
+
+    fn set_toml_table(&mut self, prefix: &str, table: &toml::Table) -> Result<(), ThemeError> {
+        for (key, value) in table {
+            let name = if prefix.is_empty() { key.clone() } else { format!("{}.{}", prefix, key) };
+            match value {
+                toml::Value::String(spec) => self.set(name.as_str(), spec).map_err(|e| ThemeError::InvalidStyle(name, e))?,
+                toml::Value::Table(table) => self.set_toml_table(&name, table)?,
+                _ => return Err(ThemeError::InvalidValue(name, value.to_string())),
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "json")]
+impl Theme {
+    /// Parse a JSON theme file, an object in which each key names a slot
+    /// and each value is a style in Git's syntax.
+    ///
+    /// Nested objects nest slot names as the tables of
+    /// `Theme::from_toml_str` do, so `{"diff": {"new": "green"}}` sets the
+    /// slot `diff.new`, as does `{"diff.new": "green"}`. Requires the
+    /// `json` feature.
+    ///
+    /// # Examples
+    ///
+    ///     # extern crate ansi_term;
+    ///     # extern crate colorparse;
+    ///     use ansi_term::Color::{Green, Red};
+    ///     use colorparse::Theme;
+    ///
+    ///     # fn main() {
+    ///     let theme = Theme::from_json_str(r#"{
+    ///         "error": "bold red",
+    ///         "diff": { "new": "green", "old": "red" }
+    ///     }"#).unwrap();
+    ///     assert_eq!(theme.get("error"), Some(Red.bold()));
+    ///     assert_eq!(theme.get("diff.new"), Some(Green.normal()));
+    ///     assert_eq!(theme.len(), 3);
+    ///     # }
+    pub fn from_json_str(s: &str) -> Result<Self, ThemeError> {
+        let value: serde_json::Value = serde_json::from_str(s).map_err(|e| ThemeError::Syntax(e.to_string()))?;
+        let object = match value {
+            serde_json::Value::Object(object) => object,
+            _ => return Err(ThemeError::Syntax("expected an object".to_string())),
+        };
+        let mut theme = Theme::new();
+        theme.set_json_object("", &object)?;
+        Ok(theme)
+    }
+
+    fn set_json_object(&mut self, prefix: &str, object: &serde_json::Map<String, serde_json::Value>) -> Result<(), ThemeError> {
+        for (key, value) in object {
+            let name = if prefix.is_empty() { key.clone() } else { format!("{}.{}", prefix, key) };
+            match value {
+                serde_json::Value::String(spec) => {
+                    self.set(name.as_str(), spec).map_err(|e| ThemeError::InvalidStyle(name, e))?
+                }
+                serde_json::Value::Object(object) => self.set_json_object(&name, object)?,
+                _ => return Err(ThemeError::InvalidValue(name, value.to_string())),
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "yaml")]
+impl Theme {
+    /// Parse a YAML theme file, a mapping in which each key names a slot
+    /// and each value is a style in Git's syntax.
+    ///
+    /// Nested mappings nest slot names as the tables of
+    /// `Theme::from_toml_str` do, so a `diff:` section with `added: green`
+    /// sets the slot `diff.added`, as does the key `diff.added` at the top
+    /// level. A plain value is read as a string, so `added: 208` sets the
+    /// color 208, while a key with no value, `~`, or `null` is an
+    /// `InvalidValue`.
+    ///
+    /// Only the subset of YAML that theme files need is accepted:
+    /// `key: value` lines, with mappings nested by indentation, plain,
+    /// `'single'`, or `"double"`-quoted scalars, and `#` comments. Flow
+    /// collections, sequences, block scalars, anchors, aliases, tags, and
+    /// further documents are rejected with `ThemeError::Syntax` rather
+    /// than misread. Requires the `yaml` feature.
+    ///
+    /// # Examples
+    ///
+    ///     # extern crate ansi_term;
+    ///     # extern crate colorparse;
+    ///     use ansi_term::Color::{Green, Red};
+    ///     use colorparse::{Theme, ThemeError};
+    ///
+    ///     # fn main() {
+    ///     let theme = Theme::from_yaml_str("
+    ///     error: bold red
+    ///     diff:
+    ///       added: green
+    ///       removed: \"red\"  # quoted
+    ///     ").unwrap();
+    ///     assert_eq!(theme.get("error"), Some(Red.bold()));
+    ///     assert_eq!(theme.get("diff.added"), Some(Green.normal()));
+    ///     assert_eq!(theme.len(), 3);
+    ///
+    ///     assert_eq!(Theme::from_yaml_str("diff: {added: green}"),
+    ///                Err(ThemeError::Syntax("line 1: flow collections are not supported".to_string())));
+    ///     # }
+    pub fn from_yaml_str(s: &str) -> Result<Self, ThemeError> {
+        let entries = yaml::parse(s).map_err(ThemeError::Syntax)?;
+        let mut theme = Theme::new();
+        theme.set_yaml_mapping("", &entries)?;
+        Ok(theme)
+    }
+
+    fn set_yaml_mapping(&mut self, prefix: &str, entries: &[yaml::Entry]) -> Result<(), ThemeError> {
+        for entry in entries {
+            let name = if prefix.is_empty() { entry.key.clone() } else { format!("{}.{}", prefix, entry.key) };
+            match &entry.value {
+                yaml::Value::Scalar(spec) => self.set(name.as_str(), spec).map_err(|e| ThemeError::InvalidStyle(name, e))?,
+                yaml::Value::Mapping(entries) => self.set_yaml_mapping(&name, entries)?,
+                yaml::Value::Null => return Err(ThemeError::InvalidValue(name, "null".to_string())),
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Type for errors returned by the theme file loaders, such as
+/// `Theme::from_toml_str`.
+#[derive(Debug, PartialEq)]
+pub enum ThemeError {
+    /// The file is not valid in its format; carries the underlying
+    /// parser's description of the problem.
+    Syntax(String),
+    /// A slot's value is neither a string nor a nested section; carries
+    /// the slot and the value.
+    InvalidValue(String, String),
+    /// A slot's style failed to parse; carries the slot and the error,
+    /// which gives the word at fault.
+    InvalidStyle(String, Error),
+}
+
+impl std::fmt::Display for ThemeError {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Syntax(message) => write!(fmt, "Error loading theme: {}", message),
+            Self::InvalidValue(slot, value) => {
+                write!(fmt, "Error loading theme: invalid value {} for \"{}\"", value, slot)
+            }
+            Self::InvalidStyle(slot, e) => write!(fmt, "Error loading theme slot \"{}\": {}", slot, e),
+        }
+    }
+}
+
+impl std::error::Error for ThemeError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::InvalidStyle(_, e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// Type for errors returned by `render_template`.
+#[derive(Debug, PartialEq)]
+pub enum TemplateError {
+    /// A placeholder named a slot not present in the theme.
+    UnknownSlot(String, String),
+    /// A `{` or `}` appeared without its partner; literal braces are
+    /// written `{{` and `}}`.
+    UnmatchedBrace(String),
+}
+
+impl std::fmt::Display for TemplateError {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::UnknownSlot(s, slot) => {
+                write!(fmt, "Error rendering template \"{}\": unknown slot \"{}\"", s, slot)
+            }
+            Self::UnmatchedBrace(s) => {
+                write!(fmt, "Error rendering template \"{}\": unmatched brace", s)
+            }
+        }
+    }
+}
+
+impl std::error::Error for TemplateError {}
+
+/// Render `template`, replacing each `{slot}` placeholder with the escape
+/// sequence that switches to the style of that slot in `theme`.
+///
+/// The placeholder `{reset}` switches back to the plain style, unless the
+/// theme defines a `reset` slot. Write `{{` and `}}` for literal braces.
+/// The output always ends in the plain style.
+///
+/// # Examples
+///
+///     use colorparse::{render_template, Theme};
+///
+///     let mut theme = Theme::new();
+///     theme.set("error", "bold red").unwrap();
+///     theme.set("path", "cyan").unwrap();
+///     assert_eq!(render_template("{error}failed:{reset} {path}/tmp", &theme).unwrap(),
+///                "\x1b[1;31mfailed:\x1b[0m \x1b[36m/tmp\x1b[0m");
+pub fn render_template(template: &str, theme: &Theme) -> Result<String, TemplateError> {
+    let unmatched = || TemplateError::UnmatchedBrace(template.to_string());
+    let mut segments = Vec::new();
+    let mut style = Style::new();
+    let mut rest = template;
+    while let Some(i) = rest.find(['{', '}']) {
+        segments.push((style, &rest[..i]));
+        let brace = &rest[i..i + 1];
+        rest = &rest[i + 1..];
+        if rest.starts_with(brace) {
+            segments.push((style, brace));
+            rest = &rest[1..];
+        } else if brace == "}" {
+            return Err(unmatched());
+        } else {
+            let end = rest.find('}').ok_or_else(unmatched)?;
+            let slot = &rest[..end];
+            if slot.contains('{') {
+                return Err(unmatched());
+            }
+            style = match theme.get(slot) {
+                Some(style) => style,
+                None if slot == "reset" => Style::new(),
+                None => return Err(TemplateError::UnknownSlot(template.to_string(), slot.to_string())),
+            };
+            rest = &rest[end + 1..];
+        }
+    }
+    segments.push((style, rest));
+    Ok(render(segments))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::TemplateError::*;
+    use ansi_term::Color::*;
+
+    #[test]
+    fn test_resolve() {
+        let mut theme = Theme::new();
+        assert_eq!(theme.resolve("diff"), None);
+        theme.insert("diff", Blue.normal());
+        theme.insert("*.old", Red.normal());
+        theme.insert("diff.old", Green.normal());
+        theme.insert("*.new", Yellow.bold());
+        theme.insert("a.*", Cyan.normal());
+        theme.insert("*.b", Purple.normal());
+        assert_eq!(theme.resolve("diff"), Some(Blue.normal()));
+        assert_eq!(theme.resolve("diff.old"), Some(Green.normal()));
+        assert_eq!(theme.resolve("diff.old.moved"), Some(Green.normal()));
+        assert_eq!(theme.resolve("status.old"), Some(Red.normal()));
+        assert_eq!(theme.resolve("diff.new"), Some(Yellow.bold()));
+        assert_eq!(theme.resolve("a.b"), Some(Cyan.normal()));
+        assert_eq!(theme.resolve("differ"), None);
+        assert_eq!(theme.resolve(""), None);
+
+        theme.set("*.new.moved", "nobold ul default").unwrap();
+        theme.set("diff.new.plain", "reset dim").unwrap();
+        assert_eq!(theme.resolve("diff.new.moved"), Some(Style::new().underline()));
+        assert_eq!(theme.resolve("diff.new.plain"), Some(Style::new().dimmed()));
+        assert_eq!(theme.get("*.new.moved"), Some(Style::new().underline()));
+    }
+
+    #[test]
+    fn test_theme() {
+        let mut theme = Theme::new();
+        assert!(theme.is_empty());
+        assert_eq!(theme.set("error", "bold red"), Ok(()));
+        assert_eq!(theme.insert("hint", Cyan.dimmed()), None);
+        assert_eq!(theme.insert("hint", Cyan.normal()), Some(Cyan.dimmed()));
+        assert!(theme.set("error", "bold blu").is_err());
+        assert_eq!(theme.get("error"), Some(Red.bold()));
+        assert_eq!(theme.len(), 2);
+        assert_eq!(theme.iter().collect::<Vec<_>>(), vec![("error", Red.bold()), ("hint", Cyan.normal())]);
+        assert_eq!(theme.remove("hint"), Some(Cyan.normal()));
+        assert_eq!(theme.get("hint"), None);
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn test_from_toml_str() {
+        let theme = Theme::from_toml_str(r#"
+error = "bold red"
+"diff.old" = "red"
+status.added = "green"
+
+[diff]
+new = "green ul"
+
+[diff.moved]
+old = "dim magenta"
+"#).unwrap();
+        assert_eq!(theme.iter().collect::<Vec<_>>(), vec![
+            ("diff.moved.old", Purple.dimmed()),
+            ("diff.new", Green.underline()),
+            ("diff.old", Red.normal()),
+            ("error", Red.bold()),
+            ("status.added", Green.normal()),
+        ]);
+        assert!(Theme::from_toml_str("").unwrap().is_empty());
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn test_from_toml_str_err() {
+        assert!(matches!(Theme::from_toml_str("error = "), Err(ThemeError::Syntax(_))));
+        assert_eq!(Theme::from_toml_str("[diff]\nnew = 2"),
+                   Err(ThemeError::InvalidValue("diff.new".to_string(), "2".to_string())));
+        let err = Theme::from_toml_str("[diff]\nnew = \"green blod\"").unwrap_err();
+        match &err {
+            ThemeError::InvalidStyle(slot, e) => assert_eq!((slot.as_str(), e.word()), ("diff.new", "blod")),
+            _ => panic!("{:?}", err),
+        }
+        assert_eq!(err.to_string(),
+                   "Error loading theme slot \"diff.new\": Error parsing style \"green blod\": unknown word: \"blod\"");
+    }
+
+    #[cfg(feature = "yaml")]
+    #[test]
+    fn test_from_yaml_str() {
+        let theme = Theme::from_yaml_str(r#"
+error: bold red
+"diff.old": red
+status.added: 208
+diff:
+  new: 'green ul'
+  moved:
+    old: dim magenta  # moved lines
+"#).unwrap();
+        assert_eq!(theme.iter().collect::<Vec<_>>(), vec![
+            ("diff.moved.old", Purple.dimmed()),
+            ("diff.new", Green.underline()),
+            ("diff.old", Red.normal()),
+            ("error", Red.bold()),
+            ("status.added", Fixed(208).normal()),
+        ]);
+        assert!(Theme::from_yaml_str("").is_ok_and(|theme| theme.is_empty()));
+    }
+
+    #[cfg(feature = "yaml")]
+    #[test]
+    fn test_from_yaml_str_err() {
+        assert_eq!(Theme::from_yaml_str("error: &e red"),
+                   Err(ThemeError::Syntax("line 1: anchors and aliases are not supported".to_string())));
+        assert_eq!(Theme::from_yaml_str("diff:
+  - green"),
+                   Err(ThemeError::Syntax("line 2: sequences are not supported".to_string())));
+        assert_eq!(Theme::from_yaml_str("diff:
+  new: ~"),
+                   Err(ThemeError::InvalidValue("diff.new".to_string(), "null".to_string())));
+        let err = Theme::from_yaml_str("diff:
+  new: green blod").unwrap_err();
+        match &err {
+            ThemeError::InvalidStyle(slot, e) => assert_eq!((slot.as_str(), e.word()), ("diff.new", "blod")),
+            _ => panic!("{:?}", err),
+        }
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn test_from_json_str() {
+        let theme = Theme::from_json_str(r#"{
+            "error": "bold red",
+            "diff.old": "red",
+            "diff": { "new": "green ul", "moved": { "old": "dim magenta" } },
+            "empty": {}
+        }"#).unwrap();
+        assert_eq!(theme.iter().collect::<Vec<_>>(), vec![
+            ("diff.moved.old", Purple.dimmed()),
+            ("diff.new", Green.underline()),
+            ("diff.old", Red.normal()),
+            ("error", Red.bold()),
+        ]);
+        assert!(Theme::from_json_str("{}").unwrap().is_empty());
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn test_from_json_str_err() {
+        assert!(matches!(Theme::from_json_str("{\"error\": "), Err(ThemeError::Syntax(_))));
+        assert_eq!(Theme::from_json_str("[\"red\"]"), Err(ThemeError::Syntax("expected an object".to_string())));
+        assert_eq!(Theme::from_json_str(r#"{"diff": {"new": null}}"#),
+                   Err(ThemeError::InvalidValue("diff.new".to_string(), "null".to_string())));
+        match Theme::from_json_str(r#"{"diff": {"new": "green blod"}}"#) {
+            Err(ThemeError::InvalidStyle(slot, e)) => assert_eq!((slot.as_str(), e.word()), ("diff.new", "blod")),
+            result => panic!("{:?}", result),
+        }
+    }
+
+    #[test]
+    fn test_render_template() {
+        let mut theme = Theme::new();
+        theme.insert("error", Red.bold());
+        theme.insert("path", Cyan.normal());
+
+        macro_rules! test {
+            ($template:expr => Ok $out:expr) => {
+                assert_eq!(render_template($template, &theme), Ok($out.to_string()));
+            };
+            ($template:expr => $err:expr) => {
+                assert_eq!(render_template($template, &theme), Err($err));
+            };
+        }
+
+        test!("" => Ok "");
+        test!("plain" => Ok "plain");
+        test!("{error}" => Ok "");
+        test!("{error}a{error}b" => Ok "\x1b[1;31mab\x1b[0m");
+        test!("{error}a{path}b{reset}c" => Ok "\x1b[1;31ma\x1b[0;36mb\x1b[0mc");
+        test!("{{{error}}}{{" => Ok "{\x1b[1;31m}{\x1b[0m");
+        test!("{nope}" => UnknownSlot("{nope}".to_string(), "nope".to_string()));
+        test!("{}" => UnknownSlot("{}".to_string(), "".to_string()));
+        test!("{error" => UnmatchedBrace("{error".to_string()));
+        test!("error}" => UnmatchedBrace("error}".to_string()));
+        test!("{err{or}" => UnmatchedBrace("{err{or}".to_string()));
+
+        theme.insert("reset", Style::new().underline());
+        test!("{reset}x" => Ok "\x1b[4mx\x1b[0m");
+    }
+}
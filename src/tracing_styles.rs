@@ -0,0 +1,241 @@
+//! Styling `tracing` output by level, configured with Git-syntax specs.
+//!
+//! # Examples
+//!
+//!     # extern crate colorparse;
+//!     # extern crate tracing_subscriber;
+//!     use colorparse::tracing_styles::{LevelStyleFormat, LevelStyles};
+//!
+//!     # fn main() {
+//!     let styles = LevelStyles::from_env("MYAPP_LOG_STYLE").unwrap();
+//!     tracing_subscriber::fmt()
+//!         .event_format(LevelStyleFormat::new(tracing_subscriber::fmt::format(), styles))
+//!         .init();
+//!     # }
+
+use std::fmt;
+
+use ansi_term::{Color, Style};
+use tracing_core::{Event, Level, Subscriber};
+use tracing_subscriber::fmt::format::Writer;
+use tracing_subscriber::fmt::{FmtContext, FormatEvent, FormatFields};
+use tracing_subscriber::registry::LookupSpan;
+
+use crate::{colors_enabled, parse, Error};
+
+/// A style for each `tracing` level.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LevelStyles {
+    error: Style,
+    warn: Style,
+    info: Style,
+    debug: Style,
+    trace: Style,
+}
+
+impl LevelStyles {
+    /// Create the default styles, matching the colors `tracing_subscriber`
+    /// uses for levels.
+    pub fn new() -> Self {
+        LevelStyles {
+            error: Color::Red.normal(),
+            warn: Color::Yellow.normal(),
+            info: Color::Green.normal(),
+            debug: Color::Blue.normal(),
+            trace: Color::Purple.normal(),
+        }
+    }
+
+    /// Parse a comma-separated list of `level=spec` entries, such as
+    /// `error=bold red,warn=yellow`, on top of the default styles. Level
+    /// names are case-insensitive, and each spec uses Git's syntax.
+    pub fn parse(s: &str) -> Result<Self, LevelStylesError> {
+        let mut styles = Self::new();
+        for entry in s.split(',') {
+            if entry.trim().is_empty() {
+                continue;
+            }
+            let mut parts = entry.splitn(2, '=');
+            let level = parts.next().unwrap_or("").trim();
+            let spec = match parts.next() {
+                Some(spec) => spec,
+                None => return Err(LevelStylesError::InvalidEntry(s.to_string(), entry.to_string())),
+            };
+            let level = match level.parse::<Level>() {
+                Ok(level) => level,
+                Err(_) => return Err(LevelStylesError::UnknownLevel(s.to_string(), level.to_string())),
+            };
+            styles.set(level, parse(spec).map_err(LevelStylesError::InvalidStyle)?);
+        }
+        Ok(styles)
+    }
+
+    /// Parse the styles from the environment variable `var` with `parse`,
+    /// or return the default styles if the variable is unset.
+    pub fn from_env(var: &str) -> Result<Self, LevelStylesError> {
+        match std::env::var(var) {
+            Ok(value) => Self::parse(&value),
+            Err(_) => Ok(Self::new()),
+        }
+    }
+
+    /// Return the style for `level`.
+    pub fn get(&self, level: Level) -> Style {
+        match level {
+            Level::ERROR => self.error,
+            Level::WARN => self.warn,
+            Level::INFO => self.info,
+            Level::DEBUG => self.debug,
+            Level::TRACE => self.trace,
+        }
+    }
+
+    /// Set the style for `level`.
+    pub fn set(&mut self, level: Level, style: Style) {
+        match level {
+            Level::ERROR => self.error = style,
+            Level::WARN => self.warn = style,
+            Level::INFO => self.info = style,
+            Level::DEBUG => self.debug = style,
+            Level::TRACE => self.trace = style,
+        }
+    }
+}
+
+impl Default for LevelStyles {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Type for errors returned by `LevelStyles::parse`.
+#[derive(Debug, PartialEq)]
+pub enum LevelStylesError {
+    /// An entry did not have the form `level=spec`.
+    InvalidEntry(String, String),
+    /// An entry named a level other than error, warn, info, debug, or trace.
+    UnknownLevel(String, String),
+    /// An entry's style failed to parse.
+    InvalidStyle(Error),
+}
+
+impl fmt::Display for LevelStylesError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::InvalidEntry(s, entry) => {
+                write!(fmt, "Error parsing level styles \"{}\": expected level=style, found \"{}\"", s, entry)
+            }
+            Self::UnknownLevel(s, level) => {
+                write!(fmt, "Error parsing level styles \"{}\": unknown level \"{}\"", s, level)
+            }
+            Self::InvalidStyle(e) => e.fmt(fmt),
+        }
+    }
+}
+
+impl std::error::Error for LevelStylesError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::InvalidStyle(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// A `FormatEvent` that formats each event with an inner formatter, then
+/// styles the whole line according to the event's level.
+///
+/// The inner formatter runs without ANSI escapes of its own. Lines are left
+/// unstyled when the subscriber has ANSI output disabled, or when
+/// `colors_enabled()` returns false.
+#[derive(Clone, Debug)]
+pub struct LevelStyleFormat<F> {
+    inner: F,
+    styles: LevelStyles,
+}
+
+impl<F> LevelStyleFormat<F> {
+    /// Wrap the formatter `inner`, styling its output with `styles`.
+    pub fn new(inner: F, styles: LevelStyles) -> Self {
+        LevelStyleFormat { inner, styles }
+    }
+}
+
+impl<S, N, F> FormatEvent<S, N> for LevelStyleFormat<F>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    N: for<'a> FormatFields<'a> + 'static,
+    F: FormatEvent<S, N>,
+{
+    fn format_event(&self, ctx: &FmtContext<'_, S, N>, mut writer: Writer<'_>, event: &Event<'_>) -> fmt::Result {
+        let style = self.styles.get(*event.metadata().level());
+        if !writer.has_ansi_escapes() || !colors_enabled() {
+            return self.inner.format_event(ctx, writer, event);
+        }
+        let mut buf = String::new();
+        self.inner.format_event(ctx, Writer::new(&mut buf), event)?;
+        let line = buf.trim_end_matches('\n');
+        write!(writer, "{}{}{}{}", style.prefix(), line, style.suffix(), &buf[line.len()..])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::LevelStylesError::*;
+    use crate::global::lock_override;
+    use crate::{set_override, ErrorKind};
+    use ansi_term::Color::*;
+    use std::io;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn test_parse() {
+        let styles = LevelStyles::parse("error=bold red, WARN=yellow ul,,info=normal").unwrap();
+        assert_eq!(styles.get(Level::ERROR), Red.bold());
+        assert_eq!(styles.get(Level::WARN), Yellow.underline());
+        assert_eq!(styles.get(Level::INFO), Style::new());
+        assert_eq!(styles.get(Level::TRACE), Purple.normal());
+        assert_eq!(LevelStyles::parse(""), Ok(LevelStyles::new()));
+
+        assert_eq!(LevelStyles::parse("error"), Err(InvalidEntry("error".to_string(), "error".to_string())));
+        assert_eq!(LevelStyles::parse("fatal=red"), Err(UnknownLevel("fatal=red".to_string(), "fatal".to_string())));
+        assert_eq!(LevelStyles::parse("warn=blu"),
+                   Err(InvalidStyle(Error::new(ErrorKind::UnknownWord, "blu", "blu", 0..3))));
+    }
+
+    #[derive(Clone, Default)]
+    struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+    impl io::Write for Buffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_format() {
+        let _lock = lock_override();
+        set_override(Some(true));
+        let buffer = Buffer::default();
+        let writer = buffer.clone();
+        let format = tracing_subscriber::fmt::format().without_time().with_target(false);
+        let styles = LevelStyles::parse("warn=bold yellow,info=normal").unwrap();
+        let subscriber = tracing_subscriber::fmt()
+            .with_ansi(true)
+            .event_format(LevelStyleFormat::new(format, styles))
+            .with_writer(move || writer.clone())
+            .finish();
+        tracing_core::dispatcher::with_default(&subscriber.into(), || {
+            tracing::warn!("careful");
+            tracing::info!("fine");
+        });
+        set_override(None);
+        let output = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+        assert_eq!(output, "\x1b[1;33m WARN careful\x1b[0m\n INFO fine\n");
+    }
+}
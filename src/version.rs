@@ -0,0 +1,199 @@
+use std::fmt;
+
+use ansi_term::Color;
+
+use crate::{downgrade, Attribute, ColorLevel, StyleSpec};
+
+/// A Git release, for targeting the color syntax it understands.
+///
+/// # Examples
+///
+///     use colorparse::GitVersion;
+///
+///     assert!(GitVersion::new(2, 1, 4) < GitVersion::new(2, 26, 0));
+///     assert_eq!(GitVersion::new(2, 26, 0).to_string(), "2.26.0");
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct GitVersion {
+    major: u16,
+    minor: u16,
+    patch: u16,
+}
+
+impl GitVersion {
+    /// The first release accepting 24-bit `#rrggbb` colors and the `no`
+    /// prefix for attributes.
+    pub const RGB: GitVersion = GitVersion::new(2, 3, 0);
+    /// The first release accepting the `italic` and `strike` attributes.
+    pub const ITALIC_STRIKE: GitVersion = GitVersion::new(2, 9, 0);
+    /// The first release accepting the `default` color, the `reset`
+    /// pseudo-attribute, and the bright color names, such as `brightred`.
+    pub const DEFAULT_COLOR: GitVersion = GitVersion::new(2, 26, 0);
+
+    /// Create a version from its components.
+    pub const fn new(major: u16, minor: u16, patch: u16) -> Self {
+        GitVersion { major, minor, patch }
+    }
+}
+
+impl fmt::Display for GitVersion {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+/// Write `style` in Git syntax, as it would appear in a config file, such
+/// as `bold red blue`: the inverse of `parse`.
+///
+/// Attributes come first, in the order of `Attribute::ALL`, then the
+/// foreground and background colors, as `StyleSpec`'s `Display` writes
+/// them. Parsing the result gives back the same style. `style` may be a
+/// `Style` or a `StyleSpec`, or a reference to either.
+///
+/// # Examples
+///
+///     use colorparse::{parse, to_config_string};
+///
+///     let style = parse("Blue ul BOLD #FF8000").unwrap();
+///     assert_eq!(to_config_string(&style), "bold ul blue #ff8000");
+///     assert_eq!(parse(&to_config_string(style)), Ok(style));
+///     assert_eq!(to_config_string(parse("").unwrap()), "normal");
+pub fn to_config_string<S: Into<StyleSpec>>(style: S) -> String {
+    style.into().to_string()
+}
+
+/// Write `style` in Git syntax that `version` can parse, rewriting or
+/// dropping what it cannot.
+///
+/// Releases before `GitVersion::RGB` get 24-bit colors approximated from
+/// the 256-color palette, and no negated attributes; releases before
+/// `GitVersion::ITALIC_STRIKE` get no `italic` or `strike`, and releases
+/// before `GitVersion::DEFAULT_COLOR` get no `default` color and no
+/// `reset`, and a bright foreground, colors 8 through 15, becomes bold
+/// with the basic color, as `brightred` becomes `bold red`, unless the
+/// style clears bold. A bright background keeps its number, which every
+/// release accepts, since bold does not brighten backgrounds. No release
+/// understands the hidden attribute, overline, rapid blink, or an
+/// underline color, so those are always dropped.
+///
+/// # Examples
+///
+///     use colorparse::{to_config_string_for, GitVersion};
+///
+///     let style = colorparse::parse("italic bold #ff0000").unwrap();
+///     assert_eq!(to_config_string_for(style, GitVersion::new(2, 26, 0)), "bold italic #ff0000");
+///     assert_eq!(to_config_string_for(style, GitVersion::new(2, 1, 4)), "bold 196");
+///
+///     let spec = colorparse::parse_spec("reset brightred").unwrap();
+///     assert_eq!(to_config_string_for(spec, GitVersion::new(2, 40, 0)), "reset 9");
+///     assert_eq!(to_config_string_for(spec, GitVersion::new(2, 25, 0)), "bold red");
+pub fn to_config_string_for<S: Into<StyleSpec>>(style: S, version: GitVersion) -> String {
+    let spec = style.into();
+    let level = if version < GitVersion::RGB { ColorLevel::Ansi256 } else { ColorLevel::TrueColor };
+    let supported = |attribute| match attribute {
+        Attribute::Hidden | Attribute::Overline | Attribute::RapidBlink => false,
+        Attribute::Italic | Attribute::Strikethrough => version >= GitVersion::ITALIC_STRIKE,
+        _ => true,
+    };
+    let color = |color: Color| downgrade(color, level);
+    let mut builder = StyleSpec::builder();
+    if spec.is_reset() && version >= GitVersion::DEFAULT_COLOR {
+        builder = builder.reset();
+    }
+    if let Some(fg) = spec.foreground() {
+        builder = match color(fg) {
+            Color::Fixed(n @ 8..=15) if version < GitVersion::DEFAULT_COLOR && !spec.clears(Attribute::Bold) => {
+                builder.fg(downgrade::from_index(n - 8)).bold()
+            }
+            fg => builder.fg(fg),
+        };
+    }
+    if let Some(bg) = spec.background() {
+        builder = builder.bg(color(bg));
+    }
+    if version >= GitVersion::DEFAULT_COLOR {
+        if spec.is_default_fg() {
+            builder = builder.default_fg();
+        }
+        if spec.is_default_bg() {
+            builder = builder.default_bg();
+        }
+    }
+    for attribute in spec.attributes().filter(|&a| supported(a)) {
+        builder = builder.attribute(attribute);
+    }
+    if version >= GitVersion::RGB {
+        for attribute in spec.cleared().filter(|&a| supported(a)) {
+            builder = builder.no(attribute);
+        }
+    }
+    builder.build().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ansi_term::Color::*;
+    use crate::{parse, parse_spec};
+
+    #[test]
+    fn test_to_config_string() {
+        for s in ["bold red blue", "normal 208", "dim italic 9 #010203", "reverse black white", "normal"].iter() {
+            let style = parse(s).unwrap();
+            assert_eq!(to_config_string(style), *s);
+            assert_eq!(parse(&to_config_string(style)), Ok(style));
+        }
+        assert_eq!(to_config_string(Red.on(Fixed(1))), "red 1");
+        let (style, spec) = (&Red.bold(), &parse_spec("reset blue").unwrap());
+        assert_eq!(to_config_string(style), "bold red");
+        assert_eq!(to_config_string(spec), "reset blue");
+        assert_eq!(to_config_string(StyleSpec::builder().no(Attribute::Bold).default_fg().build()), "nobold default");
+    }
+
+    #[test]
+    fn test_to_config_string_for() {
+        let current = GitVersion::new(2, 40, 0);
+        let latest = GitVersion::new(2, 26, 0);
+        let before_default = GitVersion::new(2, 25, 0);
+        let old = GitVersion::new(2, 8, 0);
+        let ancient = GitVersion::new(1, 9, 5);
+
+        let style = parse("bold strike red #102030").unwrap();
+        assert_eq!(to_config_string_for(style, latest), "bold strike red #102030");
+        assert_eq!(to_config_string_for(style, old), "bold red #102030");
+        assert_eq!(to_config_string_for(style, ancient), "bold red 234");
+        assert_eq!(to_config_string_for(style, current), "bold strike red #102030");
+        assert_eq!(to_config_string_for(parse("9 normal").unwrap(), current), "9");
+        assert_eq!(to_config_string_for(parse("9 normal").unwrap(), ancient), "bold red");
+        assert_eq!(to_config_string_for(parse("brightblack 15").unwrap(), before_default), "bold black 15");
+
+        let spec = parse_spec("reset red").unwrap();
+        assert_eq!(to_config_string_for(spec, current), "reset red");
+        assert_eq!(to_config_string_for(spec, latest), "reset red");
+        assert_eq!(to_config_string_for(spec, before_default), "red");
+        assert_eq!(to_config_string_for(parse_spec("reset").unwrap(), current), "reset");
+        assert_eq!(to_config_string_for(parse_spec("reset").unwrap(), before_default), "normal");
+        assert_eq!(to_config_string_for(parse_spec("reset dim nobold brightcyan").unwrap(), current),
+                   "reset dim nobold 14");
+        assert_eq!(to_config_string_for(parse_spec("nobold brightcyan").unwrap(), before_default), "nobold 14");
+
+        let spec = StyleSpec::builder().fg(Red).hidden().underline().underline_color(Blue)
+            .no(Attribute::Bold).no(Attribute::Italic).build();
+        assert_eq!(to_config_string_for(spec, current), "ul nobold noitalic red");
+        assert_eq!(to_config_string_for(spec, latest), "ul nobold noitalic red");
+        assert_eq!(to_config_string_for(spec, old), "ul nobold red");
+        assert_eq!(to_config_string_for(spec, ancient), "ul red");
+        assert_eq!(to_config_string_for(StyleSpec::builder().hidden().build(), latest), "normal");
+
+        let spec = StyleSpec::builder().default_fg().bg(Red).build();
+        assert_eq!(to_config_string_for(spec, current), "default red");
+        assert_eq!(to_config_string_for(spec, latest), "default red");
+        assert_eq!(to_config_string_for(spec, old), "normal red");
+    }
+
+    #[test]
+    fn test_git_version() {
+        assert!(GitVersion::new(2, 10, 0) > GitVersion::ITALIC_STRIKE);
+        assert!(GitVersion::new(1, 99, 99) < GitVersion::RGB);
+        assert_eq!(GitVersion::RGB.to_string(), "2.3.0");
+    }
+}
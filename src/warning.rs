@@ -0,0 +1,104 @@
+use std::fmt;
+use std::ops::Range;
+
+use crate::span;
+use crate::{Attribute, Category};
+
+/// A word in a style string that parses, but is probably a mistake, as
+/// reported by `parse_with_diagnostics`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Warning {
+    kind: WarningKind,
+    input: String,
+    word: String,
+    span: Range<usize>,
+}
+
+/// The kinds of warning `parse_with_diagnostics` reports, as returned by
+/// `Warning::kind`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum WarningKind {
+    /// The word undoes an attribute set earlier in the same style, as the
+    /// `nobold` in `bold red nobold` does, or sets one cleared earlier.
+    Cancelled(Attribute),
+    /// The word `normal` (or `-1`) came after the foreground color, where
+    /// it sets the background to the default, which it already is; it may
+    /// have been meant as the foreground.
+    NormalAfterColor,
+    /// The word sets the background to the same color as the foreground,
+    /// which makes the text invisible.
+    SameColors,
+}
+
+impl Warning {
+    /// Create a warning of `kind` for `word`, a slice of `input`.
+    pub(crate) fn at(kind: WarningKind, input: &str, word: &str) -> Self {
+        Warning { kind, input: input.to_string(), word: word.to_string(), span: span(input, word) }
+    }
+
+    /// Return what looks wrong.
+    pub fn kind(&self) -> &WarningKind {
+        &self.kind
+    }
+
+    /// Return the input the warning is about.
+    pub fn input(&self) -> &str {
+        &self.input
+    }
+
+    /// Return the word the warning is about.
+    pub fn word(&self) -> &str {
+        &self.word
+    }
+
+    /// Return the byte range of the word within the input.
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+
+    /// Return the stable code identifying this kind of warning, such as
+    /// `CP1001`. Warning codes start at `CP1001`, apart from the error
+    /// codes listed at `Error::code`, and like them are never reused or
+    /// reassigned across versions.
+    pub fn code(&self) -> &'static str {
+        match self.kind {
+            WarningKind::Cancelled(_) => "CP1001",
+            WarningKind::NormalAfterColor => "CP1002",
+            WarningKind::SameColors => "CP1003",
+        }
+    }
+
+    /// Return the stable short name of this kind of warning, such as
+    /// `cancelled-attribute`.
+    pub fn name(&self) -> &'static str {
+        match self.kind {
+            WarningKind::Cancelled(_) => "cancelled-attribute",
+            WarningKind::NormalAfterColor => "normal-after-color",
+            WarningKind::SameColors => "same-colors",
+        }
+    }
+
+    /// Return the category this kind of warning belongs to, which is
+    /// always `Category::Suspicious`.
+    pub fn category(&self) -> Category {
+        Category::Suspicious
+    }
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        let (s, word) = (&self.input, &self.word);
+        match self.kind {
+            WarningKind::Cancelled(attribute) => {
+                write!(fmt, "Warning for style \"{}\": \"{}\" overrides the earlier setting of {}", s, word, attribute.name())
+            }
+            WarningKind::NormalAfterColor => {
+                write!(fmt, "Warning for style \"{}\": \"{}\" after a color sets the default background", s, word)
+            }
+            WarningKind::SameColors => {
+                write!(fmt, "Warning for style \"{}\": background \"{}\" is the same as the foreground", s, word)
+            }
+        }
+    }
+}
@@ -0,0 +1,94 @@
+use std::borrow::Cow;
+
+use unicode_width::UnicodeWidthStr;
+
+/// Return the length of the escape sequence at the start of `s`, which
+/// begins with ESC: a CSI sequence such as SGR, an OSC sequence such as a
+/// hyperlink (ended by BEL or ESC `\`), or a two-byte escape.
+pub(crate) fn escape_len(s: &str) -> usize {
+    let bytes = s.as_bytes();
+    match bytes.get(1) {
+        Some(b'[') => bytes[2..].iter().position(|b| (0x40..=0x7e).contains(b)).map_or(bytes.len(), |i| i + 3),
+        Some(b']') => {
+            let mut i = 2;
+            while i < bytes.len() {
+                match bytes[i] {
+                    0x07 => return i + 1,
+                    0x1b if bytes.get(i + 1) == Some(&b'\\') => return i + 2,
+                    _ => i += 1,
+                }
+            }
+            bytes.len()
+        }
+        Some(_) => 1 + s[1..].chars().next().map_or(0, char::len_utf8),
+        None => 1,
+    }
+}
+
+/// Return an iterator over the runs of `s` outside escape sequences.
+fn text_runs(mut s: &str) -> impl Iterator<Item = &str> {
+    std::iter::from_fn(move || {
+        while !s.is_empty() {
+            let end = s.find('\x1b').unwrap_or(s.len());
+            let (text, rest) = s.split_at(end);
+            s = if rest.is_empty() { rest } else { &rest[escape_len(rest)..] };
+            if !text.is_empty() {
+                return Some(text);
+            }
+        }
+        None
+    })
+}
+
+/// Remove escape sequences from `s`, such as those written by `paint`,
+/// `render`, and `cformat!`, leaving the text a terminal would display.
+///
+/// # Examples
+///
+///     # colorparse::set_override(Some(true));
+///     let style = colorparse::parse("bold red").unwrap();
+///     let s = format!("{}: ok", colorparse::paint(style, "error"));
+///     assert_eq!(colorparse::strip_ansi(&s), "error: ok");
+pub fn strip_ansi(s: &str) -> Cow<'_, str> {
+    if !s.contains('\x1b') {
+        return Cow::Borrowed(s);
+    }
+    Cow::Owned(text_runs(s).collect())
+}
+
+/// Return the number of terminal columns `s` occupies, ignoring escape
+/// sequences and counting wide characters (such as CJK) as two columns.
+///
+/// # Examples
+///
+///     # colorparse::set_override(Some(true));
+///     let style = colorparse::parse("blue ul").unwrap();
+///     let s = format!("{} 日本", colorparse::paint(style, "link"));
+///     assert_eq!(colorparse::visible_width(&s), 9);
+pub fn visible_width(s: &str) -> usize {
+    text_runs(s).map(UnicodeWidthStr::width).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strip_ansi() {
+        assert_eq!(strip_ansi("plain"), "plain");
+        assert_eq!(strip_ansi("\x1b[1;31mred\x1b[0m text"), "red text");
+        assert_eq!(strip_ansi("\x1b]8;;https://example.com\x1b\\link\x1b]8;;\x07!"), "link!");
+        assert_eq!(strip_ansi("a\x1b7b\x1b8c"), "abc");
+        assert_eq!(strip_ansi("x\x1b[38;2;1;2;3"), "x");
+        assert_eq!(strip_ansi("x\x1b"), "x");
+        assert_eq!(strip_ansi("\x1b]0;unterminated"), "");
+    }
+
+    #[test]
+    fn test_visible_width() {
+        assert_eq!(visible_width(""), 0);
+        assert_eq!(visible_width("\x1b[32mabc\x1b[0m"), 3);
+        assert_eq!(visible_width("\x1b[1m漢字\x1b[0m"), 4);
+        assert_eq!(visible_width("e\u{301}"), 1);
+    }
+}
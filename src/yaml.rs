@@ -0,0 +1,258 @@
+use std::iter::Peekable;
+
+/// A value in the subset of YAML that `parse` reads.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum Value {
+    /// A key with no value, or a value of `~` or `null`.
+    Null,
+    /// A plain, single-quoted, or double-quoted scalar, as a string.
+    Scalar(String),
+    /// A block mapping, nested under its key by indentation.
+    Mapping(Vec<Entry>),
+}
+
+/// A key of a mapping, with the line it is on and its value.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Entry {
+    pub(crate) line: usize,
+    pub(crate) key: String,
+    pub(crate) value: Value,
+}
+
+/// A `key: value` line: its line number, indentation, key, and the value
+/// after the `:`, or `None` for a key with nothing after it.
+struct Line {
+    number: usize,
+    indent: usize,
+    key: String,
+    value: Option<Value>,
+}
+
+/// Return an error for the YAML feature that `text` starts with, if
+/// `parse` does not support it.
+fn unsupported(text: &str) -> Option<&'static str> {
+    let mut chars = text.chars();
+    let first = chars.next()?;
+    let spaced = chars.next().is_none_or(|c| c == ' ');
+    match first {
+        '[' | '{' => Some("flow collections are not supported"),
+        '|' | '>' => Some("block scalars are not supported"),
+        '&' | '*' => Some("anchors and aliases are not supported"),
+        '!' => Some("tags are not supported"),
+        '%' | '@' | '`' => Some("reserved indicators are not supported"),
+        '-' if spaced => Some("sequences are not supported"),
+        '?' if spaced => Some("complex keys are not supported"),
+        _ => None,
+    }
+}
+
+/// Parse the quoted scalar at the start of `text`, which starts with `'`
+/// or `"`, returning it and the text after its closing quote.
+fn quoted(text: &str) -> Result<(String, &str), &'static str> {
+    let quote = text.chars().next().unwrap_or('"');
+    let mut value = String::new();
+    let mut chars = text.char_indices().skip(1);
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '\'' if quote == '\'' => {
+                if text[i + 1..].starts_with('\'') {
+                    chars.next();
+                    value.push('\'');
+                } else {
+                    return Ok((value, &text[i + 1..]));
+                }
+            }
+            '"' if quote == '"' => return Ok((value, &text[i + 1..])),
+            '\\' if quote == '"' => match chars.next().map(|(_, c)| c) {
+                Some('n') => value.push('\n'),
+                Some('t') => value.push('\t'),
+                Some('r') => value.push('\r'),
+                Some('0') => value.push('\0'),
+                Some(c @ '"') | Some(c @ '\\') | Some(c @ '/') => value.push(c),
+                _ => return Err("unknown escape in a double-quoted scalar"),
+            },
+            c => value.push(c),
+        }
+    }
+    Err("unterminated quoted scalar")
+}
+
+/// Parse the value after a key's `:`, which is not empty.
+fn value(text: &str) -> Result<Option<Value>, &'static str> {
+    if text.starts_with('#') {
+        return Ok(None);
+    }
+    if text.starts_with('"') || text.starts_with('\'') {
+        let (value, rest) = quoted(text)?;
+        let rest = rest.trim_start();
+        if !rest.is_empty() && !rest.starts_with('#') {
+            return Err("unexpected text after a quoted scalar");
+        }
+        return Ok(Some(Value::Scalar(value)));
+    }
+    if let Some(message) = unsupported(text) {
+        return Err(message);
+    }
+    let value = text.split(" #").next().unwrap_or("").trim_end();
+    if value.contains(": ") || value.ends_with(':') {
+        return Err("unexpected \":\" in a plain scalar");
+    }
+    Ok(Some(match value {
+        "~" | "null" | "Null" | "NULL" => Value::Null,
+        _ => Value::Scalar(value.to_string()),
+    }))
+}
+
+/// Parse the `key: value` line `content`, indented by `indent` spaces.
+fn line(number: usize, indent: usize, content: &str) -> Result<Line, String> {
+    let err = |message: &str| format!("line {}: {}", number, message);
+    let (key, rest) = if content.starts_with('"') || content.starts_with('\'') {
+        let (key, rest) = quoted(content).map_err(err)?;
+        let rest = rest.trim_start();
+        match rest.strip_prefix(':') {
+            Some(rest) if rest.is_empty() || rest.starts_with(' ') => (key, rest),
+            _ => return Err(err("expected \"key: value\"")),
+        }
+    } else {
+        if let Some(message) = unsupported(content) {
+            return Err(err(message));
+        }
+        let colon = content.char_indices().find(|&(i, c)| c == ':' && content[i + 1..].chars().next().is_none_or(|c| c == ' '));
+        match colon {
+            Some((i, _)) if i > 0 => (content[..i].trim_end().to_string(), &content[i + 1..]),
+            _ => return Err(err("expected \"key: value\"")),
+        }
+    };
+    let rest = rest.trim_start();
+    let value = if rest.is_empty() { None } else { value(rest).map_err(err)? };
+    Ok(Line { number, indent, key, value })
+}
+
+/// Parse the lines of a mapping indented by `indent` spaces.
+fn mapping<I: Iterator<Item = Line>>(lines: &mut Peekable<I>, indent: usize) -> Result<Vec<Entry>, String> {
+    let mut entries: Vec<Entry> = Vec::new();
+    while let Some(next) = lines.next_if(|next| next.indent >= indent) {
+        if next.indent > indent {
+            return Err(format!("line {}: unexpected indentation", next.number));
+        }
+        if entries.iter().any(|entry| entry.key == next.key) {
+            return Err(format!("line {}: duplicate key \"{}\"", next.number, next.key));
+        }
+        let value = match next.value {
+            Some(value) => value,
+            None => match lines.peek().map(|child| child.indent).filter(|&child| child > indent) {
+                Some(child) => Value::Mapping(mapping(lines, child)?),
+                None => Value::Null,
+            },
+        };
+        entries.push(Entry { line: next.number, key: next.key, value });
+    }
+    Ok(entries)
+}
+
+/// Parse a YAML document made of block mappings, returning the entries of
+/// its top-level mapping, or a description of the first problem, starting
+/// with its line number.
+///
+/// Keys and scalars may be plain, single-quoted, or double-quoted, a
+/// mapping nests under a key by indentation, and `#` starts a comment.
+/// Anything else YAML allows, such as flow collections, sequences, block
+/// scalars, anchors, aliases, tags, or a second document, is rejected
+/// rather than misread.
+pub(crate) fn parse(s: &str) -> Result<Vec<Entry>, String> {
+    let mut lines = Vec::new();
+    for (i, text) in s.lines().enumerate() {
+        let number = i + 1;
+        let content = text.trim_start_matches(' ');
+        let indent = text.len() - content.len();
+        if content.starts_with('\t') {
+            return Err(format!("line {}: tabs are not allowed in indentation", number));
+        }
+        let content = content.trim_end();
+        if content.is_empty() || content.starts_with('#') {
+            continue;
+        }
+        if content == "---" && indent == 0 {
+            if !lines.is_empty() {
+                return Err(format!("line {}: multiple documents are not supported", number));
+            }
+            continue;
+        }
+        lines.push(line(number, indent, content)?);
+    }
+    let mut lines = lines.into_iter().peekable();
+    let indent = lines.peek().map_or(0, |first| first.indent);
+    let entries = mapping(&mut lines, indent)?;
+    match lines.next() {
+        Some(next) => Err(format!("line {}: unexpected indentation", next.number)),
+        None => Ok(entries),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(line: usize, key: &str, value: Value) -> Entry {
+        Entry { line, key: key.to_string(), value }
+    }
+
+    fn scalar(s: &str) -> Value {
+        Value::Scalar(s.to_string())
+    }
+
+    #[test]
+    fn test_parse() {
+        let entries = parse(r#"---
+# comment
+error: bold red  # trailing
+diff:
+    "added": 'green ''ul'''
+    removed: "red\tbold \"x\""
+
+    moved:
+      old: dim
+url: http://example.com/a#b
+empty:
+none: ~
+"#);
+        assert_eq!(entries, Ok(vec![
+            entry(3, "error", scalar("bold red")),
+            entry(4, "diff", Value::Mapping(vec![
+                entry(5, "added", scalar("green 'ul'")),
+                entry(6, "removed", scalar("red\tbold \"x\"")),
+                entry(8, "moved", Value::Mapping(vec![entry(9, "old", scalar("dim"))])),
+            ])),
+            entry(10, "url", scalar("http://example.com/a#b")),
+            entry(11, "empty", Value::Null),
+            entry(12, "none", Value::Null),
+        ]));
+        assert_eq!(parse("  a: b\n  c: d"), Ok(vec![entry(1, "a", scalar("b")), entry(2, "c", scalar("d"))]));
+        assert_eq!(parse(""), Ok(vec![]));
+    }
+
+    #[test]
+    fn test_parse_err() {
+        let err = |s| parse(s).unwrap_err();
+        assert_eq!(err("a: [red, blue]"), "line 1: flow collections are not supported");
+        assert_eq!(err("a:\n  {b: c}"), "line 2: flow collections are not supported");
+        assert_eq!(err("a: |\n  red"), "line 1: block scalars are not supported");
+        assert_eq!(err("a: >-\n  red"), "line 1: block scalars are not supported");
+        assert_eq!(err("a: &x red\nb: *x"), "line 1: anchors and aliases are not supported");
+        assert_eq!(err("a: !!str red"), "line 1: tags are not supported");
+        assert_eq!(err("a:\n  - red"), "line 2: sequences are not supported");
+        assert_eq!(err("? a\n: b"), "line 1: complex keys are not supported");
+        assert_eq!(err("a: b\n---\nc: d"), "line 2: multiple documents are not supported");
+        assert_eq!(err("a: b\n\tc: d"), "line 2: tabs are not allowed in indentation");
+        assert_eq!(err("a: red\n  blue"), "line 2: expected \"key: value\"");
+        assert_eq!(err("a: red\n  b: blue"), "line 2: unexpected indentation");
+        assert_eq!(err("a:\n    b: c\n  d: e"), "line 3: unexpected indentation");
+        assert_eq!(err("  a: b\nc: d"), "line 2: unexpected indentation");
+        assert_eq!(err("a: b: c"), "line 1: unexpected \":\" in a plain scalar");
+        assert_eq!(err("a: \"red"), "line 1: unterminated quoted scalar");
+        assert_eq!(err("a: \"\\q\""), "line 1: unknown escape in a double-quoted scalar");
+        assert_eq!(err("a: 'red' blue"), "line 1: unexpected text after a quoted scalar");
+        assert_eq!(err("a: b\na: c"), "line 2: duplicate key \"a\"");
+        assert_eq!(err(": b"), "line 1: expected \"key: value\"");
+    }
+}